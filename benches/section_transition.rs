@@ -0,0 +1,75 @@
+//! Measures the heap allocations and time spent streaming sections in and out
+//! as the Agent walks in a straight line, crossing one section boundary per
+//! step. Nothing has been collected, so the items of every section are
+//! streamed in with it.
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	collections::HashSet,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::{Duration, Instant},
+};
+
+use kiss3d::window::Window;
+use little_game::{game::Chunks, init_resources};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const SEED: u64 = 0;
+const TRANSITIONS: i64 = 200;
+const RANGE: i64 = 2;
+
+fn main() {
+	let mut window = Window::new_hidden("Section transition benchmark");
+	init_resources();
+	let collected_items = HashSet::new();
+	let mut chunks = Chunks::new(SEED, RANGE);
+
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	chunks.update(&mut window, (0, 0), &collected_items);
+	println!(
+		"Initial load: {} allocations in {:?}",
+		ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+		start.elapsed()
+	);
+
+	let mut items = 0;
+	let mut elapsed = Duration::default();
+	let mut allocations = 0;
+	for step in 1..=TRANSITIONS {
+		let before = ALLOCATIONS.load(Ordering::Relaxed);
+		let start = Instant::now();
+		chunks.update(&mut window, (0, step), &collected_items);
+		elapsed += start.elapsed();
+		allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+		items += chunks
+			.iter_mut()
+			.map(|(_, chunk)| chunk.items.len())
+			.sum::<usize>();
+	}
+	println!(
+		"Per section transition: {:.1} allocations in {:?}, with {:.1} items in range",
+		allocations as f64 / TRANSITIONS as f64,
+		elapsed / TRANSITIONS as u32,
+		items as f64 / TRANSITIONS as f64
+	);
+
+	chunks.clear(&mut window);
+}
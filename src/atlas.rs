@@ -0,0 +1,267 @@
+//! Pictures of the generated world seen from above, for looking at it
+//! outside of the game.
+
+use std::ops::RangeInclusive;
+
+use super::{
+	game::{SectionLayout, WallSlot, WorldCell},
+	map::{Position, ROOM_SIZE},
+	meshes::{key_color, ItemKind},
+	rng::WorldParams,
+	text::generate_name,
+};
+
+const WALL_COLOR: [u8; 3] = [24, 24, 24];
+const MONSTER_COLOR: [u8; 3] = [200, 0, 200];
+const LOCK_COLOR: [u8; 3] = [220, 30, 30];
+const COIN_COLOR: [u8; 3] = [230, 230, 230];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Tile {
+	/// Where walls meet, drawn as a wall whether there are any or not.
+	Corner,
+	Wall,
+	/// An open place between two cells, coloured like its section.
+	Passage([u8; 3]),
+	/// An open place between two cells where a monster is waiting.
+	Monster,
+	/// A door that opens with the key of its tier.
+	Door(usize),
+	Cell([u8; 3], Option<ItemKind>),
+}
+
+/// A rectangle of sections stitched together, with north, the way the Agent
+/// starts out facing, up. The columns of sections run from left to right and
+/// their rows from bottom to top.
+///
+/// Made of tiles, where every other tile is a cell and the ones between them
+/// are the walls, or openings, separating them.
+pub struct Atlas {
+	seed: u64,
+	params: WorldParams,
+	width: usize,
+	height: usize,
+	tiles: Vec<Tile>,
+	names: Vec<((i64, i64), String)>,
+}
+
+impl Atlas {
+	/// The atlas of the sections in the rectangle with `corner` and `other`
+	/// at opposite corners, including the sections at both of them.
+	pub fn generate(
+		seed: u64,
+		params: &WorldParams,
+		corner: (i64, i64),
+		other: (i64, i64),
+	) -> Self {
+		let rows = span(corner.0, other.0);
+		let cols = span(corner.1, other.1);
+		let cells = |sections: &RangeInclusive<i64>| {
+			sections.start() * ROOM_SIZE as i64..=(sections.end() + 1) * ROOM_SIZE as i64 - 1
+		};
+		let (xs, zs) = (cells(&cols), cells(&rows));
+		let width = 2 * (xs.end() - xs.start() + 1) as usize + 1;
+		let height = 2 * (zs.end() - zs.start() + 1) as usize + 1;
+		let mut atlas = Self {
+			seed,
+			params: *params,
+			width,
+			height,
+			tiles: vec![Tile::Corner; width * height],
+			names: Vec::new(),
+		};
+
+		for row in rows.clone().rev() {
+			for col in cols.clone() {
+				let section = (row, col);
+				let layout = SectionLayout::generate_with_params(seed, params, section, true);
+				for i in 0..ROOM_SIZE * ROOM_SIZE {
+					let pos = Position(i / ROOM_SIZE, i % ROOM_SIZE);
+					let cell = WorldCell::new(section, pos);
+					let tx = 2 * (cell.x - xs.start()) as usize + 1;
+					let ty = 2 * (zs.end() - cell.z) as usize + 1;
+					atlas.draw_cell(&layout, pos, tx, ty);
+				}
+				let mut name = String::new();
+				generate_name(seed, section, &mut name);
+				atlas.names.push((section, name));
+			}
+		}
+		atlas
+	}
+
+	/// Draws the cell at `pos` in `layout` at tile `(tx, ty)`, along with the
+	/// walls on its north and east sides. The westmost and southmost cells
+	/// also draw the walls on their other sides, since there are no cells
+	/// there to do it.
+	fn draw_cell(&mut self, layout: &SectionLayout, pos: Position, tx: usize, ty: usize) {
+		let color = rgb(layout.color);
+		let item = layout
+			.items
+			.iter()
+			.find(|(p, _)| *p == pos)
+			.map(|(_, kind)| *kind);
+		self.set(tx, ty, Tile::Cell(color, item));
+		self.set(tx, ty - 1, wall_tile(layout, WallSlot::Right(pos)));
+		self.set(tx + 1, ty, wall_tile(layout, WallSlot::Below(pos)));
+		if tx == 1 {
+			self.set(tx - 1, ty, wall_tile(layout, WallSlot::Above(pos.1)));
+		}
+		if ty + 2 == self.height {
+			self.set(tx, ty + 1, wall_tile(layout, WallSlot::Left(pos.0)));
+		}
+	}
+
+	fn set(&mut self, tx: usize, ty: usize, tile: Tile) {
+		self.tiles[ty * self.width + tx] = tile;
+	}
+
+	fn get(&self, tx: usize, ty: usize) -> Tile {
+		self.tiles[ty * self.width + tx]
+	}
+
+	/// Names of the sections, from the north-west one, a row at a time.
+	pub fn names(&self) -> &[((i64, i64), String)] {
+		&self.names
+	}
+
+	/// The atlas drawn with `+` corners, `-` and `|` walls, `m` for monsters,
+	/// and the tier of locked doors. Cells are three characters wide and hold
+	/// `K` and its tier for keys, `L` for the lock, and `c` for coins.
+	pub fn to_ascii(&self) -> String {
+		let mut ascii = String::new();
+		for ty in 0..self.height {
+			for tx in 0..self.width {
+				let wide = tx % 2 == 1;
+				let text: String = match (self.get(tx, ty), wide) {
+					(Tile::Corner, _) => "+".into(),
+					(Tile::Wall, true) => "---".into(),
+					(Tile::Wall, false) => "|".into(),
+					(Tile::Passage(_), true) => "   ".into(),
+					(Tile::Passage(_), false) => " ".into(),
+					(Tile::Monster, true) => " m ".into(),
+					(Tile::Monster, false) => "m".into(),
+					(Tile::Door(tier), true) => format!("={}=", tier % 10),
+					(Tile::Door(tier), false) => (tier % 10).to_string(),
+					(Tile::Cell(_, Some(ItemKind::Key(tier))), _) => format!("K{} ", tier % 10),
+					(Tile::Cell(_, Some(ItemKind::Lock)), _) => " L ".into(),
+					(Tile::Cell(_, Some(ItemKind::Coin)), _) => " c ".into(),
+					(Tile::Cell(_, None), _) => "   ".into(),
+				};
+				ascii.push_str(&text);
+			}
+			ascii.push('\n');
+		}
+		ascii
+	}
+
+	/// The atlas as rows of RGB pixels, with cells `cell_size` pixels wide
+	/// and walls a quarter of that. Returns the width and height in pixels
+	/// along with the pixels.
+	pub fn to_rgb(&self, cell_size: usize) -> (usize, usize, Vec<u8>) {
+		let wall_size = (cell_size / 4).max(1);
+		let size = |t: usize| if t % 2 == 1 { cell_size } else { wall_size };
+		let starts = |tiles: usize| {
+			(0..tiles)
+				.scan(0, |start, t| {
+					let s = *start;
+					*start += size(t);
+					Some(s)
+				})
+				.collect::<Vec<_>>()
+		};
+		let (xs, ys) = (starts(self.width), starts(self.height));
+		let width = xs[self.width - 1] + wall_size;
+		let height = ys[self.height - 1] + wall_size;
+
+		let mut pixels = vec![0; width * height * 3];
+		for (ty, y) in ys.iter().enumerate() {
+			for (tx, x) in xs.iter().enumerate() {
+				let (w, h) = (size(tx), size(ty));
+				let (outer, inner) = match self.get(tx, ty) {
+					Tile::Corner | Tile::Wall => (WALL_COLOR, None),
+					Tile::Passage(color) => (color, None),
+					Tile::Monster => (MONSTER_COLOR, None),
+					Tile::Door(tier) => (rgb(key_color(self.seed, &self.params, tier)), None),
+					Tile::Cell(color, item) => (color, item.map(|kind| self.item_color(kind))),
+				};
+				for py in 0..h {
+					for px in 0..w {
+						let middle =
+							(w / 4..w - w / 4).contains(&px) && (h / 4..h - h / 4).contains(&py);
+						let color = match inner {
+							Some(color) if middle => color,
+							_ => outer,
+						};
+						let i = ((y + py) * width + x + px) * 3;
+						pixels[i..i + 3].copy_from_slice(&color);
+					}
+				}
+			}
+		}
+		(width, height, pixels)
+	}
+
+	fn item_color(&self, kind: ItemKind) -> [u8; 3] {
+		match kind {
+			ItemKind::Key(tier) => rgb(key_color(self.seed, &self.params, tier)),
+			ItemKind::Lock => LOCK_COLOR,
+			ItemKind::Coin => COIN_COLOR,
+		}
+	}
+}
+
+/// The sections from `a` to `b`, or from `b` to `a`, whichever is not empty.
+pub fn span(a: i64, b: i64) -> RangeInclusive<i64> {
+	a.min(b)..=a.max(b)
+}
+
+fn wall_tile(layout: &SectionLayout, slot: WallSlot) -> Tile {
+	if let Some(door) = layout.doors.iter().find(|door| door.slot == slot) {
+		Tile::Door(door.tier)
+	} else if layout.is_closed(slot) {
+		Tile::Wall
+	} else if layout.monsters.contains(&slot) {
+		Tile::Monster
+	} else {
+		Tile::Passage(rgb(layout.color))
+	}
+}
+
+fn rgb((r, g, b): (f32, f32, f32)) -> [u8; 3] {
+	[(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn regions_are_drawn_whole() {
+		let atlas = Atlas::generate(7, &WorldParams::default(), (1, -1), (0, 1));
+		let ascii = atlas.to_ascii();
+		let lines = ascii.lines().collect::<Vec<_>>();
+		assert_eq!(lines.len(), 2 * 2 * ROOM_SIZE + 1);
+		assert!(lines
+			.iter()
+			.all(|line| line.chars().count() == 4 * 3 * ROOM_SIZE + 1));
+		assert_eq!(ascii.matches('L').count(), 1);
+		assert_eq!(atlas.names().len(), 6);
+
+		let (width, height, pixels) = atlas.to_rgb(8);
+		assert_eq!(width, 3 * ROOM_SIZE * (8 + 2) + 2);
+		assert_eq!(height, 2 * ROOM_SIZE * (8 + 2) + 2);
+		assert_eq!(pixels.len(), width * height * 3);
+	}
+
+	#[test]
+	fn neighbours_agree_on_doors() {
+		let params = WorldParams::default();
+		let whole = Atlas::generate(3, &params, (0, 0), (0, 1)).to_ascii();
+		let west = Atlas::generate(3, &params, (0, 0), (0, 0)).to_ascii();
+		let east = Atlas::generate(3, &params, (0, 1), (0, 1)).to_ascii();
+		for ((whole, west), east) in whole.lines().zip(west.lines()).zip(east.lines()) {
+			assert_eq!(whole, format!("{}{}", west, &east[1..]));
+		}
+	}
+}
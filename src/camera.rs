@@ -1,4 +1,4 @@
-use std::f32;
+use std::{f32, mem};
 
 use kiss3d::{
 	camera::Camera,
@@ -18,13 +18,16 @@ use kiss3d::{
 	window::Canvas,
 };
 
-const LOOK_STEP: f32 = 0.0025;
+/// Radians turned for every pixel the cursor moves.
+pub const LOOK_STEP: f32 = 0.0025;
 const MOVE_STEP: f32 = 0.05;
 
 pub struct FirstPerson {
 	eye: Point3<f32>,
 	yaw: f32,
 	pitch: f32,
+	/// Cursor movement since it was last taken.
+	look_delta: Vector2<f32>,
 	projection: Perspective3<f32>,
 	proj: Matrix4<f32>,
 	view: Matrix4<f32>,
@@ -49,6 +52,7 @@ impl FirstPerson {
 			eye: Point3::new(0.0, 0.0, 0.0),
 			yaw: 0.0,
 			pitch: 0.0,
+			look_delta: Vector2::zeros(),
 			projection: Perspective3::new(800.0 / 600.0, fov, znear, zfar),
 			proj: nalgebra::zero(),
 			view: nalgebra::zero(),
@@ -95,6 +99,27 @@ impl FirstPerson {
 		}
 	}
 
+	/// Yaw and pitch of the camera.
+	#[inline]
+	pub fn look_angles(&self) -> (f32, f32) {
+		(self.yaw, self.pitch)
+	}
+
+	/// Sets the yaw and pitch directly, which unlike looking at a point keeps
+	/// them exactly as they were.
+	pub fn set_look_angles(&mut self, (yaw, pitch): (f32, f32)) {
+		self.yaw = yaw;
+		self.pitch = pitch;
+		self.update_restrictions();
+		self.update_projviews();
+	}
+
+	/// Takes the cursor movement gathered from window events, leaving it to
+	/// the caller to look around with it.
+	pub fn take_look_delta(&mut self) -> Vector2<f32> {
+		mem::replace(&mut self.look_delta, Vector2::zeros())
+	}
+
 	pub fn handle_left_button_displacement(&mut self, dpos: &Vector2<f32>) {
 		self.yaw += dpos.x * LOOK_STEP;
 		self.pitch += dpos.y * LOOK_STEP;
@@ -140,6 +165,22 @@ impl FirstPerson {
 		}
 	}
 
+	#[inline]
+	pub fn fov(&self) -> f32 {
+		self.projection.fovy()
+	}
+
+	#[inline]
+	pub fn aspect(&self) -> f32 {
+		self.projection.aspect()
+	}
+
+	/// Moves the far clipping plane to `zfar`.
+	pub fn set_zfar(&mut self, zfar: f32) {
+		self.projection.set_zfar(zfar);
+		self.update_projviews();
+	}
+
 	#[inline]
 	pub fn eye(&self) -> &Point3<f32> {
 		&self.eye
@@ -174,8 +215,7 @@ impl Camera for FirstPerson {
 
 				let size = canvas.size();
 				let center = Vector2::new(size.0 as f32 / 2.0, size.1 as f32 / 2.0);
-				let dpos = curr_pos - center;
-				self.handle_left_button_displacement(&dpos);
+				self.look_delta += curr_pos - center;
 				canvas.set_cursor_position(center.x as f64, center.y as f64);
 			}
 			WindowEvent::FramebufferSize(w, h) => {
@@ -0,0 +1,249 @@
+//! Co-op, where two Agents play in the same maze through a relay. The world
+//! follows from its seed, so only what the Agents do has to be sent.
+
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{io::ErrorKind, net::TcpStream};
+
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use tungstenite::{client::IntoClientRequest, WebSocket};
+
+use super::map::Direction;
+
+/// Port the relay listens on unless told otherwise.
+pub const RELAY_PORT: u16 = 9002;
+
+/// What is sent between the Agents of a room, and from the relay to them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+	/// From the relay, to an Agent that joined a room as its `player`th.
+	Welcome { player: usize },
+	/// From the relay, when the other Agent joins the room.
+	Joined,
+	/// From the relay, when the other Agent leaves the room.
+	Left,
+	/// From the relay, to an Agent that tried to join a room of two.
+	Full,
+	/// The seed of the world, sent by the first Agent once the second joins.
+	World { seed: u64 },
+	/// Where on the floor the Agent is.
+	Position { x: f32, z: f32 },
+	/// The Agent picked up the item in the cell of `section` at row and
+	/// column `cell`. What the item was follows from the seed.
+	Collected {
+		section: (i64, i64),
+		cell: (usize, usize),
+	},
+	/// The Agent started opening the door that swings open on the border of
+	/// `section` in `direction`, as the section closer to the middle sees it.
+	Opened {
+		section: (i64, i64),
+		direction: Direction,
+	},
+	/// The Agent got out through the lock.
+	Escaped,
+}
+
+impl Message {
+	pub fn to_text(&self) -> String {
+		serde_json::to_string(self).expect("messages are always valid JSON")
+	}
+
+	pub fn from_text(text: &str) -> Option<Self> {
+		serde_json::from_str(text).ok()
+	}
+}
+
+/// A connection to a room of the relay, which is the path of its URL.
+/// Nothing waits on it after it is opened, messages are sent and received as
+/// they can.
+pub struct Connection {
+	#[cfg(not(target_arch = "wasm32"))]
+	socket: WebSocket<TcpStream>,
+	closed: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Connection {
+	pub fn open(url: &str) -> io::Result<Self> {
+		let to_io = |e: tungstenite::Error| io::Error::new(ErrorKind::Other, e.to_string());
+		let request = url.into_client_request().map_err(to_io)?;
+		let host = request
+			.uri()
+			.host()
+			.ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "the URL has no host"))?;
+		let port = request.uri().port_u16().unwrap_or(80);
+		let stream = TcpStream::connect((host, port))?;
+		stream.set_nodelay(true)?;
+		let (mut socket, _) = tungstenite::client(request, stream)
+			.map_err(|e| io::Error::new(ErrorKind::Other, e.to_string()))?;
+		socket.get_mut().set_nonblocking(true)?;
+		Ok(Self {
+			socket,
+			closed: false,
+		})
+	}
+
+	pub fn send(&mut self, message: &Message) {
+		if self.closed {
+			return;
+		}
+		let result = self
+			.socket
+			.write_message(tungstenite::Message::Text(message.to_text()));
+		self.check(result);
+	}
+
+	/// The next message that has arrived, if any.
+	pub fn receive(&mut self) -> Option<Message> {
+		while !self.closed {
+			// Whatever could not be sent before goes first.
+			let result = self.socket.write_pending();
+			self.check(result);
+			let result = self.socket.read_message();
+			match result {
+				Ok(tungstenite::Message::Text(text)) => {
+					if let Some(message) = Message::from_text(&text) {
+						return Some(message);
+					}
+				}
+				Ok(_) => {}
+				Err(e) => {
+					self.check(Err(e));
+					return None;
+				}
+			}
+		}
+		None
+	}
+
+	pub fn is_closed(&self) -> bool {
+		self.closed
+	}
+
+	/// Closes the connection on any error but not being able to go on
+	/// without waiting.
+	fn check(&mut self, result: tungstenite::Result<()>) {
+		match result {
+			Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => {}
+			Err(_) => self.closed = true,
+			Ok(()) => {}
+		}
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Connection {
+	fn drop(&mut self) {
+		if !self.closed {
+			let _ = self.socket.close(None);
+			let _ = self.socket.write_pending();
+		}
+	}
+}
+
+/// On the web, the page holds the socket. There is only ever one connection.
+#[cfg(target_arch = "wasm32")]
+impl Connection {
+	pub fn open(url: &str) -> io::Result<Self> {
+		super::js::coop_open(url);
+		Ok(Self {
+			closed: false,
+		})
+	}
+
+	pub fn send(&mut self, message: &Message) {
+		super::js::coop_send(&message.to_text());
+	}
+
+	/// The next message that has arrived, if any.
+	pub fn receive(&mut self) -> Option<Message> {
+		while let Some(text) = super::js::coop_receive() {
+			if let Some(message) = Message::from_text(&text) {
+				return Some(message);
+			}
+		}
+		self.closed = super::js::coop_is_closed();
+		None
+	}
+
+	pub fn is_closed(&self) -> bool {
+		self.closed
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for Connection {
+	fn drop(&mut self) {
+		super::js::coop_close();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn messages_round_trip() {
+		let messages = [
+			Message::Welcome {
+				player: 1,
+			},
+			Message::Joined,
+			Message::Left,
+			Message::Full,
+			Message::World {
+				seed: u64::MAX,
+			},
+			Message::Position {
+				x: -1.5,
+				z: 0.25,
+			},
+			Message::Collected {
+				section: (-3, 7),
+				cell: (1, 4),
+			},
+			Message::Opened {
+				section: (0, -2),
+				direction: Direction::Left,
+			},
+			Message::Escaped,
+		];
+		for message in messages.iter() {
+			assert_eq!(
+				Message::from_text(&message.to_text()).as_ref(),
+				Some(message)
+			);
+		}
+	}
+
+	#[test]
+	fn messages_are_tagged_json() {
+		assert_eq!(
+			Message::Collected {
+				section: (-3, 7),
+				cell: (1, 4),
+			}
+			.to_text(),
+			r#"{"type":"collected","section":[-3,7],"cell":[1,4]}"#
+		);
+		assert_eq!(
+			Message::Opened {
+				section: (2, 0),
+				direction: Direction::Up,
+			}
+			.to_text(),
+			r#"{"type":"opened","section":[2,0],"direction":"up"}"#
+		);
+		assert_eq!(
+			Message::from_text(r#"{"type":"world","seed":42}"#),
+			Some(Message::World {
+				seed: 42,
+			})
+		);
+		assert_eq!(Message::from_text(r#"{"type":"teleport"}"#), None);
+		assert_eq!(Message::from_text("not json"), None);
+	}
+}
@@ -0,0 +1,156 @@
+//! The Daily Maze, a world everyone gets to play on the same day.
+
+use std::fmt;
+
+use super::{stats::DailyResult, GAME_NAME};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A day in the Gregorian calendar.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Date {
+	pub year: i64,
+	pub month: u32,
+	pub day: u32,
+}
+
+impl Date {
+	/// The current date in UTC.
+	pub fn today() -> Self {
+		Self::from_days((unix_seconds() / SECONDS_PER_DAY) as i64)
+	}
+
+	/// The date `days` days after the first of January 1970.
+	pub fn from_days(days: i64) -> Self {
+		// Counts in eras of 400 years starting on the first of March, which puts
+		// leap days at the end of their years.
+		let days = days + 719_468;
+		let era = days.div_euclid(146_097);
+		let day_of_era = days.rem_euclid(146_097);
+		let year_of_era =
+			(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month_from_march = (5 * day_of_year + 2) / 153;
+		let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+		let month = if month_from_march < 10 {
+			month_from_march + 3
+		} else {
+			month_from_march - 9
+		} as u32;
+		let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+		Self {
+			year,
+			month,
+			day,
+		}
+	}
+
+	/// Number of days after the first of January 1970.
+	pub fn days(&self) -> i64 {
+		let year = self.year - if self.month <= 2 { 1 } else { 0 };
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let month_from_march = (self.month as i64 + 9) % 12;
+		let day_of_year = (153 * month_from_march + 2) / 5 + self.day as i64 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		era * 146_097 + day_of_era - 719_468
+	}
+
+	/// The seed of the world of this day. This must never change, or results
+	/// shared on the same day would be from different worlds.
+	pub fn seed(&self) -> u64 {
+		// The finalizer of SplitMix64, so that days next to each other get
+		// seeds far apart.
+		let mut z = (self.days() as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+}
+
+impl fmt::Display for Date {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+	}
+}
+
+/// A line to share the result of a Daily Maze run with.
+pub fn share_text(date: &Date, result: &DailyResult) -> String {
+	let seconds = result.seconds();
+	format!(
+		"{} {}: {} {}, {}:{:02}, {} sections",
+		GAME_NAME,
+		date,
+		result.coins,
+		if result.coins == 1 { "coin" } else { "coins" },
+		seconds / 60,
+		seconds % 60,
+		result.sections,
+	)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds() -> u64 {
+	use std::time::{SystemTime, UNIX_EPOCH};
+
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unix_seconds() -> u64 {
+	(super::js::date_now() / 1000.0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date(year: i64, month: u32, day: u32) -> Date {
+		Date {
+			year,
+			month,
+			day,
+		}
+	}
+
+	#[test]
+	fn days_count_from_1970() {
+		let dates = [
+			(0, date(1970, 1, 1)),
+			(-1, date(1969, 12, 31)),
+			(18_779, date(2021, 6, 1)),
+			(19_782, date(2024, 2, 29)),
+			(20_745, date(2026, 10, 19)),
+		];
+		for (days, date) in dates.iter() {
+			assert_eq!(Date::from_days(*days), *date);
+			assert_eq!(date.days(), *days);
+		}
+		for days in -1000..100_000 {
+			assert_eq!(Date::from_days(days).days(), days);
+		}
+	}
+
+	#[test]
+	fn seeds_never_change() {
+		assert_eq!(date(1970, 1, 1).seed(), 0xe220_a839_7b1d_cdaf);
+		assert_eq!(date(2021, 6, 1).seed(), 0x008f_8fdd_f0c7_00f3);
+		assert_eq!(date(2024, 2, 29).seed(), 0xdb8c_04ca_25f6_e90d);
+		assert_eq!(date(2026, 10, 19).seed(), 0x5595_2def_191c_203c);
+	}
+
+	#[test]
+	fn share_text_has_the_whole_result() {
+		let result = DailyResult {
+			coins: 3,
+			ticks: 60 * 125,
+			sections: 14,
+		};
+		assert_eq!(
+			share_text(&date(2021, 6, 1), &result),
+			"Little Maze 2021-06-01: 3 coins, 2:05, 14 sections"
+		);
+	}
+}
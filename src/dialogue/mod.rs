@@ -0,0 +1,518 @@
+//! What base tells the Agent before and after runs, and over the radio during
+//! them. It is written in `dialogue.json`, as scripts of pages that branch on
+//! how the run went, so that it can be changed without touching the game. The
+//! text of the pages is in the messages of each language.
+
+use std::{
+	cell::RefCell,
+	collections::{HashMap, HashSet},
+	fmt,
+	rc::Rc,
+};
+
+use serde::Deserialize;
+
+use super::{
+	game::{RunStats, TICKS_PER_SECOND},
+	locale::tr,
+};
+
+const BUILT_IN: &str = include_str!("./dialogue.json");
+/// The script told before a run.
+pub const INTRO: &str = "intro";
+/// The script told after a run.
+pub const DEBRIEF: &str = "debrief";
+/// Scripts the game can not do without.
+const REQUIRED_SCRIPTS: [&str; 2] = [INTRO, DEBRIEF];
+
+thread_local! {
+	static DIALOGUE: RefCell<Rc<Dialogue>> = RefCell::new(Rc::new(
+		Dialogue::parse(BUILT_IN).expect("the built-in dialogue is valid")
+	));
+}
+
+pub fn dialogue() -> Rc<Dialogue> {
+	DIALOGUE.with(|d| d.borrow().clone())
+}
+
+/// Tells `dialogue` instead of the built-in one.
+pub fn set_dialogue(dialogue: Dialogue) {
+	DIALOGUE.with(|d| *d.borrow_mut() = Rc::new(dialogue));
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(from = "Layout")]
+pub struct Dialogue {
+	/// All scripts, by name.
+	scripts: HashMap<String, Script>,
+	/// What base says over the radio during runs, and when.
+	radio: Vec<Transmission>,
+}
+
+/// The ways `dialogue.json` has been laid out.
+#[derive(Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum Layout {
+	Radio {
+		scripts: HashMap<String, Script>,
+		#[serde(default)]
+		radio: Vec<Transmission>,
+	},
+	/// Before there was radio, there were only the scripts.
+	Scripts(HashMap<String, Script>),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+	/// Name of the first page.
+	start: String,
+	pages: HashMap<String, Page>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Page {
+	/// Id of the message the page says, which is said as it is if there is
+	/// no such message. Pages without one only lead on to others. Called
+	/// `text` before the pages were translated.
+	#[serde(default, alias = "text")]
+	message: String,
+	/// Where to go after this page, the first branch whose condition holds
+	/// being taken. The script ends when none does.
+	#[serde(default)]
+	next: Vec<Branch>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Branch {
+	#[serde(default)]
+	when: Condition,
+	to: String,
+}
+
+/// A script told over the radio when something happens in a run.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Transmission {
+	on: Event,
+	#[serde(default)]
+	when: Condition,
+	/// Whether it is sent every time, rather than once a run.
+	#[serde(default)]
+	repeat: bool,
+	script: String,
+}
+
+/// What can happen in a run for base to comment on.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+	/// The run started.
+	Start,
+	/// Another whole second went by.
+	Second,
+	/// The Agent walked into a section.
+	Section,
+	/// A monster noticed the Agent.
+	Monster,
+	Key,
+	Coin,
+}
+
+/// Holds when all of its parts do, and always when it has none.
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct Condition {
+	mode: Option<Mode>,
+	escaped: Option<bool>,
+	has_key: Option<bool>,
+	#[serde(default)]
+	at_least: HashMap<Stat, u64>,
+	#[serde(default)]
+	at_most: HashMap<Stat, u64>,
+}
+
+/// The kind of run the Agent is sent on.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+	Normal,
+	Daily,
+	Timed,
+	Race,
+	Coop,
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+enum Stat {
+	Coins,
+	Keys,
+	Sections,
+	Encounters,
+	Seconds,
+	Distance,
+}
+
+/// What conditions are checked against.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Facts {
+	pub mode: Mode,
+	pub escaped: bool,
+	/// Whether the Agent has the key to the lock.
+	pub has_key: bool,
+	pub coins: u64,
+	pub keys: u64,
+	pub sections: u64,
+	/// Number of times a monster noticed the Agent.
+	pub encounters: u64,
+	pub seconds: u64,
+	/// Number of sections between the Agent and the lock.
+	pub distance: u64,
+}
+
+#[derive(Debug)]
+pub enum DialogueError {
+	Json(serde_json::Error),
+	MissingScript(String),
+	MissingPage { script: String, page: String },
+}
+
+impl From<Layout> for Dialogue {
+	fn from(layout: Layout) -> Self {
+		match layout {
+			Layout::Radio {
+				scripts,
+				radio,
+			} => Self {
+				scripts,
+				radio,
+			},
+			Layout::Scripts(scripts) => Self {
+				scripts,
+				radio: Vec::new(),
+			},
+		}
+	}
+}
+
+impl Dialogue {
+	/// Reads dialogue from JSON, checking that every page a script can go to
+	/// is there.
+	pub fn parse(json: &str) -> Result<Self, DialogueError> {
+		let dialogue: Self = serde_json::from_str(json).map_err(DialogueError::Json)?;
+		for name in REQUIRED_SCRIPTS.iter() {
+			if !dialogue.scripts.contains_key(*name) {
+				return Err(DialogueError::MissingScript(name.to_string()));
+			}
+		}
+		for transmission in dialogue.radio.iter() {
+			if !dialogue.scripts.contains_key(&transmission.script) {
+				return Err(DialogueError::MissingScript(transmission.script.clone()));
+			}
+		}
+		for (name, script) in dialogue.scripts.iter() {
+			let targets = script.pages.values().flat_map(|page| page.next.iter());
+			for page in Some(&script.start)
+				.into_iter()
+				.chain(targets.map(|b| &b.to))
+			{
+				if !script.pages.contains_key(page) {
+					return Err(DialogueError::MissingPage {
+						script: name.clone(),
+						page: page.clone(),
+					});
+				}
+			}
+		}
+		Ok(dialogue)
+	}
+
+	/// The pages of `script` that are told given `facts`, in order and in the
+	/// current language. Nothing if there is no such script.
+	pub fn tell(&self, script: &str, facts: &Facts) -> Vec<String> {
+		let script = match self.scripts.get(script) {
+			Some(script) => script,
+			None => return Vec::new(),
+		};
+		let mut told = Vec::new();
+		let mut visited = HashSet::new();
+		let mut name = Some(&script.start);
+		// A page that was already told would lead the same way again.
+		while let Some(page) = name.filter(|name| visited.insert(*name)) {
+			let page = &script.pages[page];
+			if !page.message.is_empty() {
+				told.push(tr(&page.message));
+			}
+			name = page
+				.next
+				.iter()
+				.find(|branch| branch.when.holds(facts))
+				.map(|branch| &branch.to);
+		}
+		told
+	}
+
+	/// What base says over the radio on `event` given `facts`. The indices
+	/// of transmissions sent once a run are kept in `sent`.
+	pub fn radio(&self, event: Event, facts: &Facts, sent: &mut HashSet<usize>) -> Vec<String> {
+		let mut told = Vec::new();
+		for (i, transmission) in self.radio.iter().enumerate() {
+			if transmission.on == event
+				&& transmission.when.holds(facts)
+				&& (transmission.repeat || sent.insert(i))
+			{
+				told.extend(self.tell(&transmission.script, facts));
+			}
+		}
+		told
+	}
+}
+
+impl Condition {
+	fn holds(&self, facts: &Facts) -> bool {
+		self.mode.map_or(true, |mode| mode == facts.mode)
+			&& self
+				.escaped
+				.map_or(true, |escaped| escaped == facts.escaped)
+			&& self
+				.has_key
+				.map_or(true, |has_key| has_key == facts.has_key)
+			&& self
+				.at_least
+				.iter()
+				.all(|(stat, value)| facts.get(*stat) >= *value)
+			&& self
+				.at_most
+				.iter()
+				.all(|(stat, value)| facts.get(*stat) <= *value)
+	}
+}
+
+impl Facts {
+	/// The facts before a run of `mode` has started.
+	pub fn before(mode: Mode) -> Self {
+		Self {
+			mode,
+			escaped: false,
+			has_key: false,
+			coins: 0,
+			keys: 0,
+			sections: 0,
+			encounters: 0,
+			seconds: 0,
+			distance: 0,
+		}
+	}
+
+	/// The facts of a run of `mode` that went as `stats`, with the Agent
+	/// `distance` sections from the lock.
+	pub fn after(mode: Mode, stats: &RunStats, distance: u64) -> Self {
+		Self {
+			mode,
+			escaped: stats.escaped,
+			has_key: stats.key_tick.is_some(),
+			coins: stats.coins as u64,
+			keys: stats.keys as u64,
+			sections: stats.sections_visited as u64,
+			encounters: stats.monster_encounters,
+			seconds: (stats.ticks as f32 / TICKS_PER_SECOND) as u64,
+			distance,
+		}
+	}
+
+	fn get(&self, stat: Stat) -> u64 {
+		match stat {
+			Stat::Coins => self.coins,
+			Stat::Keys => self.keys,
+			Stat::Sections => self.sections,
+			Stat::Encounters => self.encounters,
+			Stat::Seconds => self.seconds,
+			Stat::Distance => self.distance,
+		}
+	}
+}
+
+impl fmt::Display for DialogueError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Json(e) => write!(f, "{}", e),
+			Self::MissingScript(script) => write!(f, "there is no {} script", script),
+			Self::MissingPage {
+				script,
+				page,
+			} => write!(f, "the {} script has no page {}", script, page),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::{
+			locale::Language,
+			settings::{set_settings, settings, Settings},
+		},
+		*,
+	};
+
+	fn after(mode: Mode, escaped: bool, coins: u64) -> Facts {
+		Facts {
+			escaped,
+			coins,
+			..Facts::before(mode)
+		}
+	}
+
+	#[test]
+	fn built_in_dialogue_is_valid() {
+		let dialogue = Dialogue::parse(BUILT_IN).unwrap();
+		let intro = dialogue.tell(INTRO, &Facts::before(Mode::Normal));
+		assert_eq!(intro.first().map(String::as_str), Some("Wake up Agent!"));
+		assert_eq!(intro.last().map(String::as_str), Some("Good luck!"));
+		let timed_intro = dialogue.tell(INTRO, &Facts::before(Mode::Timed));
+		assert_eq!(timed_intro.len(), intro.len() + 1);
+		let pages = dialogue.scripts.values().flat_map(|s| s.pages.values());
+		for page in pages.filter(|page| !page.message.is_empty()) {
+			assert_ne!(
+				tr(&page.message),
+				page.message,
+				"{} is missing",
+				page.message
+			);
+		}
+	}
+
+	#[test]
+	fn pages_are_told_in_the_language() {
+		let dialogue = Dialogue::parse(BUILT_IN).unwrap();
+		let tell = |language| {
+			set_settings(Settings {
+				language,
+				..settings()
+			});
+			dialogue.tell(INTRO, &Facts::before(Mode::Normal))
+		};
+		let swedish = tell(Language::Swedish);
+		let english = tell(Language::English);
+		assert_eq!(swedish.first().map(String::as_str), Some("Vakna Agent!"));
+		assert_eq!(swedish.len(), english.len());
+	}
+
+	#[test]
+	fn debriefs_follow_the_run() {
+		let dialogue = Dialogue::parse(BUILT_IN).unwrap();
+		let debrief = |facts| dialogue.tell(DEBRIEF, &facts).join("\n");
+		assert_eq!(
+			debrief(after(Mode::Normal, true, 0)),
+			"At least you made it back alive Agent"
+		);
+		assert!(debrief(after(Mode::Normal, true, 1)).contains("a coin"));
+		assert!(debrief(after(Mode::Normal, true, 5)).contains("these coins"));
+		assert!(debrief(after(Mode::Normal, true, 6)).contains("risk your life"));
+		assert!(debrief(after(Mode::Timed, false, 6)).contains("in time"));
+	}
+
+	#[test]
+	fn scripts_branch_on_facts() {
+		let dialogue = Dialogue::parse(
+			r#"{ "scripts": {
+				"intro": { "start": "a", "pages": {
+					"a": { "message": "A", "next": [
+						{ "when": { "mode": "race", "at_least": { "coins": 2 } }, "to": "b" },
+						{ "when": { "at_most": { "coins": 0 } }, "to": "a" },
+						{ "to": "c" }
+					] },
+					"b": { "message": "B" },
+					"c": { "next": [{ "to": "b" }] }
+				} },
+				"debrief": { "start": "a", "pages": { "a": {} } }
+			} }"#,
+		)
+		.unwrap();
+		let tell = |facts| dialogue.tell(INTRO, &facts).join("");
+		assert_eq!(tell(after(Mode::Race, true, 2)), "AB");
+		assert_eq!(tell(after(Mode::Normal, true, 2)), "AB");
+		assert_eq!(tell(after(Mode::Normal, true, 0)), "A");
+		assert!(dialogue
+			.tell(DEBRIEF, &Facts::before(Mode::Normal))
+			.is_empty());
+		assert!(dialogue
+			.tell("radio", &Facts::before(Mode::Normal))
+			.is_empty());
+	}
+
+	#[test]
+	fn broken_dialogue_is_rejected() {
+		let missing_page = r#"{ "scripts": {
+			"intro": { "start": "a", "pages": { "a": { "next": [{ "to": "b" }] } } },
+			"debrief": { "start": "a", "pages": { "a": {} } }
+		} }"#;
+		assert!(matches!(
+			Dialogue::parse(missing_page),
+			Err(DialogueError::MissingPage { .. })
+		));
+		assert!(matches!(
+			Dialogue::parse(
+				r#"{ "scripts": { "intro": { "start": "a", "pages": { "a": {} } } } }"#
+			),
+			Err(DialogueError::MissingScript(_))
+		));
+		assert!(matches!(
+			Dialogue::parse(r#"{ "scripts": {}, "radio": [{ "on": "lunch", "script": "a" }] }"#),
+			Err(DialogueError::Json(_))
+		));
+	}
+
+	#[test]
+	fn dialogue_without_radio_is_read() {
+		let dialogue = Dialogue::parse(
+			r#"{
+				"intro": { "start": "a", "pages": { "a": { "text": "Wake up" } } },
+				"debrief": { "start": "a", "pages": { "a": {} } }
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(dialogue.tell(INTRO, &Facts::before(Mode::Normal)), [
+			"Wake up"
+		]);
+		assert!(dialogue.radio.is_empty());
+	}
+
+	#[test]
+	fn radio_transmissions_follow_events() {
+		let dialogue = Dialogue::parse(
+			r#"{
+				"scripts": {
+					"intro": { "start": "a", "pages": { "a": {} } },
+					"debrief": { "start": "a", "pages": { "a": {} } },
+					"far": { "start": "a", "pages": { "a": { "message": "Far" } } },
+					"coin": { "start": "a", "pages": { "a": { "message": "Coin" } } }
+				},
+				"radio": [
+					{ "on": "section", "when": { "at_least": { "distance": 3 } }, "script": "far" },
+					{ "on": "coin", "repeat": true, "script": "coin" }
+				]
+			}"#,
+		)
+		.unwrap();
+		let mut sent = HashSet::new();
+		let near = Facts::before(Mode::Normal);
+		let far = Facts {
+			distance: 3,
+			..near
+		};
+		assert!(dialogue.radio(Event::Section, &near, &mut sent).is_empty());
+		assert_eq!(dialogue.radio(Event::Coin, &far, &mut sent), ["Coin"]);
+		assert_eq!(dialogue.radio(Event::Section, &far, &mut sent), ["Far"]);
+		assert!(dialogue.radio(Event::Section, &far, &mut sent).is_empty());
+		assert_eq!(dialogue.radio(Event::Coin, &far, &mut sent), ["Coin"]);
+
+		let built_in = Dialogue::parse(BUILT_IN).unwrap();
+		let mut sent = HashSet::new();
+		assert!(!built_in.radio(Event::Monster, &near, &mut sent).is_empty());
+		assert!(built_in.radio(Event::Monster, &near, &mut sent).is_empty());
+	}
+}
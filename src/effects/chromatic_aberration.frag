@@ -0,0 +1,19 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+uniform float intensity;
+
+void main() {
+	vec2 offset = (f_texcoord - 0.5) * intensity * 0.02;
+	vec4 color = texture2D(fbo_texture, f_texcoord);
+	float r = texture2D(fbo_texture, clamp(f_texcoord + offset, 0.0, 1.0)).r;
+	float b = texture2D(fbo_texture, clamp(f_texcoord - offset, 0.0, 1.0)).b;
+	gl_FragColor = vec4(r, color.g, b, color.a);
+}
@@ -0,0 +1,14 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+
+void main() {
+	gl_FragColor = texture2D(fbo_texture, f_texcoord);
+}
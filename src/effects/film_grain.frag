@@ -0,0 +1,22 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+uniform float intensity;
+uniform float time;
+
+float noise(vec2 co) {
+	return fract(sin(dot(co, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+void main() {
+	vec4 color = texture2D(fbo_texture, f_texcoord);
+	float grain = noise(f_texcoord + fract(time)) - 0.5;
+	gl_FragColor = vec4(color.rgb + grain * intensity * 0.5, color.a);
+}
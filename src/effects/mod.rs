@@ -0,0 +1,276 @@
+use kiss3d::{
+	context::Context,
+	nalgebra::Vector2,
+	post_processing::PostProcessingEffect,
+	resource::{
+		AllocationType,
+		BufferType,
+		Effect,
+		FramebufferManager,
+		GPUVec,
+		RenderTarget,
+		ShaderAttribute,
+		ShaderUniform,
+	},
+};
+
+use super::settings::Settings;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ScreenEffect {
+	ScreenShake,
+	ChromaticAberration,
+	Pixelation,
+	FilmGrain,
+	Vignette,
+}
+
+/// All effects, in the order they are applied.
+pub const SCREEN_EFFECTS: [ScreenEffect; 5] = [
+	ScreenEffect::ScreenShake,
+	ScreenEffect::ChromaticAberration,
+	ScreenEffect::Pixelation,
+	ScreenEffect::FilmGrain,
+	ScreenEffect::Vignette,
+];
+
+impl ScreenEffect {
+	/// Id of the message the effect is named by.
+	pub fn message(&self) -> &'static str {
+		match self {
+			Self::ScreenShake => "effect-screen-shake",
+			Self::ChromaticAberration => "effect-chromatic-aberration",
+			Self::Pixelation => "effect-pixelation",
+			Self::FilmGrain => "effect-film-grain",
+			Self::Vignette => "effect-vignette",
+		}
+	}
+
+	/// How strong the effect is with a monster `monster_proximity` close,
+	/// from zero far away to one next to the Agent, after `exposure`.
+	pub fn intensity(&self, monster_proximity: f32, exposure: f32) -> f32 {
+		let monster_proximity = monster_proximity.clamp(0.0, 1.0);
+		let exposure = exposure.clamp(0.0, 2.0) / 2.0;
+		let intensity = match self {
+			Self::ScreenShake => monster_proximity.powi(2),
+			Self::ChromaticAberration => monster_proximity,
+			Self::Pixelation => exposure - 0.5,
+			Self::FilmGrain => 0.1 + 0.2 * exposure + 0.3 * monster_proximity,
+			Self::Vignette => 0.3 + 0.3 * exposure + 0.4 * monster_proximity,
+		};
+		intensity.clamp(0.0, 1.0)
+	}
+
+	fn fragment_src(&self) -> &'static str {
+		match self {
+			Self::ScreenShake => include_str!("./screen_shake.frag"),
+			Self::ChromaticAberration => include_str!("./chromatic_aberration.frag"),
+			Self::Pixelation => include_str!("./pixelation.frag"),
+			Self::FilmGrain => include_str!("./film_grain.frag"),
+			Self::Vignette => include_str!("./vignette.frag"),
+		}
+	}
+}
+
+/// A chain of full screen effects, each rendered in its own pass. Effects
+/// that are disabled or have no intensity are skipped.
+pub struct EffectChain {
+	passes: Vec<(ScreenEffect, ScreenPass)>,
+	copy: ScreenPass,
+	targets: [RenderTarget; 2],
+	framebuffers: FramebufferManager,
+	size: (f32, f32),
+}
+
+impl EffectChain {
+	pub fn new() -> Self {
+		Self {
+			passes: SCREEN_EFFECTS
+				.iter()
+				.map(|effect| (*effect, ScreenPass::new(effect.fragment_src())))
+				.collect(),
+			copy: ScreenPass::new(COPY_FRAGMENT_SRC),
+			targets: [
+				FramebufferManager::new_render_target(800, 600, false),
+				FramebufferManager::new_render_target(800, 600, false),
+			],
+			framebuffers: FramebufferManager::new(),
+			size: (800.0, 600.0),
+		}
+	}
+
+	pub fn set_intensity(&mut self, effect: ScreenEffect, intensity: f32) {
+		if let Some((_, pass)) = self.passes.iter_mut().find(|(e, _)| *e == effect) {
+			pass.intensity = intensity.clamp(0.0, 1.0);
+		}
+	}
+
+	/// Enables or disables each effect according to the settings.
+	pub fn apply_settings(&mut self, settings: &Settings) {
+		for (effect, pass) in self.passes.iter_mut() {
+			pass.enabled = settings.is_effect_enabled(*effect);
+		}
+	}
+}
+
+impl Default for EffectChain {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PostProcessingEffect for EffectChain {
+	fn update(&mut self, dt: f32, w: f32, h: f32, _: f32, _: f32) {
+		if self.size != (w, h) {
+			self.size = (w, h);
+			for target in self.targets.iter_mut() {
+				target.resize(w, h);
+			}
+		}
+		for (_, pass) in self.passes.iter_mut() {
+			pass.update(dt, w, h);
+		}
+	}
+
+	fn draw(&mut self, target: &RenderTarget) {
+		let Self {
+			passes,
+			copy,
+			targets,
+			framebuffers,
+			..
+		} = self;
+
+		let mut active: Vec<_> = passes
+			.iter_mut()
+			.map(|(_, pass)| pass)
+			.filter(|pass| pass.is_active())
+			.collect();
+		if active.is_empty() {
+			active.push(copy);
+		}
+
+		let last = active.len() - 1;
+		for (i, pass) in active.into_iter().enumerate() {
+			if i == last {
+				framebuffers.select(&FramebufferManager::screen());
+			} else {
+				framebuffers.select(&targets[i % 2]);
+			}
+			pass.draw(if i == 0 {
+				target
+			} else {
+				&targets[(i - 1) % 2]
+			});
+		}
+	}
+}
+
+struct ScreenPass {
+	enabled: bool,
+	intensity: f32,
+	time: f32,
+	resolution: Vector2<f32>,
+	shader: Effect,
+	vertices: GPUVec<Vector2<f32>>,
+	v_coord: ShaderAttribute<Vector2<f32>>,
+	fbo_texture: ShaderUniform<i32>,
+	intensity_uniform: Option<ShaderUniform<f32>>,
+	time_uniform: Option<ShaderUniform<f32>>,
+	resolution_uniform: Option<ShaderUniform<Vector2<f32>>>,
+}
+
+impl ScreenPass {
+	fn new(fragment_src: &str) -> Self {
+		let mut vertices = GPUVec::new(
+			vec![
+				Vector2::new(-1.0, -1.0),
+				Vector2::new(1.0, -1.0),
+				Vector2::new(-1.0, 1.0),
+				Vector2::new(1.0, 1.0),
+			],
+			BufferType::Array,
+			AllocationType::StaticDraw,
+		);
+		vertices.load_to_gpu();
+		vertices.unload_from_ram();
+
+		let mut shader = Effect::new_from_str(SCREEN_VERTEX_SRC, fragment_src);
+		shader.use_program();
+		Self {
+			enabled: true,
+			intensity: 0.0,
+			time: 0.0,
+			resolution: Vector2::new(800.0, 600.0),
+			v_coord: shader.get_attrib("v_coord").unwrap(),
+			fbo_texture: shader.get_uniform("fbo_texture").unwrap(),
+			intensity_uniform: shader.get_uniform("intensity"),
+			time_uniform: shader.get_uniform("time"),
+			resolution_uniform: shader.get_uniform("resolution"),
+			vertices,
+			shader,
+		}
+	}
+
+	fn is_active(&self) -> bool {
+		self.enabled && self.intensity > 0.0
+	}
+
+	fn update(&mut self, dt: f32, w: f32, h: f32) {
+		self.time += dt;
+		self.resolution = Vector2::new(w, h);
+	}
+
+	fn draw(&mut self, target: &RenderTarget) {
+		let ctx = Context::get();
+		self.shader.use_program();
+		self.v_coord.enable();
+
+		ctx.active_texture(Context::TEXTURE0);
+		ctx.bind_texture(Context::TEXTURE_2D, target.texture_id());
+		self.fbo_texture.upload(&0);
+		if let Some(intensity) = &mut self.intensity_uniform {
+			intensity.upload(&self.intensity);
+		}
+		if let Some(time) = &mut self.time_uniform {
+			time.upload(&self.time);
+		}
+		if let Some(resolution) = &mut self.resolution_uniform {
+			resolution.upload(&self.resolution);
+		}
+
+		ctx.clear_color(0.0, 0.0, 0.0, 1.0);
+		ctx.clear(Context::COLOR_BUFFER_BIT | Context::DEPTH_BUFFER_BIT);
+
+		self.v_coord.bind(&mut self.vertices);
+		ctx.draw_arrays(Context::TRIANGLE_STRIP, 0, 4);
+
+		self.v_coord.disable();
+	}
+}
+
+const SCREEN_VERTEX_SRC: &str = include_str!("./screen.vert");
+const COPY_FRAGMENT_SRC: &str = include_str!("./copy.frag");
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn intensities_follow_monsters_and_exposure() {
+		assert_eq!(ScreenEffect::ScreenShake.intensity(0.0, 0.0), 0.0);
+		assert_eq!(ScreenEffect::ChromaticAberration.intensity(0.0, 2.0), 0.0);
+		assert_eq!(ScreenEffect::Pixelation.intensity(1.0, 0.0), 0.0);
+		assert!(
+			ScreenEffect::Vignette.intensity(0.5, 0.0) > ScreenEffect::Vignette.intensity(0.0, 0.0)
+		);
+		for effect in SCREEN_EFFECTS.iter() {
+			assert_eq!(
+				effect.intensity(1.0, 2.0),
+				effect.intensity(4.0, 9.0).min(1.0)
+			);
+			assert!(effect.intensity(4.0, 9.0) <= 1.0);
+			assert!(effect.intensity(-1.0, -1.0) >= 0.0);
+		}
+	}
+}
@@ -0,0 +1,18 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+uniform float intensity;
+uniform vec2 resolution;
+
+void main() {
+	vec2 block = (1.0 + floor(intensity * 7.0)) / resolution;
+	vec2 coord = (floor(f_texcoord / block) + 0.5) * block;
+	gl_FragColor = texture2D(fbo_texture, clamp(coord, 0.0, 1.0));
+}
@@ -0,0 +1,17 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+uniform float intensity;
+uniform float time;
+
+void main() {
+	vec2 offset = vec2(sin(time * 53.0), cos(time * 47.0)) * intensity * 0.01;
+	gl_FragColor = texture2D(fbo_texture, clamp(f_texcoord + offset, 0.0, 1.0));
+}
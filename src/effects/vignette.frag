@@ -0,0 +1,18 @@
+#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+	precision highp float;
+#else
+	precision mediump float;
+#endif
+
+varying vec2 f_texcoord;
+
+uniform sampler2D fbo_texture;
+uniform float intensity;
+
+void main() {
+	vec4 color = texture2D(fbo_texture, f_texcoord);
+	float edge = length(f_texcoord - 0.5) * 1.4142;
+	float shade = 1.0 - smoothstep(1.0 - intensity, 1.5 - intensity, edge) * intensity;
+	gl_FragColor = vec4(color.rgb * clamp(shade, 0.0, 1.0), color.a);
+}
@@ -0,0 +1,136 @@
+use std::collections::{hash_map, HashMap, VecDeque};
+
+use super::{
+	super::{
+		super::map::DIRECTIONS,
+		input::TickInput,
+		simulation::Simulation,
+		visibility::WorldCell,
+	},
+	drop_reached,
+	exits,
+	lock_cell,
+	path_to,
+	use_items,
+	walk,
+	Bot,
+};
+
+/// Explores by going to cells it has not been in, picking up everything it
+/// passes. Once it has the key to the lock it goes back to it. It only knows
+/// the ways out of cells it has been in.
+#[derive(Default)]
+pub struct FrontierExplorer {
+	/// Which ways out of each visited cell are open, in the order of
+	/// [`DIRECTIONS`].
+	exits: HashMap<WorldCell, [bool; 4]>,
+	/// Visited cells next to locked doors, to visit again once a key has
+	/// been found.
+	locked: Vec<WorldCell>,
+	/// Cells to go through, starting with the one being headed for.
+	path: VecDeque<WorldCell>,
+	keys: usize,
+}
+
+impl FrontierExplorer {
+	/// The shortest way through known cells from `start` to the lock if
+	/// `has_key`, otherwise to a cell not yet visited. The key is never far
+	/// from the lock, so cells in sections closer to the lock are explored
+	/// first, and the closest of those is picked.
+	fn plan(&self, start: WorldCell, has_key: bool) -> VecDeque<WorldCell> {
+		let mut previous = HashMap::new();
+		previous.insert(start, start);
+		let mut queue = VecDeque::new();
+		queue.push_back(start);
+		let mut frontier: Option<(i64, WorldCell)> = None;
+		while let Some(cell) = queue.pop_front() {
+			let exits = match self.exits.get(&cell) {
+				Some(exits) => exits,
+				None => {
+					let (row, col) = cell.section();
+					let ring = row.abs().max(col.abs());
+					if frontier.map_or(true, |(r, _)| ring < r) {
+						frontier = Some((ring, cell));
+					}
+					continue;
+				}
+			};
+			if has_key && cell == lock_cell() {
+				return path_to(&previous, cell);
+			}
+			for (direction, open) in DIRECTIONS.iter().zip(exits.iter()) {
+				let next = cell.step(*direction);
+				if *open && !previous.contains_key(&next) {
+					previous.insert(next, cell);
+					queue.push_back(next);
+				}
+			}
+		}
+		match frontier {
+			Some((_, cell)) if !has_key => path_to(&previous, cell),
+			_ => VecDeque::new(),
+		}
+	}
+}
+
+impl Bot for FrontierExplorer {
+	fn name(&self) -> &'static str {
+		"explorer"
+	}
+
+	fn next_input(&mut self, simulation: &Simulation) -> TickInput {
+		let eye = simulation.camera().eye();
+		let cell = WorldCell::containing(eye);
+		if simulation.keys() != self.keys {
+			self.keys = simulation.keys();
+			for cell in self.locked.drain(..) {
+				self.exits.remove(&cell);
+			}
+			self.path.clear();
+		}
+		if let hash_map::Entry::Vacant(entry) = self.exits.entry(cell) {
+			entry.insert(exits(simulation, cell));
+			if DIRECTIONS
+				.iter()
+				.any(|direction| simulation.is_locked(cell, *direction))
+			{
+				self.locked.push(cell);
+			}
+			self.path.clear();
+		}
+
+		drop_reached(&mut self.path, eye);
+		if self.path.is_empty() {
+			self.path = self.plan(cell, simulation.has_key());
+		}
+		walk(&self.path, simulation.camera(), use_items(simulation))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::{super::super::rng::WorldParams, run_bot},
+		*,
+	};
+
+	#[test]
+	fn explorer_escapes() {
+		for seed in 0..3 {
+			let run = run_bot(
+				seed,
+				WorldParams {
+					locked_rings: 1,
+					..WorldParams::default()
+				},
+				&mut FrontierExplorer::default(),
+				60_000,
+			);
+			assert!(run.stats.escaped, "{:?}", run);
+			assert!(run
+				.stats
+				.key_tick
+				.map_or(false, |tick| tick < run.stats.ticks));
+		}
+	}
+}
@@ -0,0 +1,191 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	f32,
+};
+
+use kiss3d::nalgebra::Point3;
+use serde::Serialize;
+
+use super::{
+	super::{
+		camera::{FirstPerson, LOOK_STEP},
+		map::{Direction, Position, DIRECTIONS, ROOM_CENTER},
+		meshes::ItemKind,
+		rng::WorldParams,
+	},
+	input::{Actions, TickInput},
+	simulation::{RunStats, Simulation},
+	visibility::WorldCell,
+};
+
+mod explorer;
+mod planner;
+mod wall_follower;
+
+pub use explorer::FrontierExplorer;
+pub use planner::OptimalPlanner;
+pub use wall_follower::WallFollower;
+
+/// How close to the middle of a cell a bot has to be to have reached it.
+const ARRIVAL_DISTANCE: f32 = 0.2;
+/// Furthest a bot turns in a tick, in pixels of cursor movement.
+const MAX_TURN: f32 = 120.0;
+
+/// Plays the game by deciding the input of every tick, the same input a
+/// player would give.
+pub trait Bot {
+	fn name(&self) -> &'static str;
+
+	fn next_input(&mut self, simulation: &Simulation) -> TickInput;
+
+	/// Whether the bot knows that it can not escape, which ends its run.
+	fn has_given_up(&self) -> bool {
+		false
+	}
+}
+
+/// Names of the bots that can be created with [`bot_by_name`].
+pub const BOT_NAMES: [&str; 3] = ["explorer", "wall-follower", "planner"];
+
+/// The bot called `name` on the command line.
+pub fn bot_by_name(name: &str) -> Option<Box<dyn Bot>> {
+	match name {
+		"explorer" => Some(Box::new(FrontierExplorer::default())),
+		"wall-follower" => Some(Box::new(WallFollower::default())),
+		"planner" => Some(Box::new(OptimalPlanner::default())),
+		_ => None,
+	}
+}
+
+/// How a bot did on a run.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct BotRun {
+	pub bot: &'static str,
+	pub key_distance: i64,
+	pub door_odds: f32,
+	pub generator: &'static str,
+	pub locked_rings: usize,
+	#[serde(flatten)]
+	pub stats: RunStats,
+	pub gave_up: bool,
+}
+
+/// Lets `bot` play on `seed` until it escapes, gives up, or `max_ticks` have
+/// passed.
+pub fn run_bot(seed: u64, params: WorldParams, bot: &mut dyn Bot, max_ticks: u64) -> BotRun {
+	let mut simulation = Simulation::with_params(seed, params);
+	while !simulation.has_escaped() && !bot.has_given_up() && simulation.tick() < max_ticks {
+		let input = bot.next_input(&simulation);
+		simulation.step(input);
+	}
+	BotRun {
+		bot: bot.name(),
+		key_distance: params.key_distance,
+		door_odds: params.door_odds,
+		generator: params.generator.name(),
+		locked_rings: params.locked_rings,
+		stats: simulation.stats(),
+		gave_up: bot.has_given_up(),
+	}
+}
+
+/// The cell the lock is in.
+fn lock_cell() -> WorldCell {
+	WorldCell::new((0, 0), Position(ROOM_CENTER, ROOM_CENTER))
+}
+
+/// Uses whatever item is close enough, unless it is the lock and there is no
+/// key to open it with, and opens the doors in the way.
+fn use_items(simulation: &Simulation) -> Actions {
+	let mut actions = Actions::default();
+	match simulation.nearby_item() {
+		Some(ItemKind::Lock) if !simulation.has_key() => {}
+		Some(_) => actions.insert(Actions::USE),
+		None => {}
+	}
+	if simulation.nearby_hinged_door().is_some() {
+		actions.insert(Actions::USE);
+	}
+	actions
+}
+
+/// Drops the cells at the start of `path` that the Agent has reached.
+fn drop_reached(path: &mut VecDeque<WorldCell>, eye: &Point3<f32>) {
+	while let Some(target) = path.front() {
+		if horizontal_distance(eye, &target.center()) < ARRIVAL_DISTANCE {
+			path.pop_front();
+		} else {
+			break;
+		}
+	}
+}
+
+/// Heads for the first cell of `path`, or stands still if there is none.
+fn walk(path: &VecDeque<WorldCell>, camera: &FirstPerson, actions: Actions) -> TickInput {
+	match path.front() {
+		Some(target) => steer(camera, &target.center(), actions),
+		None => TickInput {
+			actions,
+			look: (0, 0),
+		},
+	}
+}
+
+/// The ways out of `cell` that are open, in the order of [`DIRECTIONS`].
+/// Doors that swing open count as open, as bots use the doors they are up
+/// against.
+fn exits(simulation: &Simulation, cell: WorldCell) -> [bool; 4] {
+	let mut exits = [false; 4];
+	for (open, direction) in exits.iter_mut().zip(DIRECTIONS.iter()) {
+		*open = simulation.is_passable(cell, *direction);
+	}
+	exits
+}
+
+/// The way back from `goal` to where the search that filled in `previous`
+/// started, in the order it is walked.
+fn path_to(previous: &HashMap<WorldCell, WorldCell>, goal: WorldCell) -> VecDeque<WorldCell> {
+	let mut path = VecDeque::new();
+	let mut cell = goal;
+	loop {
+		path.push_front(cell);
+		match previous.get(&cell) {
+			Some(p) if *p != cell => cell = *p,
+			_ => return path,
+		}
+	}
+}
+
+/// Turns towards `target` and walks there once facing it, keeping the view
+/// level.
+fn steer(camera: &FirstPerson, target: &Point3<f32>, mut actions: Actions) -> TickInput {
+	let eye = camera.eye();
+	let (yaw, pitch) = camera.look_angles();
+	let wanted = (target.z - eye.z).atan2(target.x - eye.x);
+	let turn = (wanted - yaw + f32::consts::PI).rem_euclid(2.0 * f32::consts::PI) - f32::consts::PI;
+	let look_x = (turn / LOOK_STEP).round().clamp(-MAX_TURN, MAX_TURN);
+	let look_y = ((f32::consts::FRAC_PI_2 - pitch) / LOOK_STEP)
+		.round()
+		.clamp(-MAX_TURN, MAX_TURN);
+	if (turn / LOOK_STEP).abs() <= MAX_TURN {
+		actions.insert(Actions::FORWARD);
+	}
+	TickInput {
+		actions,
+		look: (look_x as i16, look_y as i16),
+	}
+}
+
+fn horizontal_distance(a: &Point3<f32>, b: &Point3<f32>) -> f32 {
+	((a.x - b.x).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+/// Index of `direction` in [`DIRECTIONS`].
+fn direction_index(direction: Direction) -> usize {
+	match direction {
+		Direction::Up => 0,
+		Direction::Left => 1,
+		Direction::Right => 2,
+		Direction::Down => 3,
+	}
+}
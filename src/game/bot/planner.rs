@@ -0,0 +1,163 @@
+use std::collections::{HashMap, VecDeque};
+
+use rand::rngs::StdRng;
+
+use super::{
+	super::{
+		super::{
+			map::{Direction, DIRECTIONS},
+			meshes::ItemKind,
+			rng::{rand_for_key, ring, WorldParams},
+		},
+		input::TickInput,
+		section::SectionLayout,
+		simulation::Simulation,
+		visibility::{is_open, WorldCell},
+	},
+	drop_reached,
+	lock_cell,
+	path_to,
+	use_items,
+	walk,
+	Bot,
+};
+
+/// How many sections further out than the keys can be the planner looks for
+/// a way to them.
+const PLANNING_MARGIN: i64 = 2;
+
+/// Knows the whole world from the seed, and walks the shortest way to each key
+/// in turn and from the last one to the lock. Gives up if there is no such way
+/// among the sections around the lock, which makes the seed unwinnable in
+/// practice.
+#[derive(Default)]
+pub struct OptimalPlanner {
+	/// Cells to go through, or `None` before the first tick.
+	path: Option<VecDeque<WorldCell>>,
+	gave_up: bool,
+}
+
+impl Bot for OptimalPlanner {
+	fn name(&self) -> &'static str {
+		"planner"
+	}
+
+	fn next_input(&mut self, simulation: &Simulation) -> TickInput {
+		let eye = simulation.camera().eye();
+		if self.path.is_none() {
+			let start = WorldCell::containing(eye);
+			let planned = plan(simulation.seed(), simulation.params(), start);
+			self.gave_up = planned.is_none();
+			self.path = Some(planned.unwrap_or_default());
+		}
+		let path = self.path.get_or_insert_with(VecDeque::new);
+		drop_reached(path, eye);
+		walk(path, simulation.camera(), use_items(simulation))
+	}
+
+	fn has_given_up(&self) -> bool {
+		self.gave_up
+	}
+}
+
+/// The shortest way from `start` to every key in turn and on to the lock,
+/// only going through the doors of keys already picked up.
+fn plan(seed: u64, params: &WorldParams, start: WorldCell) -> Option<VecDeque<WorldCell>> {
+	let limit = params.key_rings() + PLANNING_MARGIN;
+	let mut layouts = HashMap::new();
+	let mut path = VecDeque::new();
+	path.push_back(start);
+	for keys in 0..=params.locked_rings + 1 {
+		let goal = if keys <= params.locked_rings {
+			key_cell(seed, params, keys)?
+		} else {
+			lock_cell()
+		};
+		let mut is_open_within = |cell: WorldCell, direction: Direction| {
+			if ring(cell.step(direction).section()) > limit {
+				return false;
+			}
+			let layout = layouts.entry(cell.section()).or_insert_with(|| {
+				SectionLayout::generate_with_params(seed, params, cell.section(), true)
+			});
+			is_open(&layout.map, &layout.openings, cell.position(), direction)
+				&& layout
+					.door(cell.position(), direction)
+					.map_or(true, |door| door.tier < keys)
+		};
+		let from = *path.back()?;
+		let leg = shortest_path(from, goal, &mut is_open_within)?;
+		path.extend(leg.into_iter().skip(1));
+	}
+	Some(path)
+}
+
+/// The cell key `tier` is in.
+fn key_cell(seed: u64, params: &WorldParams, tier: usize) -> Option<WorldCell> {
+	let section = rand_for_key::<StdRng>(seed, params, tier);
+	SectionLayout::generate_with_params(seed, params, section, true)
+		.items
+		.iter()
+		.find(|(_, kind)| *kind == ItemKind::Key(tier))
+		.map(|(position, _)| WorldCell::new(section, *position))
+}
+
+/// Breadth first search from `start` to `goal` through open ways.
+fn shortest_path<F>(
+	start: WorldCell,
+	goal: WorldCell,
+	is_open: &mut F,
+) -> Option<VecDeque<WorldCell>>
+where
+	F: FnMut(WorldCell, Direction) -> bool,
+{
+	let mut previous = HashMap::new();
+	previous.insert(start, start);
+	let mut queue = VecDeque::new();
+	queue.push_back(start);
+	while let Some(cell) = queue.pop_front() {
+		if cell == goal {
+			return Some(path_to(&previous, cell));
+		}
+		for direction in DIRECTIONS.iter() {
+			let next = cell.step(*direction);
+			if !previous.contains_key(&next) && is_open(cell, *direction) {
+				previous.insert(next, cell);
+				queue.push_back(next);
+			}
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::{run_bot, FrontierExplorer},
+		*,
+	};
+
+	#[test]
+	fn planner_beats_explorer() {
+		for seed in 0..3 {
+			let params = WorldParams::default();
+			let planned = run_bot(seed, params, &mut OptimalPlanner::default(), 60_000);
+			let explored = run_bot(seed, params, &mut FrontierExplorer::default(), 60_000);
+			assert!(planned.stats.escaped, "{:?}", planned);
+			assert!(planned.stats.ticks <= explored.stats.ticks);
+		}
+	}
+
+	#[test]
+	fn closed_worlds_are_given_up_on() {
+		let params = WorldParams {
+			door_odds: 0.0,
+			locked_rings: 0,
+			..WorldParams::default()
+		};
+		let run = run_bot(0, params, &mut OptimalPlanner::default(), 60_000);
+		assert!(run.gave_up);
+		assert!(!run.stats.escaped);
+		assert_eq!(run.stats.ticks, 1);
+	}
+}
@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+
+use super::{
+	super::{
+		super::map::Direction,
+		input::TickInput,
+		simulation::Simulation,
+		visibility::WorldCell,
+	},
+	direction_index,
+	drop_reached,
+	exits,
+	use_items,
+	walk,
+	Bot,
+};
+
+/// Keeps a hand on the wall to its left, cell by cell, the way people are
+/// told to get out of mazes. That only gets everywhere when the walls are
+/// all connected, which they are not once border walls have doors on both
+/// sides of a section, so it can end up walking in circles.
+pub struct WallFollower {
+	heading: Direction,
+	/// The cell being headed for, if any.
+	path: VecDeque<WorldCell>,
+}
+
+impl Default for WallFollower {
+	fn default() -> Self {
+		Self {
+			// The way the Agent starts out facing.
+			heading: Direction::Right,
+			path: VecDeque::new(),
+		}
+	}
+}
+
+impl Bot for WallFollower {
+	fn name(&self) -> &'static str {
+		"wall-follower"
+	}
+
+	fn next_input(&mut self, simulation: &Simulation) -> TickInput {
+		let eye = simulation.camera().eye();
+		drop_reached(&mut self.path, eye);
+		if self.path.is_empty() {
+			let cell = WorldCell::containing(eye);
+			let exits = exits(simulation, cell);
+			let heading = self.heading;
+			let choices = [
+				turn_left(heading),
+				heading,
+				turn_right(heading),
+				turn_right(turn_right(heading)),
+			];
+			if let Some(direction) = choices
+				.iter()
+				.find(|direction| exits[direction_index(**direction)])
+			{
+				self.heading = *direction;
+				self.path.push_back(cell.step(*direction));
+			}
+		}
+		walk(&self.path, simulation.camera(), use_items(simulation))
+	}
+}
+
+/// The direction on the left when facing `direction`. Rows grow towards
+/// world x and columns towards negative world z, so facing `Right` has `Up`
+/// on the left.
+fn turn_left(direction: Direction) -> Direction {
+	match direction {
+		Direction::Up => Direction::Left,
+		Direction::Left => Direction::Down,
+		Direction::Down => Direction::Right,
+		Direction::Right => Direction::Up,
+	}
+}
+
+fn turn_right(direction: Direction) -> Direction {
+	match direction {
+		Direction::Up => Direction::Right,
+		Direction::Right => Direction::Down,
+		Direction::Down => Direction::Left,
+		Direction::Left => Direction::Up,
+	}
+}
@@ -0,0 +1,611 @@
+use std::{
+	cell::RefCell,
+	collections::{hash_map, HashMap, HashSet},
+	f32,
+	rc::Rc,
+	time::Duration,
+};
+
+use kiss3d::{
+	nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3},
+	resource::Mesh,
+	scene::SceneNode,
+	window::Window,
+};
+
+use super::{
+	super::{
+		map::{Direction, Position, ROOM_CENTER, ROOM_SIZE},
+		meshes::{generate_coin, generate_key, generate_lock, key_color, ItemKind},
+		rng::WorldParams,
+	},
+	landmark::Landmark,
+	section::{
+		grid_translation,
+		DoorId,
+		HingedDoor,
+		ItemId,
+		LockedDoor,
+		SectionLayout,
+		MAZE_HEIGHT,
+		MAZE_SIZE,
+		MAZE_SIZE_HALF,
+	},
+	section_generator::{diamond, manhattan, SectionGenerator},
+	section_mesh::{ceiling_mesh, floor_mesh, wall_mesh, MeshData},
+	visibility::{is_open, visible_cells, ViewCone, WorldCell},
+};
+
+const DOOR_THICKNESS: f32 = 0.1;
+/// How deep a flooded landmark is under water.
+const WATER_DEPTH: f32 = 0.3;
+
+/// The parts of landmarks that are more than walls.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum Prop {
+	Water,
+	Body,
+	Note,
+}
+
+pub struct Chunk {
+	pub layout: SectionLayout,
+	section: SectionNodes,
+	/// Item nodes, with the cell they rest in.
+	pub items: Vec<(Position, SceneNode, ItemKind)>,
+	/// Monster nodes, in the order of the layout's monsters.
+	pub monsters: Vec<SceneNode>,
+	/// Locked door nodes, with the tier of the key that opens them.
+	pub doors: Vec<(SceneNode, usize)>,
+	/// Nodes of the landmark laid out in the section, if it is one.
+	props: Vec<(Prop, SceneNode)>,
+	/// Whether any part of the section can be seen from the camera.
+	pub visible: bool,
+}
+
+/// The sections around the Agent, streamed in and out as the Agent moves.
+/// Scene nodes of sections that go out of range are kept in a pool and
+/// re-targeted to new sections instead of being rebuilt.
+pub struct Chunks {
+	seed: u64,
+	/// Sections within this many sections of the Agent are streamed in.
+	range: i64,
+	chunks: HashMap<(i64, i64), Chunk>,
+	/// Nodes of doors that swing open, with where their hinges are when they
+	/// are shut. Both sections by a door have it, so they are kept apart from
+	/// the sections and shown while either of them is streamed in.
+	hinged_doors: HashMap<DoorId, (SceneNode, Isometry3<f32>)>,
+	pool: SectionPool,
+	generator: SectionGenerator,
+}
+
+impl Chunks {
+	pub fn new(seed: u64, range: i64) -> Self {
+		Self {
+			seed,
+			range,
+			chunks: HashMap::new(),
+			hinged_doors: HashMap::new(),
+			pool: SectionPool::default(),
+			generator: SectionGenerator::new(seed, range),
+		}
+	}
+
+	/// Streams in the sections in range of `position`, and streams out the
+	/// ones that are not.
+	pub fn update(
+		&mut self,
+		window: &mut Window,
+		position: (i64, i64),
+		collected_items: &HashSet<ItemId>,
+	) {
+		let range = self.range;
+		for (_, chunk) in self
+			.chunks
+			.drain_filter(|p, _| manhattan(*p, position) > range)
+		{
+			self.pool.give_back(chunk);
+		}
+
+		for position in diamond(position, range) {
+			if let hash_map::Entry::Vacant(entry) = self.chunks.entry(position) {
+				let mut layout = self.generator.take(position);
+				layout
+					.items
+					.retain(|(cell, _)| !collected_items.contains(&(position, *cell)));
+				entry.insert(self.pool.take(window, self.seed, layout));
+			}
+		}
+		self.generator.forget_distant(position);
+		self.update_hinged_doors(window);
+	}
+
+	/// Gives back the nodes of doors that swing open that neither of their
+	/// sections has any more, and places the ones that came into range.
+	fn update_hinged_doors(&mut self, window: &mut Window) {
+		let in_range: HashSet<DoorId> = self
+			.chunks
+			.values()
+			.flat_map(|chunk| chunk.layout.hinged_doors.iter().map(|door| door.id))
+			.collect();
+		for (_, (node, _)) in self
+			.hinged_doors
+			.drain_filter(|id, _| !in_range.contains(id))
+		{
+			self.pool.give_back_hinged_door(node);
+		}
+
+		for chunk in self.chunks.values() {
+			for door in chunk.layout.hinged_doors.iter() {
+				if let hash_map::Entry::Vacant(entry) = self.hinged_doors.entry(door.id) {
+					entry.insert(self.pool.take_hinged_door(window, &chunk.layout, door));
+				}
+			}
+		}
+	}
+
+	/// Changes how far sections are streamed in. Takes effect on the next
+	/// update.
+	pub fn set_range(&mut self, range: i64) {
+		self.range = range;
+		self.generator.set_range(range);
+	}
+
+	/// Hides the sections that cannot be seen in `view`, and what is in them.
+	pub fn cull(&mut self, view: &ViewCone) {
+		let visible: HashSet<_> = visible_cells(view, self.range, |cell, direction| {
+			self.is_open(cell, direction)
+		})
+		.iter()
+		.map(WorldCell::section)
+		.collect();
+		for (position, chunk) in self.chunks.iter_mut() {
+			chunk.visible = visible.contains(position);
+			chunk.section.group.set_visible(chunk.visible);
+			for (_, item, _) in chunk.items.iter_mut() {
+				item.set_visible(chunk.visible);
+			}
+			for (_, prop) in chunk.props.iter_mut() {
+				prop.set_visible(chunk.visible);
+			}
+		}
+		for (id, (door, _)) in self.hinged_doors.iter_mut() {
+			let shows = |chunk: &Chunk| {
+				chunk.visible && chunk.layout.hinged_doors.iter().any(|d| d.id == *id)
+			};
+			door.set_visible(self.chunks.values().any(shows));
+		}
+	}
+
+	/// Whether the way out of `cell` in `direction` is open and leads into a
+	/// section in range.
+	fn is_open(&self, cell: WorldCell, direction: Direction) -> bool {
+		self.chunks.contains_key(&cell.step(direction).section())
+			&& self.chunks.get(&cell.section()).map_or(false, |chunk| {
+				is_open(
+					&chunk.layout.map,
+					&chunk.layout.openings,
+					cell.position(),
+					direction,
+				)
+			})
+	}
+
+	/// Generates the sections the Agent is heading towards, moving in
+	/// `direction` given as rows and columns, for at most `budget`. Only the
+	/// scene nodes are left to create once they come into range.
+	pub fn generate_ahead(
+		&mut self,
+		position: (i64, i64),
+		direction: (f32, f32),
+		budget: Duration,
+	) {
+		self.generator.predict(position, direction);
+		self.generator.generate(budget);
+	}
+
+	/// Takes the item resting in `cell` of `section` out of it.
+	pub fn remove_item(&mut self, section: (i64, i64), cell: Position) {
+		if let Some(chunk) = self.chunks.get_mut(&section) {
+			if let Some(index) = chunk.items.iter().position(|(pos, _, _)| *pos == cell) {
+				let (_, item, kind) = chunk.items.remove(index);
+				self.pool.give_back_item(item, kind);
+			}
+			chunk.layout.items.retain(|(pos, _)| *pos != cell);
+		}
+	}
+
+	#[inline]
+	pub fn get(&self, position: &(i64, i64)) -> Option<&Chunk> {
+		self.chunks.get(position)
+	}
+
+	#[inline]
+	pub fn get_mut(&mut self, position: &(i64, i64)) -> Option<&mut Chunk> {
+		self.chunks.get_mut(position)
+	}
+
+	#[inline]
+	pub fn iter_mut(&mut self) -> hash_map::IterMut<(i64, i64), Chunk> {
+		self.chunks.iter_mut()
+	}
+
+	/// The nodes of doors that swing open, with where their hinges are when
+	/// they are shut.
+	#[inline]
+	pub fn hinged_doors_mut(&mut self) -> hash_map::IterMut<DoorId, (SceneNode, Isometry3<f32>)> {
+		self.hinged_doors.iter_mut()
+	}
+
+	/// Removes every section, including the pooled ones, from the scene.
+	pub fn clear(&mut self, window: &mut Window) {
+		for (_, chunk) in self.chunks.drain() {
+			self.pool.give_back(chunk);
+		}
+		for (_, (node, _)) in self.hinged_doors.drain() {
+			self.pool.give_back_hinged_door(node);
+		}
+		self.pool.clear(window);
+	}
+}
+
+struct SectionNodes {
+	group: SceneNode,
+	walls: Rc<RefCell<Mesh>>,
+}
+
+/// Scene nodes not in use by any section. Floors and ceilings look the same
+/// in every section, so all sections share the same meshes for them.
+#[derive(Default)]
+struct SectionPool {
+	sections: Vec<SectionNodes>,
+	monsters: Vec<SceneNode>,
+	/// Item nodes, by the kind of item they are.
+	items: HashMap<ItemKind, Vec<SceneNode>>,
+	/// Locked door nodes, of any tier.
+	doors: Vec<SceneNode>,
+	hinged_doors: Vec<SceneNode>,
+	props: HashMap<Prop, Vec<SceneNode>>,
+	tiles: Option<(Rc<RefCell<Mesh>>, Rc<RefCell<Mesh>>)>,
+}
+
+impl SectionPool {
+	fn take(&mut self, window: &mut Window, seed: u64, layout: SectionLayout) -> Chunk {
+		let mut section = match self.sections.pop() {
+			Some(section) => {
+				update_mesh(&section.walls, wall_mesh(&layout));
+				section
+			}
+			None => {
+				let (floor, ceiling) = self.tiles.get_or_insert_with(|| {
+					(
+						create_mesh(floor_mesh(), false),
+						create_mesh(ceiling_mesh(), false),
+					)
+				});
+				create_section_nodes(window, &layout, floor.clone(), ceiling.clone())
+			}
+		};
+		section.group.set_local_translation(layout.offset);
+		section
+			.group
+			.set_color(layout.color.0, layout.color.1, layout.color.2);
+		section.group.set_visible(true);
+
+		let mut monsters = Vec::with_capacity(layout.monsters.len());
+		for slot in layout.monsters.iter() {
+			let mut monster = self
+				.monsters
+				.pop()
+				.unwrap_or_else(|| create_monster_node(window));
+			let transformation = slot.monster_transformation();
+			monster.set_local_transformation(Isometry3::from_parts(
+				Translation3::from(layout.offset.vector + transformation.translation.vector),
+				transformation.rotation,
+			));
+			monster.set_visible(false);
+			monsters.push(monster);
+		}
+
+		let mut items = Vec::with_capacity(layout.items.len());
+		for (cell, kind) in layout.items.iter() {
+			let mut item = self
+				.items
+				.get_mut(kind)
+				.and_then(Vec::pop)
+				.unwrap_or_else(|| create_item_node(window, seed, *kind));
+			item.set_local_transformation(Isometry3::from_parts(
+				layout.item_translation(*cell),
+				UnitQuaternion::identity(),
+			));
+			item.set_visible(true);
+			items.push((*cell, item, *kind));
+		}
+
+		let mut props = Vec::new();
+		if let Some(landmark) = &layout.landmark {
+			for (prop, transformation) in landmark_props(&layout.offset, landmark) {
+				let mut node = self
+					.props
+					.get_mut(&prop)
+					.and_then(Vec::pop)
+					.unwrap_or_else(|| create_prop_node(window, prop));
+				node.set_local_transformation(transformation);
+				node.set_visible(true);
+				props.push((prop, node));
+			}
+		}
+
+		let mut doors = Vec::with_capacity(layout.doors.len());
+		for door in layout.doors.iter() {
+			let mut node = self.doors.pop().unwrap_or_else(|| create_door_node(window));
+			place_door_node(&mut node, seed, &layout.offset, door);
+			doors.push((node, door.tier));
+		}
+
+		Chunk {
+			layout,
+			section,
+			items,
+			monsters,
+			doors,
+			props,
+			visible: true,
+		}
+	}
+
+	fn give_back(&mut self, chunk: Chunk) {
+		let Chunk {
+			mut section,
+			items,
+			monsters,
+			doors,
+			props,
+			..
+		} = chunk;
+		section.group.set_visible(false);
+		self.sections.push(section);
+		for mut monster in monsters {
+			monster.set_visible(false);
+			self.monsters.push(monster);
+		}
+		for (_, item, kind) in items {
+			self.give_back_item(item, kind);
+		}
+		for (mut door, _) in doors {
+			door.set_visible(false);
+			self.doors.push(door);
+		}
+		for (prop, mut node) in props {
+			node.set_visible(false);
+			self.props.entry(prop).or_default().push(node);
+		}
+	}
+
+	fn give_back_item(&mut self, mut item: SceneNode, kind: ItemKind) {
+		item.set_visible(false);
+		self.items.entry(kind).or_default().push(item);
+	}
+
+	/// A node for the door that swings open `door` of `layout`, shut, and
+	/// where its hinge is.
+	fn take_hinged_door(
+		&mut self,
+		window: &mut Window,
+		layout: &SectionLayout,
+		door: &HingedDoor,
+	) -> (SceneNode, Isometry3<f32>) {
+		let mut node = self
+			.hinged_doors
+			.pop()
+			.unwrap_or_else(|| create_hinged_door_node(window));
+		let hinge = hinge(layout, door);
+		node.set_local_transformation(hinge);
+		node.set_visible(true);
+		(node, hinge)
+	}
+
+	fn give_back_hinged_door(&mut self, mut node: SceneNode) {
+		node.set_visible(false);
+		self.hinged_doors.push(node);
+	}
+
+	fn clear(&mut self, window: &mut Window) {
+		for mut section in self.sections.drain(..) {
+			window.remove_node(&mut section.group);
+		}
+		let items = self.items.drain().flat_map(|(_, items)| items);
+		let props = self.props.drain().flat_map(|(_, props)| props);
+		for mut node in self
+			.monsters
+			.drain(..)
+			.chain(items)
+			.chain(self.doors.drain(..))
+			.chain(self.hinged_doors.drain(..))
+			.chain(props)
+		{
+			window.remove_node(&mut node);
+		}
+	}
+}
+
+fn create_section_nodes(
+	window: &mut Window,
+	layout: &SectionLayout,
+	floor: Rc<RefCell<Mesh>>,
+	ceiling: Rc<RefCell<Mesh>>,
+) -> SectionNodes {
+	let walls = create_mesh(wall_mesh(layout), true);
+
+	let mut group = window.add_group();
+	group
+		.add_mesh(ceiling, Vector3::new(1.0, 1.0, 1.0))
+		.set_texture_with_name("ceiling");
+	group
+		.add_mesh(walls.clone(), Vector3::new(1.0, 1.0, 1.0))
+		.set_texture_with_name("wall");
+	group
+		.add_mesh(floor, Vector3::new(1.0, 1.0, 1.0))
+		.set_texture_with_name("floor");
+	group.set_material_with_name("pixel");
+
+	SectionNodes {
+		group,
+		walls,
+	}
+}
+
+fn create_mesh(data: MeshData, dynamic_draw: bool) -> Rc<RefCell<Mesh>> {
+	Rc::new(RefCell::new(Mesh::new(
+		data.coords,
+		data.faces,
+		Some(data.normals),
+		Some(data.uvs),
+		dynamic_draw,
+	)))
+}
+
+/// Replaces the buffers of `mesh`, which are uploaded again the next time it
+/// is rendered.
+fn update_mesh(mesh: &Rc<RefCell<Mesh>>, data: MeshData) {
+	let mesh = mesh.borrow();
+	*mesh.coords().write().unwrap().data_mut() = Some(data.coords);
+	*mesh.normals().write().unwrap().data_mut() = Some(data.normals);
+	*mesh.uvs().write().unwrap().data_mut() = Some(data.uvs);
+	*mesh.faces().write().unwrap().data_mut() = Some(data.faces);
+}
+
+fn create_item_node(window: &mut Window, seed: u64, kind: ItemKind) -> SceneNode {
+	let parent = window.scene_mut();
+	match kind {
+		ItemKind::Lock => generate_lock(parent),
+		ItemKind::Key(tier) => generate_key(parent, seed, &WorldParams::default(), tier),
+		ItemKind::Coin => generate_coin(parent),
+	}
+}
+
+fn create_door_node(window: &mut Window) -> SceneNode {
+	let mut node = window.add_cube(MAZE_SIZE, MAZE_HEIGHT, DOOR_THICKNESS);
+	node.set_material_with_name("pixel");
+	node
+}
+
+/// Moves a locked door node into the opening of `door`, in the colour of its
+/// key.
+fn place_door_node(node: &mut SceneNode, seed: u64, offset: &Translation3<f32>, door: &LockedDoor) {
+	let transformation = door.slot.transformation();
+	node.set_local_transformation(Isometry3::from_parts(
+		Translation3::from(offset.vector + transformation.translation.vector),
+		transformation.rotation,
+	));
+	let (r, g, b) = key_color(seed, &WorldParams::default(), door.tier);
+	node.set_color(r, g, b);
+	node.set_visible(true);
+}
+
+/// A door that swings open, turning around a hinge at one side of its
+/// opening. It is placed at its [`hinge`] and swung with [`swing_door`].
+fn create_hinged_door_node(window: &mut Window) -> SceneNode {
+	let mut node = window.add_group();
+	let mut panel = node.add_cube(MAZE_SIZE, MAZE_HEIGHT, DOOR_THICKNESS);
+	panel.set_local_translation(Translation3::new(MAZE_SIZE_HALF, 0.0, 0.0));
+	panel.set_color(0.45, 0.3, 0.2);
+	panel.set_material_with_name("pixel");
+	node
+}
+
+/// Where the hinge of `door` of `layout` is when it is shut. The section it
+/// is not shared as sees it from behind, so that it turns the same way from
+/// both sections.
+fn hinge(layout: &SectionLayout, door: &HingedDoor) -> Isometry3<f32> {
+	let transformation = door.slot.transformation();
+	let mut rotation = transformation.rotation;
+	if door.id.0 != layout.position {
+		rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI) * rotation;
+	}
+	Isometry3::from_parts(
+		Translation3::from(layout.offset.vector + transformation.translation.vector),
+		rotation,
+	) * Translation3::new(-MAZE_SIZE_HALF, 0.0, 0.0)
+}
+
+/// Swings the door at `hinge` open by `openness`, from shut at zero to a
+/// quarter turn at one.
+pub fn swing_door(node: &mut SceneNode, hinge: &Isometry3<f32>, openness: f32) {
+	let turn =
+		UnitQuaternion::from_axis_angle(&Vector3::y_axis(), openness * f32::consts::FRAC_PI_2);
+	node.set_local_transformation(hinge * turn);
+}
+
+/// Where the water over the floor of a flooded landmark goes, and the fallen
+/// Agent with its note.
+fn landmark_props(offset: &Translation3<f32>, landmark: &Landmark) -> Vec<(Prop, Isometry3<f32>)> {
+	let mut props = Vec::new();
+	let floor = -MAZE_HEIGHT / 2.0;
+	if landmark.flooded {
+		let water = Translation3::new(0.0, floor + WATER_DEPTH / 2.0 - 0.05, 0.0)
+			* offset * grid_translation(ROOM_CENTER, ROOM_CENTER);
+		props.push((
+			Prop::Water,
+			Isometry3::from_parts(water, UnitQuaternion::identity()),
+		));
+	}
+	if let Some(Position(row, col)) = landmark.body {
+		let cell = offset * grid_translation(row, col);
+		props.push((
+			Prop::Body,
+			Isometry3::from_parts(
+				Translation3::new(0.0, floor + 0.02, 0.0) * cell,
+				UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -f32::consts::PI / 2.0),
+			),
+		));
+		let note = Translation3::new(MAZE_SIZE / 4.0, floor + 0.01, 0.0) * cell;
+		props.push((
+			Prop::Note,
+			Isometry3::from_parts(note, UnitQuaternion::identity()),
+		));
+	}
+	props
+}
+
+fn create_prop_node(window: &mut Window, prop: Prop) -> SceneNode {
+	match prop {
+		Prop::Water => {
+			let width = ROOM_SIZE as f32 * MAZE_SIZE;
+			let mut water = window.add_cube(width, WATER_DEPTH, width);
+			water.set_color(0.2, 0.35, 0.6);
+			water.set_material_with_name("pixel");
+			water
+		}
+		Prop::Body => {
+			let mut body =
+				create_billboard(window, "ghost", MAZE_SIZE * 0.408, MAZE_HEIGHT * 0.861);
+			body.set_color(0.3, 0.3, 0.3);
+			body
+		}
+		Prop::Note => {
+			let mut note = window.add_cube(0.15, 0.01, 0.2);
+			note.set_color(1.0, 1.0, 0.95);
+			note.set_material_with_name("pixel");
+			note
+		}
+	}
+}
+
+fn create_monster_node(window: &mut Window) -> SceneNode {
+	let mut quad = create_billboard(window, "monster", MAZE_SIZE * 0.408, MAZE_HEIGHT * 0.861);
+	quad.set_visible(false);
+	quad
+}
+
+/// A picture with `texture` on both sides, so that it can be seen from
+/// either.
+pub fn create_billboard(window: &mut Window, texture: &str, width: f32, height: f32) -> SceneNode {
+	let half_turn = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI);
+	let mut quad = window.add_quad(width, height, 1, 1);
+	let mut quad2 = quad.add_quad(width, height, 1, 1);
+	quad2.prepend_to_local_rotation(&half_turn);
+	quad.set_texture_with_name(texture);
+	quad.set_material_with_name("pixel");
+	quad
+}
@@ -0,0 +1,138 @@
+use std::{cell::RefCell, rc::Rc};
+
+use kiss3d::nalgebra::Point3;
+
+use super::{
+	super::{
+		coop::{Connection, Message, RELAY_PORT},
+		locale::tr,
+		map::Position,
+	},
+	section::{DoorId, ItemId},
+	simulation::Simulation,
+};
+
+thread_local! {
+	static RELAY_URL: RefCell<String> =
+		RefCell::new(format!("ws://localhost:{}/little_maze", RELAY_PORT));
+}
+
+/// Sets the relay co-op runs go through, the path being the room.
+pub fn set_relay_url(url: String) {
+	RELAY_URL.with(|u| *u.borrow_mut() = url);
+}
+
+pub fn relay_url() -> String {
+	RELAY_URL.with(|u| u.borrow().clone())
+}
+
+/// The other Agent of a co-op run. Its position and pickups come over the
+/// connection, and ours go the other way.
+#[derive(Clone)]
+pub struct Partner {
+	/// Shared with the saved state while the game is paused, so that the
+	/// room is kept.
+	connection: Rc<RefCell<Connection>>,
+	position: Option<(f32, f32)>,
+	sent_position: Option<(f32, f32)>,
+	escaped: bool,
+	left: bool,
+}
+
+impl Partner {
+	pub fn new(connection: Connection) -> Self {
+		Self {
+			connection: Rc::new(RefCell::new(connection)),
+			position: None,
+			sent_position: None,
+			escaped: false,
+			left: false,
+		}
+	}
+
+	/// Applies what the other Agent has done since the last tick to
+	/// `simulation`. Returns the items it picked up.
+	pub fn receive(&mut self, simulation: &mut Simulation) -> Vec<ItemId> {
+		let mut collected = Vec::new();
+		let mut connection = self.connection.borrow_mut();
+		while let Some(message) = connection.receive() {
+			match message {
+				Message::Position {
+					x,
+					z,
+				} => self.position = Some((x, z)),
+				Message::Collected {
+					section,
+					cell,
+				} => {
+					let cell = Position(cell.0, cell.1);
+					if simulation.collect_shared(section, cell) {
+						collected.push((section, cell));
+					}
+				}
+				Message::Opened {
+					section,
+					direction,
+				} => {
+					simulation.open_shared((section, direction));
+				}
+				Message::Escaped => self.escaped = true,
+				Message::Left => self.left = true,
+				_ => {}
+			}
+		}
+		self.left |= connection.is_closed();
+		simulation.set_partner(self.position());
+		collected
+	}
+
+	/// Lets the other Agent know where ours is, if it has moved.
+	pub fn send_position(&mut self, eye: &Point3<f32>) {
+		let position = Some((eye.x, eye.z));
+		if position != self.sent_position {
+			self.sent_position = position;
+			self.connection.borrow_mut().send(&Message::Position {
+				x: eye.x,
+				z: eye.z,
+			});
+		}
+	}
+
+	/// Lets the other Agent know that ours picked up the item in `cell` of
+	/// `section`.
+	pub fn send_collected(&mut self, section: (i64, i64), cell: Position) {
+		self.connection.borrow_mut().send(&Message::Collected {
+			section,
+			cell: (cell.0, cell.1),
+		});
+	}
+
+	/// Lets the other Agent know that ours started opening the door `id`.
+	pub fn send_opened(&mut self, id: DoorId) {
+		let (section, direction) = id;
+		self.connection.borrow_mut().send(&Message::Opened {
+			section,
+			direction,
+		});
+	}
+
+	pub fn send_escaped(&mut self) {
+		self.connection.borrow_mut().send(&Message::Escaped);
+	}
+
+	/// Where on the floor the other Agent is, while it is still in the maze.
+	pub fn position(&self) -> Option<(f32, f32)> {
+		self.position.filter(|_| !self.escaped && !self.left)
+	}
+
+	/// What has become of the other Agent, if it is no longer in the maze.
+	pub fn status(&self) -> Option<String> {
+		if self.escaped {
+			Some(tr("partner-escaped"))
+		} else if self.left {
+			Some(tr("partner-left"))
+		} else {
+			None
+		}
+	}
+}
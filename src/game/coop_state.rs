@@ -0,0 +1,145 @@
+use kiss3d::{
+	conrod::{
+		color::{Color, Colorable},
+		position::{Positionable, Sizeable},
+		widget::{self, Widget},
+		widget_ids,
+		Borderable,
+		Labelable,
+	},
+	window::Window,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+	super::{
+		coop::{Connection, Message},
+		locale::{tr, tr_with},
+		GAME_NAME,
+	},
+	coop::relay_url,
+	InnerGameState,
+	MouseButtons,
+};
+
+/// Waits in a room of the relay for the other Agent of a co-op run. The
+/// first to join picks the world once the second does.
+pub struct CoopState {
+	ui_ids: UiIds,
+	connection: Option<Connection>,
+	player: Option<usize>,
+	status: String,
+}
+
+impl CoopState {
+	pub fn new(window: &mut Window) -> Self {
+		let url = relay_url();
+		let (connection, status) = match Connection::open(&url) {
+			Ok(connection) => (Some(connection), tr("coop-joining")),
+			Err(e) => (
+				None,
+				tr_with("coop-unreachable", &[
+					("url", url.as_str().into()),
+					("error", e.to_string().into()),
+				]),
+			),
+		};
+		Self {
+			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
+			connection,
+			player: None,
+			status,
+		}
+	}
+
+	/// The seed of the world to start in, once both Agents are in the room.
+	fn poll(&mut self) -> Option<u64> {
+		let connection = self.connection.as_mut()?;
+		while let Some(message) = connection.receive() {
+			match message {
+				Message::Welcome {
+					player,
+				} => {
+					self.player = Some(player);
+					self.status = tr("coop-waiting");
+				}
+				Message::Joined if self.player == Some(0) => {
+					let seed = StdRng::from_entropy().gen();
+					connection.send(&Message::World {
+						seed,
+					});
+					return Some(seed);
+				}
+				Message::World {
+					seed,
+				} => return Some(seed),
+				Message::Full => {
+					self.status = tr("coop-full");
+					self.connection = None;
+					return None;
+				}
+				_ => {}
+			}
+		}
+		if connection.is_closed() {
+			self.status = tr("coop-lost");
+			self.connection = None;
+		}
+		None
+	}
+}
+
+impl InnerGameState for CoopState {
+	fn step(&mut self, window: &mut Window, _: &MouseButtons) -> Option<Box<dyn InnerGameState>> {
+		if let Some(seed) = self.poll() {
+			let connection = self.connection.take()?;
+			return Some(Box::new(super::PlayingState::coop(
+				window, seed, connection,
+			)));
+		}
+
+		let menu_clicked;
+		{
+			let mut ui = window.conrod_ui_mut().set_widgets();
+
+			widget::Text::new(GAME_NAME)
+				.font_size(75)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.mid_top_with_margin(150.0)
+				.center_justify()
+				.set(self.ui_ids.title, &mut ui);
+
+			widget::Text::new(&self.status)
+				.font_size(24)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.middle()
+				.center_justify()
+				.set(self.ui_ids.status_text, &mut ui);
+
+			menu_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("main-menu"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(100.0)
+				.set(self.ui_ids.menu_button, &mut ui);
+		}
+
+		if menu_clicked.was_clicked() {
+			Some(Box::new(super::MenuState::new(window)))
+		} else {
+			None
+		}
+	}
+}
+
+widget_ids! {
+	struct UiIds {
+		title,
+		status_text,
+		menu_button,
+	}
+}
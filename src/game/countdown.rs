@@ -0,0 +1,89 @@
+use super::{super::meshes::ItemKind, input::TICKS_PER_SECOND};
+
+/// Seconds a timed run starts with.
+const START_SECONDS: f32 = 180.0;
+const COIN_BONUS_SECONDS: f32 = 10.0;
+const KEY_BONUS_SECONDS: f32 = 30.0;
+const COIN_POINTS: u64 = 100;
+const SECOND_POINTS: u64 = 10;
+
+/// The time left of a timed run, in which the Agent has to get back to the
+/// lock. Picking things up gives more time.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Countdown {
+	/// Tick the time runs out on.
+	deadline: u64,
+}
+
+impl Default for Countdown {
+	fn default() -> Self {
+		Self {
+			deadline: ticks(START_SECONDS),
+		}
+	}
+}
+
+impl Countdown {
+	pub fn deadline(&self) -> u64 {
+		self.deadline
+	}
+
+	/// Number of ticks left after `tick`.
+	pub fn remaining(&self, tick: u64) -> u64 {
+		self.deadline.saturating_sub(tick)
+	}
+
+	pub fn is_over(&self, tick: u64) -> bool {
+		tick >= self.deadline
+	}
+
+	/// Adds the bonus time for picking up `kind`.
+	pub fn add_bonus(&mut self, kind: ItemKind) {
+		self.deadline += match kind {
+			ItemKind::Key(_) => ticks(KEY_BONUS_SECONDS),
+			ItemKind::Coin => ticks(COIN_BONUS_SECONDS),
+			ItemKind::Lock => 0,
+		};
+	}
+}
+
+/// Score of a timed run that escaped with `coins` and `remaining_ticks` left.
+pub fn score(coins: usize, remaining_ticks: u64) -> u64 {
+	coins as u64 * COIN_POINTS + seconds(remaining_ticks) * SECOND_POINTS
+}
+
+/// Whole seconds in `ticks`, as shown on the timer.
+pub fn seconds(ticks: u64) -> u64 {
+	(ticks as f32 / TICKS_PER_SECOND).ceil() as u64
+}
+
+fn ticks(seconds: f32) -> u64 {
+	(seconds * TICKS_PER_SECOND) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn pickups_give_more_time() {
+		let mut countdown = Countdown::default();
+		let start = countdown.remaining(0);
+		assert_eq!(seconds(start), 180);
+		countdown.add_bonus(ItemKind::Coin);
+		countdown.add_bonus(ItemKind::Key(0));
+		countdown.add_bonus(ItemKind::Lock);
+		assert_eq!(seconds(countdown.remaining(0)), 220);
+		assert!(!countdown.is_over(countdown.deadline() - 1));
+		assert!(countdown.is_over(countdown.deadline()));
+		assert_eq!(countdown.remaining(countdown.deadline() + 5), 0);
+	}
+
+	#[test]
+	fn coins_and_time_both_score() {
+		assert_eq!(score(0, 0), 0);
+		assert_eq!(score(3, 0), 300);
+		assert_eq!(score(3, ticks(12.0)), 420);
+		assert_eq!(score(0, 1), 10);
+	}
+}
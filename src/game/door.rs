@@ -0,0 +1,73 @@
+use super::input::TICKS_PER_SECOND;
+
+/// How long a door takes to swing open.
+const OPENING_SECONDS: f32 = 0.5;
+
+/// How far a door that swings open has been opened. Doors start out shut,
+/// and swing open when the Agent uses them.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct DoorState {
+	opening: bool,
+	/// Ticks the door has been swinging open for.
+	ticks: u64,
+}
+
+impl DoorState {
+	/// Starts swinging the door open.
+	#[inline]
+	pub fn open(&mut self) {
+		self.opening = true;
+	}
+
+	/// Swings the door a tick further open, if it is opening.
+	pub fn tick(&mut self) {
+		if self.opening && !self.is_open() {
+			self.ticks += 1;
+		}
+	}
+
+	/// Whether the door is open wide enough to walk through.
+	#[inline]
+	pub fn is_open(&self) -> bool {
+		self.ticks >= opening_ticks()
+	}
+
+	/// Whether the door is shut and not being opened.
+	#[inline]
+	pub fn is_shut(&self) -> bool {
+		!self.opening
+	}
+
+	/// How far open the door is, from shut at zero to wide open at one.
+	pub fn openness(&self) -> f32 {
+		self.ticks as f32 / opening_ticks() as f32
+	}
+}
+
+fn opening_ticks() -> u64 {
+	(OPENING_SECONDS * TICKS_PER_SECOND) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn doors_swing_open_over_time() {
+		let mut door = DoorState::default();
+		door.tick();
+		assert!(door.is_shut());
+		assert_eq!(door.openness(), 0.0);
+
+		door.open();
+		assert!(!door.is_shut());
+		for _ in 1..opening_ticks() {
+			door.tick();
+		}
+		assert!(!door.is_open());
+		door.tick();
+		door.tick();
+		assert!(door.is_open());
+		assert_eq!(door.openness(), 1.0);
+	}
+}
@@ -11,18 +11,77 @@ use kiss3d::{
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use super::{super::GAME_NAME, InnerGameState, MouseButtons};
+use super::{
+	super::{
+		daily::{share_text, Date},
+		dialogue::{dialogue, Facts, DEBRIEF},
+		locale::{tr, tr_with},
+		stats::DailyResult,
+		GAME_NAME,
+	},
+	countdown::{self, score},
+	InnerGameState,
+	MouseButtons,
+};
 
 pub struct EndState {
 	ui_ids: UiIds,
 	coins: usize,
+	/// What base has to say about the run.
+	debrief: String,
+	daily: Option<DailyEnd>,
+	timed: Option<TimedEnd>,
+}
+
+struct DailyEnd {
+	share_text: String,
+	/// Whether this was the first try of the day, the one that counts.
+	recorded: bool,
+}
+
+struct TimedEnd {
+	remaining_ticks: u64,
+	escaped: bool,
 }
 
 impl EndState {
-	pub fn new(window: &mut Window, coins: usize) -> Self {
+	/// The end of a run that went as `facts`.
+	pub fn new(window: &mut Window, facts: &Facts) -> Self {
 		EndState {
 			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
-			coins,
+			coins: facts.coins as usize,
+			debrief: dialogue().tell(DEBRIEF, facts).join("\n"),
+			daily: None,
+			timed: None,
+		}
+	}
+
+	/// The end of a timed run, which either escaped with `remaining_ticks` to
+	/// spare or ran out of time.
+	pub fn timed(window: &mut Window, facts: &Facts, remaining_ticks: u64) -> Self {
+		EndState {
+			timed: Some(TimedEnd {
+				remaining_ticks,
+				escaped: facts.escaped,
+			}),
+			..Self::new(window, facts)
+		}
+	}
+
+	/// The end of a Daily Maze run, showing a line to share the result with.
+	pub fn daily(
+		window: &mut Window,
+		facts: &Facts,
+		date: Date,
+		result: DailyResult,
+		recorded: bool,
+	) -> Self {
+		EndState {
+			daily: Some(DailyEnd {
+				share_text: share_text(&date, &result),
+				recorded,
+			}),
+			..Self::new(window, facts)
 		}
 	}
 }
@@ -48,7 +107,7 @@ impl InnerGameState for EndState {
 				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-				.label("Play Again")
+				.label(&tr("play-again"))
 				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.w(200.0)
 				.mid_bottom_with_margin(200.0)
@@ -58,28 +117,58 @@ impl InnerGameState for EndState {
 				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-				.label("Main Menu")
+				.label(&tr("main-menu"))
 				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.w(200.0)
 				.mid_bottom_with_margin(100.0)
 				.set(self.ui_ids.menu_button, &mut ui);
 
-			widget::Text::new(if self.coins == 0 {
-				"At least you made it back alive Agent"
-			} else if self.coins == 1 {
-				"Glad to have you back Agent!\nAnd a coin sure doesn't hurt either."
-			} else if self.coins <= 5 {
-				"Glad to have you back Agent!\nAnd these coins sure doesn't hurt either."
-			} else {
-				"I'm happy to have you back but,\nyou shouldn't risk your life for these coins \
-				 Agent!"
-			})
-			.font_size(24)
-			.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-			.middle()
-			.set(self.ui_ids.results_text, &mut ui);
-
-			widget::Text::new("Created by")
+			widget::Text::new(&self.debrief)
+				.font_size(24)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.middle()
+				.set(self.ui_ids.results_text, &mut ui);
+
+			if let Some(timed) = &self.timed {
+				let (coins, remaining_ticks) = if timed.escaped {
+					(self.coins, timed.remaining_ticks)
+				} else {
+					(0, 0)
+				};
+				let seconds = countdown::seconds(remaining_ticks);
+				widget::Text::new(&tr_with("end-score", &[
+					("score", score(coins, remaining_ticks).into()),
+					("coins", coins.into()),
+					(
+						"time",
+						format!("{}:{:02}", seconds / 60, seconds % 60).into(),
+					),
+				]))
+				.font_size(20)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.mid_bottom_with_margin(270.0)
+				.center_justify()
+				.set(self.ui_ids.score_text, &mut ui);
+			}
+
+			if let Some(daily) = &self.daily {
+				widget::Text::new(&daily.share_text)
+					.font_size(20)
+					.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+					.mid_bottom_with_margin(300.0)
+					.center_justify()
+					.set(self.ui_ids.share_text, &mut ui);
+				if !daily.recorded {
+					widget::Text::new(&tr("end-first-try"))
+						.font_size(14)
+						.color(Color::Rgba(1.0, 1.0, 1.0, 0.6))
+						.mid_bottom_with_margin(270.0)
+						.center_justify()
+						.set(self.ui_ids.first_try_text, &mut ui);
+				}
+			}
+
+			widget::Text::new(&tr("created-by"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.bottom_left_with_margin(10.0)
@@ -94,7 +183,7 @@ impl InnerGameState for EndState {
 				.label_color(Color::Rgba(0.0, 0.5, 1.0, 1.0))
 				.right_from(self.ui_ids.created_text, 15.0)
 				.set(self.ui_ids.me_link, &mut ui);
-			widget::Text::new("for the")
+			widget::Text::new(&tr("for-the"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.right_from(self.ui_ids.me_link, 17.0)
@@ -112,10 +201,12 @@ impl InnerGameState for EndState {
 		}
 
 		if play_again_clicked.was_clicked() {
-			Some(Box::new(super::PlayingState::new(
-				window,
-				StdRng::from_entropy().gen(),
-			)))
+			let seed = StdRng::from_entropy().gen();
+			Some(Box::new(if self.timed.is_some() {
+				super::PlayingState::timed(window, seed)
+			} else {
+				super::PlayingState::new(window, seed)
+			}))
 		} else if menu_clicked.was_clicked() {
 			Some(Box::new(super::MenuState::new(window)))
 		} else if me_clicked.was_clicked() {
@@ -136,6 +227,9 @@ widget_ids! {
 		play_again_button,
 		menu_button,
 		results_text,
+		score_text,
+		share_text,
+		first_try_text,
 		created_text,
 		me_link,
 		for_text,
@@ -0,0 +1,121 @@
+/// How much of each new frame time goes into the average.
+const SMOOTHING: f32 = 0.05;
+/// Frames slower than this, in seconds, are hitches or pauses and are left
+/// out of the average.
+const MAX_FRAME_TIME: f32 = 0.25;
+/// How much slower than the target the average may be before shrinking.
+const SHRINK_ABOVE: f32 = 1.2;
+/// How close to the target the average must be before growing.
+const GROW_BELOW: f32 = 1.05;
+const SHRINK_COOLDOWN: u32 = 60;
+const GROW_COOLDOWN: u32 = 300;
+const MAX_GROW_COOLDOWN: u32 = 4800;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ViewChange {
+	Shrink,
+	Grow,
+}
+
+/// Keeps an average of how long frames take, and tells when the view
+/// distance should change to hold a target frame rate. Every time growing
+/// has to be undone, it waits twice as long before growing again.
+pub struct FrameTimeMonitor {
+	target: f32,
+	average: f32,
+	frames: u32,
+	grow_cooldown: u32,
+	grew: bool,
+}
+
+impl FrameTimeMonitor {
+	pub fn new(target_fps: f32) -> Self {
+		Self {
+			target: 1.0 / target_fps,
+			average: 1.0 / target_fps,
+			frames: 0,
+			grow_cooldown: GROW_COOLDOWN,
+			grew: false,
+		}
+	}
+
+	/// Records the time of a frame in seconds.
+	pub fn record(&mut self, frame_time: f32) -> Option<ViewChange> {
+		if frame_time > MAX_FRAME_TIME {
+			return None;
+		}
+		self.average += (frame_time - self.average) * SMOOTHING;
+		self.frames = self.frames.saturating_add(1);
+
+		if self.average > self.target * SHRINK_ABOVE && self.frames >= SHRINK_COOLDOWN {
+			if self.grew {
+				self.grow_cooldown = (self.grow_cooldown * 2).min(MAX_GROW_COOLDOWN);
+			}
+			self.frames = 0;
+			self.grew = false;
+			Some(ViewChange::Shrink)
+		} else if self.average < self.target * GROW_BELOW && self.frames >= self.grow_cooldown {
+			self.frames = 0;
+			self.grew = true;
+			Some(ViewChange::Grow)
+		} else {
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn frames_until_change(monitor: &mut FrameTimeMonitor, frame_time: f32) -> (u32, ViewChange) {
+		for frame in 1..=10_000 {
+			if let Some(change) = monitor.record(frame_time) {
+				return (frame, change);
+			}
+		}
+		panic!("The view distance never changed");
+	}
+
+	#[test]
+	fn slow_frames_shrink() {
+		let mut monitor = FrameTimeMonitor::new(60.0);
+		let (frames, change) = frames_until_change(&mut monitor, 1.0 / 30.0);
+		assert_eq!(change, ViewChange::Shrink);
+		assert!(frames >= SHRINK_COOLDOWN);
+	}
+
+	#[test]
+	fn frames_on_target_grow() {
+		let mut monitor = FrameTimeMonitor::new(60.0);
+		assert_eq!(
+			frames_until_change(&mut monitor, 1.0 / 60.0),
+			(GROW_COOLDOWN, ViewChange::Grow)
+		);
+	}
+
+	#[test]
+	fn pauses_are_ignored() {
+		let mut monitor = FrameTimeMonitor::new(60.0);
+		for _ in 0..1000 {
+			assert_eq!(monitor.record(5.0), None);
+		}
+		assert_eq!(monitor.frames, 0);
+	}
+
+	#[test]
+	fn undone_growth_backs_off() {
+		let mut monitor = FrameTimeMonitor::new(60.0);
+		assert_eq!(
+			frames_until_change(&mut monitor, 1.0 / 60.0).1,
+			ViewChange::Grow
+		);
+		assert_eq!(
+			frames_until_change(&mut monitor, 1.0 / 20.0).1,
+			ViewChange::Shrink
+		);
+		let (frames, change) = frames_until_change(&mut monitor, 1.0 / 60.0);
+		assert_eq!(change, ViewChange::Grow);
+		assert!(frames >= 2 * GROW_COOLDOWN);
+	}
+}
@@ -0,0 +1,172 @@
+use std::f32;
+
+use kiss3d::{
+	nalgebra::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3},
+	scene::SceneNode,
+	window::Window,
+};
+
+use super::{
+	chunks::create_billboard,
+	replay::{unzigzag, write_varint, zigzag, Reader},
+};
+
+/// Ticks between the positions kept of a trajectory.
+pub const SAMPLE_TICKS: u64 = 6;
+/// Positions are kept in steps of this length.
+const RESOLUTION: f32 = 0.01;
+const GHOST_WIDTH: f32 = 0.6;
+const GHOST_HEIGHT: f32 = 1.4;
+/// Height of the middle of a ghost, which puts its head just above the eyes
+/// of the Agent.
+const GHOST_MIDDLE: f32 = -0.3;
+
+/// Where the Agent was on the floor during a run, every [`SAMPLE_TICKS`]
+/// ticks.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Trajectory {
+	points: Vec<(i64, i64)>,
+}
+
+impl Trajectory {
+	/// Keeps `eye` if `tick` is the next one to keep a position for.
+	pub fn record(&mut self, tick: u64, eye: &Point3<f32>) {
+		if tick == self.points.len() as u64 * SAMPLE_TICKS {
+			self.points.push((
+				(eye.x / RESOLUTION).round() as i64,
+				(eye.z / RESOLUTION).round() as i64,
+			));
+		}
+	}
+
+	/// Where on the floor the Agent was on `tick`, between the kept positions
+	/// around it. `None` after the last one.
+	pub fn position_at(&self, tick: u64) -> Option<(f32, f32)> {
+		let index = (tick / SAMPLE_TICKS) as usize;
+		let from = *self.points.get(index)?;
+		let to = *self.points.get(index + 1).unwrap_or(&from);
+		let t = (tick % SAMPLE_TICKS) as f32 / SAMPLE_TICKS as f32;
+		let lerp = |a: i64, b: i64| (a as f32 + (b - a) as f32 * t) * RESOLUTION;
+		Some((lerp(from.0, to.0), lerp(from.1, to.1)))
+	}
+
+	/// The positions as the differences from the one before, which are
+	/// small enough to mostly fit in a byte each.
+	pub fn encode(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		let mut previous = (0, 0);
+		for point in self.points.iter() {
+			write_varint(&mut bytes, zigzag(point.0 - previous.0));
+			write_varint(&mut bytes, zigzag(point.1 - previous.1));
+			previous = *point;
+		}
+		bytes
+	}
+
+	pub fn decode(bytes: &[u8]) -> Option<Self> {
+		let mut reader = Reader::new(bytes);
+		let mut points = Vec::new();
+		let mut previous: (i64, i64) = (0, 0);
+		while !reader.is_empty() {
+			let x = previous.0.checked_add(unzigzag(reader.varint().ok()?))?;
+			let z = previous.1.checked_add(unzigzag(reader.varint().ok()?))?;
+			previous = (x, z);
+			points.push(previous);
+		}
+		Some(Self {
+			points,
+		})
+	}
+
+	/// The encoded trajectory written as hexadecimal, to be stored as text.
+	pub fn to_hex(&self) -> String {
+		self.encode()
+			.iter()
+			.map(|byte| format!("{:02x}", byte))
+			.collect()
+	}
+
+	pub fn from_hex(hex: &str) -> Option<Self> {
+		if hex.len() % 2 != 0 {
+			return None;
+		}
+		let bytes = (0..hex.len())
+			.step_by(2)
+			.map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+			.collect::<Option<Vec<_>>>()?;
+		Self::decode(&bytes)
+	}
+}
+
+/// A see-through Agent, turned to always face the camera.
+pub struct GhostNode {
+	node: SceneNode,
+}
+
+impl GhostNode {
+	pub fn new(window: &mut Window) -> Self {
+		let mut node = create_billboard(window, "ghost", GHOST_WIDTH, GHOST_HEIGHT);
+		node.set_visible(false);
+		Self {
+			node,
+		}
+	}
+
+	/// Moves the ghost to `(x, z)` on the floor, facing `eye`.
+	pub fn place(&mut self, (x, z): (f32, f32), eye: &Point3<f32>) {
+		let yaw = (eye.x - x).atan2(eye.z - z);
+		self.node.set_local_transformation(Isometry3::from_parts(
+			Translation3::new(x, GHOST_MIDDLE, z),
+			UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw),
+		));
+	}
+
+	pub fn set_visible(&mut self, visible: bool) {
+		self.node.set_visible(visible);
+	}
+
+	pub fn remove(&mut self, window: &mut Window) {
+		window.remove_node(&mut self.node);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn trajectory() -> Trajectory {
+		let mut trajectory = Trajectory::default();
+		for tick in 0..=60 {
+			let eye = Point3::new(tick as f32 * 0.03, 0.25, -(tick as f32) * 0.01);
+			trajectory.record(tick, &eye);
+		}
+		trajectory
+	}
+
+	#[test]
+	fn positions_are_kept_every_few_ticks() {
+		let trajectory = trajectory();
+		assert_eq!(trajectory.points.len(), 11);
+		let (x, z) = trajectory.position_at(9).unwrap();
+		assert!((x - 0.27).abs() < 1e-4 && (z + 0.09).abs() < 1e-4);
+		assert!(trajectory.position_at(60).is_some());
+		assert_eq!(trajectory.position_at(61 + SAMPLE_TICKS), None);
+	}
+
+	#[test]
+	fn trajectories_round_trip() {
+		let trajectory = trajectory();
+		let bytes = trajectory.encode();
+		assert_eq!(bytes.len(), 2 * trajectory.points.len());
+		assert_eq!(Trajectory::decode(&bytes), Some(trajectory.clone()));
+		assert_eq!(Trajectory::from_hex(&trajectory.to_hex()), Some(trajectory));
+		assert_eq!(Trajectory::from_hex("0"), None);
+		assert_eq!(Trajectory::from_hex("zz"), None);
+		assert_eq!(Trajectory::decode(&[0x80]), None);
+		let mut overflowing = Vec::new();
+		for value in [i64::MAX, 0, 1, 0].iter() {
+			write_varint(&mut overflowing, zigzag(*value));
+		}
+		assert_eq!(Trajectory::decode(&overflowing), None);
+	}
+}
@@ -0,0 +1,181 @@
+use kiss3d::{
+	event::{Action, Key},
+	nalgebra::Vector2,
+	window::Window,
+};
+
+use super::{MouseAction, MouseButtons};
+
+/// Number of ticks the simulation is tuned for in a second. A tick is run for
+/// every frame, so this is the frame rate the game is meant to run at.
+pub const TICKS_PER_SECOND: f32 = 60.0;
+
+/// Set of things the Agent can be told to do during a tick.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Actions(u8);
+
+impl Actions {
+	const ALL: u8 = (1 << 5) - 1;
+	pub const BACKWARD: Self = Self(1 << 1);
+	pub const FORWARD: Self = Self(1);
+	pub const LEFT: Self = Self(1 << 3);
+	pub const RIGHT: Self = Self(1 << 2);
+	pub const USE: Self = Self(1 << 4);
+
+	#[inline]
+	pub fn bits(&self) -> u8 {
+		self.0
+	}
+
+	/// Returns `None` if any bit does not belong to an action.
+	pub fn from_bits(bits: u8) -> Option<Self> {
+		if bits & !Self::ALL == 0 {
+			Some(Self(bits))
+		} else {
+			None
+		}
+	}
+
+	#[inline]
+	pub fn contains(&self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	#[inline]
+	pub fn insert(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// Everything the simulation is told from the outside during one tick.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TickInput {
+	pub actions: Actions,
+	/// How far the cursor moved, in pixels.
+	pub look: (i16, i16),
+}
+
+impl TickInput {
+	#[inline]
+	pub fn look_vector(&self) -> Vector2<f32> {
+		Vector2::new(self.look.0 as f32, self.look.1 as f32)
+	}
+}
+
+/// The keys that move the Agent, so that players taking turns at the same
+/// keyboard can each have their own.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ActionMap {
+	pub forward: Key,
+	pub backward: Key,
+	pub right: Key,
+	pub left: Key,
+}
+
+impl ActionMap {
+	pub const ARROWS: Self = Self {
+		forward: Key::Up,
+		backward: Key::Down,
+		right: Key::Right,
+		left: Key::Left,
+	};
+	pub const WASD: Self = Self {
+		forward: Key::W,
+		backward: Key::S,
+		right: Key::D,
+		left: Key::A,
+	};
+
+	/// The action map of each player, by player number starting from zero.
+	pub fn for_player(player: usize) -> Self {
+		[Self::WASD, Self::ARROWS][player % 2]
+	}
+
+	/// Whether the forward, backward, right, and left keys are held down.
+	pub fn held(&self, window: &Window) -> [bool; 4] {
+		let held = |key| window.get_key(key) == Action::Press;
+		[
+			held(self.forward),
+			held(self.backward),
+			held(self.right),
+			held(self.left),
+		]
+	}
+}
+
+/// Turns live input into tick inputs. Cursor movement is rounded to whole
+/// pixels, with what is left over carried on to the next tick.
+#[derive(Default)]
+pub struct InputReader {
+	remainder: Vector2<f32>,
+}
+
+impl InputReader {
+	pub fn read(
+		&mut self,
+		forward: bool,
+		backward: bool,
+		right: bool,
+		left: bool,
+		mouse_buttons: &MouseButtons,
+		cursor_movement: Vector2<f32>,
+	) -> TickInput {
+		let mut actions = Actions::default();
+		for (pressed, action) in [
+			(forward, Actions::FORWARD),
+			(backward, Actions::BACKWARD),
+			(right, Actions::RIGHT),
+			(left, Actions::LEFT),
+			(mouse_buttons.lmb == MouseAction::Pressed, Actions::USE),
+		]
+		.iter()
+		{
+			if *pressed {
+				actions.insert(*action);
+			}
+		}
+
+		let movement = self.remainder + cursor_movement;
+		let look = (
+			movement.x.round().clamp(i16::MIN as f32, i16::MAX as f32),
+			movement.y.round().clamp(i16::MIN as f32, i16::MAX as f32),
+		);
+		self.remainder = movement - Vector2::new(look.0, look.1);
+		TickInput {
+			actions,
+			look: (look.0 as i16, look.1 as i16),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unknown_action_bits_are_rejected() {
+		assert_eq!(Actions::from_bits(0b1_0011), Some(Actions(0b1_0011)));
+		assert_eq!(Actions::from_bits(0b10_0000), None);
+	}
+
+	#[test]
+	fn sub_pixel_movement_is_carried_over() {
+		let mut reader = InputReader::default();
+		let buttons = MouseButtons::default();
+		let looks: Vec<_> = (0..4)
+			.map(|_| {
+				reader
+					.read(
+						false,
+						false,
+						false,
+						false,
+						&buttons,
+						Vector2::new(0.4, -0.75),
+					)
+					.look
+			})
+			.collect();
+		assert_eq!(looks, vec![(0, -1), (1, -1), (0, 0), (1, -1)]);
+	}
+}
@@ -0,0 +1,252 @@
+//! Rare sections that are laid out by hand rather than carved at random, so
+//! that the Agent has memorable places to find its way by. They are written
+//! in `landmarks.json`, with the cells and walls of each drawn as text.
+
+use std::{collections::HashSet, fmt, rc::Rc};
+
+use rand::Rng;
+use serde::Deserialize;
+
+use super::super::map::{Map, Position, DIRECTIONS, ROOM_SIZE};
+
+const BUILT_IN: &str = include_str!("./landmarks.json");
+/// Odds of a section that holds no key and no lock being a landmark.
+pub const LANDMARK_ODDS: f32 = 0.03;
+/// Number of lines in a plan, one for each row of cells and one for the walls
+/// between each two rows.
+const PLAN_LINES: usize = 2 * ROOM_SIZE - 1;
+
+thread_local! {
+	static LANDMARKS: Vec<Rc<Landmark>> = Landmark::parse_all(BUILT_IN)
+		.expect("the built-in landmarks are valid")
+		.into_iter()
+		.map(Rc::new)
+		.collect();
+}
+
+/// One of the landmarks, picked with `rng`.
+pub fn pick_landmark<R: Rng>(rng: &mut R) -> Rc<Landmark> {
+	LANDMARKS.with(|landmarks| landmarks[rng.gen_range(0..landmarks.len())].clone())
+}
+
+pub struct Landmark {
+	/// Id of the message that names a section with the landmark, given the
+	/// made up `name` of the section.
+	pub name: String,
+	pub map: Map,
+	/// Cells with a coin in them.
+	pub coins: Vec<Position>,
+	/// Cell of the Agent who fell here, if one did.
+	pub body: Option<Position>,
+	/// Whether the floor is under water, which slows the Agent down.
+	pub flooded: bool,
+	/// Id of the message written on the note the fallen Agent left.
+	pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LandmarkData {
+	name: String,
+	/// A line for each row of cells, with a `|` between the cells that have
+	/// a wall between them, and a line below each row but the last, with a
+	/// `-` under the cells that have a wall below them. Cells are a `.`, a
+	/// `c` for a coin, or an `A` for the fallen Agent.
+	plan: Vec<String>,
+	#[serde(default)]
+	flooded: bool,
+	#[serde(default)]
+	note: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum LandmarkError {
+	Json(serde_json::Error),
+	/// The plan of the landmark `name` is not drawn right.
+	Plan {
+		name: String,
+		reason: &'static str,
+	},
+}
+
+impl Landmark {
+	/// Reads landmarks from JSON, checking that every cell of their plans can
+	/// be reached.
+	pub fn parse_all(json: &str) -> Result<Vec<Self>, LandmarkError> {
+		let data: Vec<LandmarkData> = serde_json::from_str(json).map_err(LandmarkError::Json)?;
+		data.into_iter()
+			.map(|data| {
+				Self::from_data(&data).map_err(|reason| LandmarkError::Plan {
+					name: data.name,
+					reason,
+				})
+			})
+			.collect()
+	}
+
+	fn from_data(data: &LandmarkData) -> Result<Self, &'static str> {
+		if data.plan.len() != PLAN_LINES {
+			return Err("expected a line for each row of cells and each row of walls");
+		}
+		let mut map = Map::new();
+		let mut coins = Vec::new();
+		let mut body = None;
+		for (i, line) in data.plan.iter().enumerate() {
+			let line: Vec<char> = line.chars().collect();
+			if line.len() > PLAN_LINES {
+				return Err("a line is longer than the section is wide");
+			}
+			let at = |j: usize| line.get(j).copied().unwrap_or(' ');
+			let row = i / 2;
+			for col in 0..ROOM_SIZE {
+				let pos = Position(row, col);
+				if i % 2 == 1 {
+					match at(2 * col) {
+						'-' => {}
+						' ' => map.set_below(&pos, false),
+						_ => return Err("expected - or a space below a cell"),
+					}
+					continue;
+				}
+				match at(2 * col) {
+					'.' => {}
+					'c' => coins.push(pos),
+					'A' if body.is_none() => body = Some(pos),
+					'A' => return Err("only one Agent can have fallen in a section"),
+					_ => return Err("expected ., c, or A for a cell"),
+				}
+				if col + 1 < ROOM_SIZE {
+					match at(2 * col + 1) {
+						'|' => {}
+						' ' => map.set_right(&pos, false),
+						_ => return Err("expected | or a space between cells"),
+					}
+				}
+			}
+		}
+		if data.note.is_some() && body.is_none() {
+			return Err("a note needs a fallen Agent to have left it");
+		}
+		if reachable_cells(&map) < ROOM_SIZE * ROOM_SIZE {
+			return Err("not every cell can be reached");
+		}
+		Ok(Self {
+			name: data.name.clone(),
+			map,
+			coins,
+			body,
+			flooded: data.flooded,
+			note: data.note.clone(),
+		})
+	}
+}
+
+/// Number of cells that can be reached from the first one.
+fn reachable_cells(map: &Map) -> usize {
+	let mut reached = HashSet::new();
+	let mut stack = vec![Position(0, 0)];
+	while let Some(pos) = stack.pop() {
+		if !reached.insert(pos) {
+			continue;
+		}
+		for dir in DIRECTIONS.iter() {
+			if map.is(&pos, dir) == Some(false) {
+				stack.extend(map.move_in_direction(&pos, dir));
+			}
+		}
+	}
+	reached.len()
+}
+
+impl fmt::Display for LandmarkError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Json(e) => write!(f, "{}", e),
+			Self::Plan {
+				name,
+				reason,
+			} => write!(f, "the plan of {} is wrong, {}", name, reason),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn plan_error(plan: &[&str]) -> Option<&'static str> {
+		let data = LandmarkData {
+			name: "test".to_string(),
+			plan: plan.iter().map(|line| line.to_string()).collect(),
+			flooded: false,
+			note: None,
+		};
+		Landmark::from_data(&data).err()
+	}
+
+	#[test]
+	fn built_in_landmarks_are_valid() {
+		let landmarks = Landmark::parse_all(BUILT_IN).unwrap();
+		assert!(landmarks.iter().any(|l| l.flooded));
+		assert!(landmarks.iter().any(|l| l.coins.len() > 1));
+		assert!(landmarks
+			.iter()
+			.any(|l| l.body.is_some() && l.note.is_some()));
+	}
+
+	#[test]
+	fn plans_are_drawn_as_cells_and_walls() {
+		let landmark = Landmark::from_data(&LandmarkData {
+			name: "test".to_string(),
+			plan: [
+				"c|. . . .",
+				"         ",
+				". . . . .",
+				"-        ",
+				". . . . .",
+				"",
+				". . A . .",
+				"         ",
+				". . . . .",
+			]
+			.iter()
+			.map(|line| line.to_string())
+			.collect(),
+			flooded: false,
+			note: Some("note".to_string()),
+		})
+		.unwrap();
+		assert!(landmark.map.is_right(&Position(0, 0)));
+		assert!(!landmark.map.is_right(&Position(0, 1)));
+		assert!(landmark.map.is_below(&Position(1, 0)));
+		assert!(!landmark.map.is_below(&Position(1, 1)));
+		assert_eq!(landmark.coins, [Position(0, 0)]);
+		assert_eq!(landmark.body, Some(Position(3, 2)));
+	}
+
+	#[test]
+	fn broken_plans_are_refused() {
+		assert_eq!(
+			plan_error(&[". . . . ."]),
+			Some("expected a line for each row of cells and each row of walls")
+		);
+		assert_eq!(
+			plan_error(&[
+				". . . . .",
+				"- - - - -",
+				". . . . .",
+				"",
+				". . . . .",
+				"",
+				". . . . .",
+				"",
+				". . . . .",
+			]),
+			Some("not every cell can be reached")
+		);
+		assert_eq!(
+			plan_error(&[". . x . .", "", "", "", "", "", "", "", ""]),
+			Some("expected ., c, or A for a cell")
+		);
+	}
+}
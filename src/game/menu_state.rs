@@ -10,7 +10,11 @@ use kiss3d::{
 	window::Window,
 };
 
-use super::{super::GAME_NAME, InnerGameState, MouseButtons};
+use super::{
+	super::{daily::Date, locale::tr, GAME_NAME},
+	InnerGameState,
+	MouseButtons,
+};
 
 pub struct MenuState {
 	ui_ids: UiIds,
@@ -27,6 +31,11 @@ impl MenuState {
 impl InnerGameState for MenuState {
 	fn step(&mut self, window: &mut Window, _: &MouseButtons) -> Option<Box<dyn InnerGameState>> {
 		let start_clicked;
+		let timed_clicked;
+		let daily_clicked;
+		let race_clicked;
+		let coop_clicked;
+		let settings_clicked;
 		#[cfg(not(target_arch = "wasm32"))]
 		let exit_clicked;
 		let me_clicked;
@@ -46,11 +55,61 @@ impl InnerGameState for MenuState {
 				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-				.label("Start New")
+				.label(&tr("start-new"))
 				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.w(200.0)
-				.mid_bottom_with_margin(200.0)
+				.mid_bottom_with_margin(400.0)
 				.set(self.ui_ids.start_button, &mut ui);
+			timed_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("timed-escape"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(350.0)
+				.set(self.ui_ids.timed_button, &mut ui);
+			daily_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("daily-maze"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(300.0)
+				.set(self.ui_ids.daily_button, &mut ui);
+			race_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("hot-seat-race"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(250.0)
+				.mid_bottom_with_margin(250.0)
+				.set(self.ui_ids.race_button, &mut ui);
+			coop_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("co-op"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(200.0)
+				.set(self.ui_ids.coop_button, &mut ui);
+			settings_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("settings"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(150.0)
+				.set(self.ui_ids.settings_button, &mut ui);
 
 			#[cfg(not(target_arch = "wasm32"))]
 			{
@@ -59,14 +118,14 @@ impl InnerGameState for MenuState {
 					.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 					.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 					.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-					.label("Exit")
+					.label(&tr("exit"))
 					.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 					.w(200.0)
 					.mid_bottom_with_margin(100.0)
 					.set(self.ui_ids.exit_button, &mut ui);
 			}
 
-			widget::Text::new("Created by")
+			widget::Text::new(&tr("created-by"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.bottom_left_with_margin(10.0)
@@ -81,7 +140,7 @@ impl InnerGameState for MenuState {
 				.label_color(Color::Rgba(0.0, 0.5, 1.0, 1.0))
 				.right_from(self.ui_ids.created_text, 15.0)
 				.set(self.ui_ids.me_link, &mut ui);
-			widget::Text::new("for the")
+			widget::Text::new(&tr("for-the"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.right_from(self.ui_ids.me_link, 17.0)
@@ -102,6 +161,16 @@ impl InnerGameState for MenuState {
 		{
 			if start_clicked.was_clicked() {
 				Some(Box::new(super::StoryState::new(window)))
+			} else if timed_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::timed(window)))
+			} else if daily_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::daily(window, Date::today())))
+			} else if race_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::race(window)))
+			} else if coop_clicked.was_clicked() {
+				Some(Box::new(super::CoopState::new(window)))
+			} else if settings_clicked.was_clicked() {
+				Some(Box::new(super::SettingsState::new(window, None)))
 			} else if me_clicked.was_clicked() {
 				let _ = webbrowser::open("https://åsberg.net/");
 				None
@@ -116,6 +185,16 @@ impl InnerGameState for MenuState {
 		{
 			if start_clicked.was_clicked() {
 				Some(Box::new(super::StoryState::new(window)))
+			} else if timed_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::timed(window)))
+			} else if daily_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::daily(window, Date::today())))
+			} else if race_clicked.was_clicked() {
+				Some(Box::new(super::StoryState::race(window)))
+			} else if coop_clicked.was_clicked() {
+				Some(Box::new(super::CoopState::new(window)))
+			} else if settings_clicked.was_clicked() {
+				Some(Box::new(super::SettingsState::new(window, None)))
 			} else if exit_clicked.was_clicked() {
 				window.close();
 				None
@@ -137,6 +216,11 @@ widget_ids! {
 	struct UiIds {
 		title,
 		start_button,
+		timed_button,
+		daily_button,
+		race_button,
+		coop_button,
+		settings_button,
 		created_text,
 		me_link,
 		for_text,
@@ -148,6 +232,11 @@ widget_ids! {
 	struct UiIds {
 		title,
 		start_button,
+		timed_button,
+		daily_button,
+		race_button,
+		coop_button,
+		settings_button,
 		exit_button,
 		created_text,
 		me_link,
@@ -7,19 +7,61 @@ use kiss3d::{
 	window::{State, Window},
 };
 
+use super::locale::use_font;
+
+mod bot;
+mod chunks;
+mod coop;
+mod coop_state;
+mod countdown;
+mod door;
 mod end_state;
+mod frame_time;
+mod ghost;
+mod input;
+mod landmark;
 mod menu_state;
 mod monster;
 mod pause_state;
 mod playing_state;
+mod race;
+mod race_state;
+mod radio;
+mod replay;
+mod section;
+mod section_generator;
+mod section_mesh;
+mod settings_state;
+mod simulation;
 mod story_state;
+mod visibility;
 mod wall;
 
+pub use bot::{
+	bot_by_name,
+	run_bot,
+	Bot,
+	BotRun,
+	FrontierExplorer,
+	OptimalPlanner,
+	WallFollower,
+	BOT_NAMES,
+};
+pub use chunks::Chunks;
+pub use coop::set_relay_url;
+pub use coop_state::CoopState;
 pub use end_state::EndState;
+pub use input::TICKS_PER_SECOND;
 pub use menu_state::MenuState;
 pub use pause_state::PauseState;
 pub use playing_state::PlayingState;
+pub use race_state::RaceState;
+pub use replay::{set_record_path, Replay};
+pub use section::{SectionLayout, WallSlot};
+pub use settings_state::SettingsState;
+pub use simulation::{RunStats, Simulation, TickEvents};
 pub use story_state::StoryState;
+pub use visibility::WorldCell;
 
 pub type CamerasEffectRenderer<'a> = (
 	Option<&'a mut dyn Camera>,
@@ -59,6 +101,7 @@ impl GameState {
 
 impl State for GameState {
 	fn step(&mut self, window: &mut Window) {
+		use_font(window);
 		self.mouse_buttons.step();
 		for mut event in window.events().iter() {
 			if let WindowEvent::Key(Key::Escape, _, _) = event.value {
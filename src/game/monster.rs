@@ -1,33 +1,37 @@
-use instant::Instant;
-
-use super::playing_state::MAZE_CHUNK_SIZE;
+use super::{input::TICKS_PER_SECOND, section::MAZE_CHUNK_SIZE};
 
 const VISIBLE_TIME: f32 = 0.4;
 
 pub struct Monster {
-	in_range_time: Option<Instant>,
+	in_range_tick: Option<u64>,
 }
 
 impl Default for Monster {
 	fn default() -> Self {
 		Self {
-			in_range_time: None,
+			in_range_tick: None,
 		}
 	}
 }
 
 impl Monster {
-	pub fn update(&mut self, distance: f32) -> bool {
-		if let Some(time) = self.in_range_time {
+	/// Whether the Agent is close enough to have been noticed.
+	#[inline]
+	pub fn is_near(&self) -> bool {
+		self.in_range_tick.is_some()
+	}
+
+	pub fn update(&mut self, distance: f32, tick: u64) -> bool {
+		if let Some(in_range_tick) = self.in_range_tick {
 			if distance < MAZE_CHUNK_SIZE {
-				time.elapsed().as_secs_f32() < VISIBLE_TIME
+				((tick - in_range_tick) as f32 / TICKS_PER_SECOND) < VISIBLE_TIME
 			} else {
-				self.in_range_time = None;
+				self.in_range_tick = None;
 				true
 			}
 		} else {
 			if distance < MAZE_CHUNK_SIZE {
-				self.in_range_time = Some(Instant::now());
+				self.in_range_tick = Some(tick);
 			}
 			true
 		}
@@ -10,7 +10,12 @@ use kiss3d::{
 	window::Window,
 };
 
-use super::{super::GAME_NAME, playing_state::SavedPlayingState, InnerGameState, MouseButtons};
+use super::{
+	super::{locale::tr, GAME_NAME},
+	playing_state::SavedPlayingState,
+	InnerGameState,
+	MouseButtons,
+};
 
 pub struct PauseState {
 	ui_ids: UiIds,
@@ -29,6 +34,7 @@ impl PauseState {
 impl InnerGameState for PauseState {
 	fn step(&mut self, window: &mut Window, _: &MouseButtons) -> Option<Box<dyn InnerGameState>> {
 		let continue_clicked;
+		let settings_clicked;
 		let menu_clicked;
 		let me_clicked;
 		let game_jam_clicked;
@@ -47,23 +53,33 @@ impl InnerGameState for PauseState {
 				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-				.label("Continue")
+				.label(&tr("continue"))
 				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.w(200.0)
 				.mid_bottom_with_margin(200.0)
 				.set(self.ui_ids.continue_button, &mut ui);
+			settings_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("settings"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(150.0)
+				.set(self.ui_ids.settings_button, &mut ui);
 			menu_clicked = widget::Button::new()
 				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
 				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
-				.label("Main Menu")
+				.label(&tr("main-menu"))
 				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.w(200.0)
 				.mid_bottom_with_margin(100.0)
 				.set(self.ui_ids.menu_button, &mut ui);
 
-			widget::Text::new("Created by")
+			widget::Text::new(&tr("created-by"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.bottom_left_with_margin(10.0)
@@ -78,7 +94,7 @@ impl InnerGameState for PauseState {
 				.label_color(Color::Rgba(0.0, 0.5, 1.0, 1.0))
 				.right_from(self.ui_ids.created_text, 15.0)
 				.set(self.ui_ids.me_link, &mut ui);
-			widget::Text::new("for the")
+			widget::Text::new(&tr("for-the"))
 				.font_size(12)
 				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
 				.right_from(self.ui_ids.me_link, 17.0)
@@ -100,6 +116,11 @@ impl InnerGameState for PauseState {
 				window,
 				&self.playing_state,
 			)))
+		} else if settings_clicked.was_clicked() {
+			Some(Box::new(super::SettingsState::new(
+				window,
+				Some(self.playing_state.clone()),
+			)))
 		} else if menu_clicked.was_clicked() {
 			Some(Box::new(super::MenuState::new(window)))
 		} else if me_clicked.was_clicked() {
@@ -118,6 +139,7 @@ widget_ids! {
 	struct UiIds {
 		title,
 		continue_button,
+		settings_button,
 		menu_button,
 		created_text,
 		me_link,
@@ -1,21 +1,18 @@
-use std::{
-	collections::{HashMap, HashSet},
-	f32,
-};
+use std::{f32, mem, time::Duration};
 
 use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use kiss3d::event::{Action, Key};
 #[cfg(target_arch = "wasm32")]
 use kiss3d::nalgebra::Vector2;
 use kiss3d::{
 	conrod::{
-		color::Colorable,
+		color::{Color, Colorable},
 		position::Positionable,
 		widget::{self, Widget},
 		widget_ids,
 	},
-	event::{Action, Key},
-	nalgebra::{distance, Point3, Translation3, UnitQuaternion, Vector3},
-	scene::SceneNode,
+	nalgebra::{Point3, Translation3, UnitQuaternion, Vector3},
 	window::Window,
 };
 use rand::{rngs::StdRng, Rng};
@@ -24,93 +21,417 @@ use rand::{rngs::StdRng, Rng};
 use super::super::js::{get_cursor_movement, get_focus, hide_cursor, JsVector2};
 use super::{
 	super::{
-		camera::FirstPerson,
-		map::{Direction, Map, Position, ROOM_CENTER, ROOM_SIZE},
-		meshes::{generate_coin, generate_key, generate_lock, ItemKind, MeshGenerator},
-		rng::{rand_for_border_walls, rand_for_key, rng_for_maze},
-		text::generate_name,
-		textures::hsl_to_rgb,
+		coop::Connection,
+		daily::Date,
+		dialogue::{Event, Facts, Mode},
+		effects::{EffectChain, SCREEN_EFFECTS},
+		locale::{language, tr, tr_with},
+		map::ROOM_SIZE,
+		meshes::ItemKind,
+		rng::rng_for_maze,
+		settings::{settings, Settings, MIN_VIEW_DISTANCE},
+		stats::{record_best_run, record_daily, BestRun, DailyResult, Stats},
+		text::{section_name, NameTheme},
+		textures::{set_fog, FogSettings},
 	},
-	monster::Monster,
-	wall::Wall,
+	chunks::swing_door,
+	coop::Partner,
+	countdown,
+	frame_time::{FrameTimeMonitor, ViewChange},
+	ghost::{GhostNode, Trajectory},
+	input::{ActionMap, InputReader, TickInput, TICKS_PER_SECOND},
+	landmark::Landmark,
+	race::{Ghost, Race},
+	radio::{Radio, TRANSMISSION_SECONDS},
+	replay::{is_recording, save_recording, Replay, VERSION},
+	section::{exposure, MAZE_CHUNK_SIZE, MAZE_SIZE},
+	section_generator::manhattan,
+	simulation::{SavedSimulation, Simulation, TickEvents},
+	visibility::{ViewCone, WorldCell},
 	CamerasEffectRenderer,
+	Chunks,
 	InnerGameState,
-	MouseAction,
 	MouseButtons,
 };
 
-type Chunks = HashMap<
-	(i64, i64),
-	(
-		Vec<Wall>,
-		SceneNode,
-		Option<(SceneNode, ItemKind)>,
-		Vec<(SceneNode, Monster)>,
-	),
->;
-
 pub struct PlayingState {
-	camera: FirstPerson,
-	seed: u64,
-	start_time: Instant,
+	simulation: Simulation,
+	input: InputSource,
+	input_reader: InputReader,
 	ui_ids: UiIds,
 	chunks: Chunks,
-	position: (i64, i64),
+	view_distance: i64,
+	frame_time: FrameTimeMonitor,
+	last_frame: Instant,
 	section_name: String,
-	section_name_start_time: Instant,
-	has_key: bool,
-	collected_items: HashSet<(i64, i64)>,
+	section_name_tick: u64,
+	fog: FogSettings,
+	target_fog: FogSettings,
+	effects: EffectChain,
+	/// The date of the Daily Maze being played, if it is one.
+	daily: Option<Date>,
+	/// The turn being played, if this is a race.
+	race: Option<Race>,
+	ghost: Option<Ghost>,
+	/// Where the Agent has been so far.
+	trajectory: Trajectory,
+	/// Where the Agent went on the fastest escape so far from this seed.
+	best_trajectory: Option<Trajectory>,
+	best_ghost: Option<GhostNode>,
+	/// The other Agent, if this is a co-op run.
+	partner: Option<Partner>,
+	partner_node: Option<GhostNode>,
+	radio: Radio,
 }
 
+#[derive(Clone)]
 pub struct SavedPlayingState {
-	camera_eye: Point3<f32>,
-	camera_at: Point3<f32>,
-	seed: u64,
-	position: (i64, i64),
-	has_key: bool,
-	collected_items: HashSet<(i64, i64)>,
+	simulation: SavedSimulation,
+	input: InputSource,
+	daily: Option<Date>,
+	race: Option<Race>,
+	trajectory: Trajectory,
+	best_trajectory: Option<Trajectory>,
+	partner: Option<Partner>,
+	radio: Radio,
+}
+
+/// Where the input of each tick comes from.
+#[derive(Clone)]
+enum InputSource {
+	/// Read from the window, and recorded if there is a recording.
+	Live {
+		recording: Option<Vec<TickInput>>,
+	},
+	Playback(Replay),
 }
 
 impl PlayingState {
 	pub fn new(window: &mut Window, seed: u64) -> Self {
 		let position = (0, 0);
+		let view_distance = settings().view_distance;
 		Self {
-			camera: FirstPerson::new(Point3::new(0.0, 0.25, 0.0), Point3::new(0.0, 0.25, -1.0)),
-			seed,
-			start_time: Instant::now(),
+			simulation: Simulation::new(seed),
+			input: InputSource::Live {
+				recording: if is_recording() {
+					Some(Vec::new())
+				} else {
+					None
+				},
+			},
+			input_reader: InputReader::default(),
 			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
-			chunks: HashMap::new(),
-			position,
-			section_name: get_section_name(seed, position),
-			section_name_start_time: Instant::now(),
-			has_key: false,
-			collected_items: HashSet::new(),
+			chunks: Chunks::new(seed, view_distance),
+			view_distance,
+			frame_time: FrameTimeMonitor::new(TARGET_FPS),
+			last_frame: Instant::now(),
+			section_name: get_section_name(seed, position, None),
+			section_name_tick: 0,
+			fog: section_fog(seed, position, view_distance),
+			target_fog: section_fog(seed, position, view_distance),
+			effects: EffectChain::new(),
+			daily: None,
+			race: None,
+			ghost: None,
+			trajectory: Trajectory::default(),
+			best_trajectory: Stats::load()
+				.best_run(seed, VERSION)
+				.and_then(|run| Trajectory::from_hex(&run.trajectory)),
+			best_ghost: None,
+			partner: None,
+			partner_node: None,
+			radio: Radio::default(),
+		}
+	}
+
+	/// Plays the Daily Maze of `date`.
+	pub fn daily(window: &mut Window, date: Date) -> Self {
+		Self {
+			daily: Some(date),
+			..Self::new(window, date.seed())
+		}
+	}
+
+	/// Plays a run that has to escape before a countdown runs out.
+	pub fn timed(window: &mut Window, seed: u64) -> Self {
+		Self {
+			simulation: Simulation::timed(seed),
+			..Self::new(window, seed)
+		}
+	}
+
+	/// Plays a turn of a race on `seed`. The run is recorded, so that the
+	/// first turn can be raced against in the second.
+	pub fn race(window: &mut Window, seed: u64, race: Race) -> Self {
+		let ghost = match &race {
+			Race::First => None,
+			Race::Second(replay) => Some(Ghost::new(window, replay, 0)),
+		};
+		Self {
+			input: InputSource::Live {
+				recording: Some(Vec::new()),
+			},
+			race: Some(race),
+			ghost,
+			best_trajectory: None,
+			..Self::new(window, seed)
+		}
+	}
+
+	/// Plays a co-op run on `seed` with the other Agent at the other end of
+	/// `connection`. The other Agent changes the run, so it can not be
+	/// recorded.
+	pub fn coop(window: &mut Window, seed: u64, connection: Connection) -> Self {
+		Self {
+			input: InputSource::Live {
+				recording: None,
+			},
+			best_trajectory: None,
+			partner: Some(Partner::new(connection)),
+			..Self::new(window, seed)
+		}
+	}
+
+	/// Plays back a recorded run, checking that it ends the same way.
+	pub fn replay(window: &mut Window, replay: Replay) -> Self {
+		let playing = if replay.timed {
+			Self::timed(window, replay.seed)
+		} else {
+			Self::new(window, replay.seed)
+		};
+		Self {
+			input: InputSource::Playback(replay),
+			..playing
 		}
 	}
 
 	pub fn save(&self) -> SavedPlayingState {
 		SavedPlayingState {
-			camera_eye: *self.camera.eye(),
-			camera_at: self.camera.at(),
-			seed: self.seed,
-			position: self.position,
-			has_key: self.has_key,
-			collected_items: self.collected_items.clone(),
+			simulation: self.simulation.save(),
+			input: self.input.clone(),
+			daily: self.daily,
+			race: self.race.clone(),
+			trajectory: self.trajectory.clone(),
+			best_trajectory: self.best_trajectory.clone(),
+			partner: self.partner.clone(),
+			radio: self.radio.clone(),
 		}
 	}
 
 	pub fn restore(window: &mut Window, save: &SavedPlayingState) -> Self {
+		let simulation = Simulation::restore(&save.simulation);
+		let (seed, position, tick) = (simulation.seed(), simulation.position(), simulation.tick());
+		let section_name = get_section_name(seed, position, simulation.landmark(&position));
+		let view_distance = settings().view_distance;
 		Self {
-			camera: FirstPerson::new(save.camera_eye, save.camera_at),
-			seed: save.seed,
-			start_time: Instant::now(),
+			simulation,
+			input: save.input.clone(),
+			input_reader: InputReader::default(),
 			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
-			chunks: HashMap::new(),
-			position: save.position,
-			section_name: get_section_name(save.seed, save.position),
-			section_name_start_time: Instant::now(),
-			has_key: save.has_key,
-			collected_items: save.collected_items.clone(),
+			chunks: Chunks::new(seed, view_distance),
+			view_distance,
+			frame_time: FrameTimeMonitor::new(TARGET_FPS),
+			last_frame: Instant::now(),
+			section_name,
+			section_name_tick: tick,
+			fog: section_fog(seed, position, view_distance),
+			target_fog: section_fog(seed, position, view_distance),
+			effects: EffectChain::new(),
+			daily: save.daily,
+			race: save.race.clone(),
+			ghost: match &save.race {
+				Some(Race::Second(replay)) => Some(Ghost::new(window, replay, tick)),
+				_ => None,
+			},
+			trajectory: save.trajectory.clone(),
+			best_trajectory: save.best_trajectory.clone(),
+			best_ghost: None,
+			partner: save.partner.clone(),
+			partner_node: None,
+			radio: save.radio.clone(),
+		}
+	}
+
+	/// The kind of run this is.
+	fn mode(&self) -> Mode {
+		if self.daily.is_some() {
+			Mode::Daily
+		} else if self.simulation.is_timed() {
+			Mode::Timed
+		} else if self.race.is_some() {
+			Mode::Race
+		} else if self.partner.is_some() {
+			Mode::Coop
+		} else {
+			Mode::Normal
+		}
+	}
+
+	/// How the run has gone so far.
+	fn facts(&self) -> Facts {
+		let distance = manhattan(self.simulation.position(), (0, 0)) as u64;
+		Facts::after(self.mode(), &self.simulation.stats(), distance)
+	}
+
+	/// Lets base comment on what happened during the last tick.
+	fn radio_events(&mut self, events: &TickEvents) {
+		let tick = self.simulation.tick();
+		let heard = [
+			(tick == 1, Event::Start),
+			(tick % TICKS_PER_SECOND as u64 == 0, Event::Second),
+			(events.entered_section.is_some(), Event::Section),
+			(events.noticed, Event::Monster),
+			(
+				matches!(events.collected, Some((_, ItemKind::Key(_)))),
+				Event::Key,
+			),
+			(
+				matches!(events.collected, Some((_, ItemKind::Coin))),
+				Event::Coin,
+			),
+		];
+		if heard.iter().any(|(heard, _)| *heard) {
+			let facts = self.facts();
+			for (_, event) in heard.iter().filter(|(heard, _)| *heard) {
+				self.radio.hear(*event, &facts);
+			}
+		}
+	}
+
+	/// Reads the input of the next tick, from the window or the replay being
+	/// played back. Returns `None` when the replay has run out.
+	fn next_input(&mut self, window: &Window, mouse_buttons: &MouseButtons) -> Option<TickInput> {
+		let cursor_movement = self.simulation.camera_mut().take_look_delta();
+		#[cfg(target_arch = "wasm32")]
+		let cursor_movement = cursor_movement
+			+ get_cursor_movement()
+				.into_serde::<JsVector2>()
+				.map_or(Vector2::zeros(), |movement| {
+					Vector2::new(movement.x, movement.y)
+				});
+		let player = self.race.as_ref().map_or(0, Race::player);
+		let [forward, backward, right, left] = ActionMap::for_player(player).held(window);
+		let live = self.input_reader.read(
+			forward,
+			backward,
+			right,
+			left,
+			mouse_buttons,
+			cursor_movement,
+		);
+
+		match &mut self.input {
+			InputSource::Live {
+				recording,
+			} => {
+				if let Some(ticks) = recording {
+					ticks.push(live);
+				}
+				Some(live)
+			}
+			InputSource::Playback(replay) => {
+				replay.ticks.get(self.simulation.tick() as usize).copied()
+			}
+		}
+	}
+
+	/// Saves the recording of a finished run, or checks that a play back
+	/// ended the same way as the recorded run. Returns the recording, if the
+	/// run was recorded.
+	fn finish_run(&mut self) -> Option<Replay> {
+		let final_hash = self.simulation.state_hash();
+		match &mut self.input {
+			InputSource::Live {
+				recording: Some(ticks),
+			} => {
+				let replay = Replay {
+					seed: self.simulation.seed(),
+					timed: self.simulation.is_timed(),
+					ticks: mem::take(ticks),
+					final_hash,
+				};
+				save_recording(&replay);
+				Some(replay)
+			}
+			InputSource::Live {
+				recording: None,
+			} => None,
+			InputSource::Playback(replay) => {
+				if replay.final_hash == final_hash {
+					println!("Replay verified after {} ticks", self.simulation.tick());
+				} else {
+					println!(
+						"Replay diverged, ended in state {:016x} instead of {:016x}",
+						final_hash, replay.final_hash
+					);
+				}
+				None
+			}
+		}
+	}
+
+	/// Ends the turn of a race once the player, or the ghost they are racing,
+	/// has escaped.
+	fn finish_race_turn(
+		&mut self,
+		window: &mut Window,
+		escaped: bool,
+	) -> Option<Box<dyn InnerGameState>> {
+		let race = self.race.as_ref()?;
+		match (race, &self.ghost) {
+			(Race::First, _) if escaped => {
+				let replay = self.finish_run()?;
+				Some(Box::new(super::RaceState::handover(window, replay)))
+			}
+			(Race::Second(_), Some(ghost)) if escaped || ghost.has_escaped() => {
+				let first_ticks = ghost.escape_ticks();
+				let second_ticks = if escaped {
+					Some(self.simulation.tick())
+				} else {
+					None
+				};
+				self.finish_run();
+				Some(Box::new(super::RaceState::finished(
+					window,
+					first_ticks,
+					second_ticks,
+				)))
+			}
+			_ => None,
+		}
+	}
+
+	/// Follows the view distance setting, or adapts to the frame rate up to
+	/// it if the view distance is adaptive.
+	fn update_view_distance(&mut self, window: &mut Window, settings: &Settings) {
+		let frame_time = self.last_frame.elapsed().as_secs_f32();
+		self.last_frame = Instant::now();
+		let view_distance = if settings.adaptive_view_distance {
+			match self.frame_time.record(frame_time) {
+				Some(ViewChange::Shrink) => self.view_distance - 1,
+				Some(ViewChange::Grow) => self.view_distance + 1,
+				None => self.view_distance,
+			}
+			.clamp(MIN_VIEW_DISTANCE, settings.view_distance)
+		} else {
+			settings.view_distance
+		};
+
+		if view_distance != self.view_distance {
+			self.view_distance = view_distance;
+			self.chunks.set_range(view_distance);
+			self.chunks.update(
+				window,
+				self.simulation.position(),
+				self.simulation.collected_items(),
+			);
+			self.simulation
+				.camera_mut()
+				.set_zfar(view_end(view_distance));
+			self.target_fog.end = view_end(view_distance);
+			// Sections that went out of range are already gone, so the fog
+			// can not wait to catch up when shrinking.
+			self.fog.end = self.fog.end.min(self.target_fog.end);
 		}
 	}
 }
@@ -127,13 +448,21 @@ impl InnerGameState for PlayingState {
 			let size = window.size();
 			window.set_cursor_position(size.x as f64 / 2.0, size.y as f64 / 2.0);
 		}
-		update_chunks(
-			self.seed,
-			self.position,
+		self.simulation
+			.camera_mut()
+			.set_zfar(view_end(self.view_distance));
+		self.last_frame = Instant::now();
+		self.chunks.update(
 			window,
-			&mut self.chunks,
-			&self.collected_items,
+			self.simulation.position(),
+			self.simulation.collected_items(),
 		);
+		if self.best_trajectory.is_some() {
+			self.best_ghost = Some(GhostNode::new(window));
+		}
+		if self.partner.is_some() {
+			self.partner_node = Some(GhostNode::new(window));
+		}
 	}
 
 	fn step(
@@ -150,138 +479,225 @@ impl InnerGameState for PlayingState {
 			return Some(Box::new(super::PauseState::new(window, self.save())));
 		}
 
-		#[cfg(target_arch = "wasm32")]
-		{
-			if let Ok(cursor_movement) = get_cursor_movement().into_serde::<JsVector2>() {
-				self.camera.handle_left_button_displacement(&Vector2::new(
-					cursor_movement.x,
-					cursor_movement.y,
-				));
+		let settings = settings();
+		self.update_view_distance(window, &settings);
+
+		let input = match self.next_input(window, mouse_buttons) {
+			Some(input) => input,
+			None => {
+				println!("The replay ran out before the run ended");
+				return Some(Box::new(super::MenuState::new(window)));
+			}
+		};
+		if let Some(partner) = &mut self.partner {
+			for (section, cell) in partner.receive(&mut self.simulation) {
+				self.chunks.remove_item(section, cell);
 			}
 		}
-
-		let movement = self.camera.move_dir(
-			window.get_key(Key::W) == Action::Press,
-			window.get_key(Key::S) == Action::Press,
-			window.get_key(Key::D) == Action::Press,
-			window.get_key(Key::A) == Action::Press,
-		);
-		if let Some(dir) = movement {
-			let mut next_camera_eye = self.camera.eye() + dir;
-
-			let position = (
-				(-self.camera.eye().z / MAZE_SIZE / ROOM_SIZE as f32).round() as i64,
-				(self.camera.eye().x / MAZE_SIZE / ROOM_SIZE as f32).round() as i64,
-			);
-			if position != self.position {
-				update_chunks(
-					self.seed,
-					position,
-					window,
-					&mut self.chunks,
-					&self.collected_items,
-				);
-
-				self.section_name_start_time = Instant::now();
-				self.section_name = get_section_name(self.seed, position);
+		self.trajectory
+			.record(self.simulation.tick(), self.simulation.camera().eye());
+		let events = self.simulation.step(input);
+		if let Some(partner) = &mut self.partner {
+			partner.send_position(self.simulation.camera().eye());
+			if let Some((cell, _)) = events.collected {
+				partner.send_collected(self.simulation.position(), cell);
 			}
-
-			for wall in &self.chunks.get(&position).unwrap().0 {
-				wall.push_back(&mut next_camera_eye);
+			if let Some(id) = events.opened {
+				partner.send_opened(id);
 			}
-			self.camera.set_eye(next_camera_eye);
-
-			self.position = position;
+			if events.escaped {
+				partner.send_escaped();
+			}
+		}
+		if let Some(ghost) = &mut self.ghost {
+			ghost.step();
+		}
+		if let Some(state) = self.finish_race_turn(window, events.escaped) {
+			return Some(state);
+		}
+		self.radio_events(&events);
+		let seed = self.simulation.seed();
+		let position = self.simulation.position();
+		if events.entered_section.is_some() {
+			self.chunks
+				.update(window, position, self.simulation.collected_items());
+
+			self.section_name_tick = self.simulation.tick();
+			self.section_name =
+				get_section_name(seed, position, self.simulation.landmark(&position));
+			self.target_fog = section_fog(seed, position, self.view_distance);
+		}
+		if let Some(dir) = events.movement {
+			self.chunks
+				.generate_ahead(position, (-dir.z, dir.x), GENERATION_BUDGET);
+		}
+		if let Some((cell, _)) = events.collected {
+			self.chunks.remove_item(position, cell);
 		}
+		if events.escaped || events.timed_out {
+			self.finish_run();
+			// Co-op runs share keys and coins, so their routes are not ones
+			// that can be walked alone.
+			if events.escaped
+				&& self.partner.is_none()
+				&& matches!(self.input, InputSource::Live { .. })
+			{
+				record_best_run(seed, BestRun {
+					version: VERSION,
+					ticks: self.simulation.tick(),
+					trajectory: self.trajectory.to_hex(),
+				});
+			}
+			let stats = self.simulation.stats();
+			let facts = self.facts();
+			if let Some(remaining_ticks) = stats.remaining_ticks {
+				return Some(Box::new(super::EndState::timed(
+					window,
+					&facts,
+					remaining_ticks,
+				)));
+			}
+			return Some(Box::new(match self.daily {
+				Some(date) => {
+					let result = DailyResult {
+						coins: stats.coins,
+						ticks: stats.ticks,
+						sections: stats.sections_visited,
+					};
+					let recorded = record_daily(&date, result);
+					super::EndState::daily(window, &facts, date, result, recorded)
+				}
+				None => super::EndState::new(window, &facts),
+			}));
+		}
+
+		self.fog = self.fog.lerp(&self.target_fog, FOG_BLEND);
+		set_fog(FogSettings {
+			dither_levels: if settings.dithering { DITHER_LEVELS } else { 0 },
+			..self.fog
+		});
+		window.set_background_color(self.fog.color.x, self.fog.color.y, self.fog.color.z);
 
 		let item_turn =
 			UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI / 120.0);
 		let item_float = Translation3::new(
 			0.0,
-			self.start_time.elapsed().as_secs_f32().sin() * 0.0025,
+			(self.simulation.tick() as f32 / TICKS_PER_SECOND).sin() * 0.0025,
 			0.0,
 		);
-		for item in self.chunks.iter_mut() {
-			if let Some((i, _)) = &mut item.1 .2 {
+		let camera = self.simulation.camera();
+		self.chunks.cull(&ViewCone::for_camera(
+			camera.eye(),
+			&camera.at(),
+			camera.fov(),
+			camera.aspect(),
+		));
+
+		let mut closest_monster = f32::INFINITY;
+		let keys = self.simulation.keys();
+		for (section, chunk) in self.chunks.iter_mut() {
+			for (_, i, _) in chunk.items.iter_mut() {
 				i.prepend_to_local_rotation(&item_turn);
 				i.append_translation(&item_float);
 			}
+			for (door, tier) in chunk.doors.iter_mut() {
+				door.set_visible(*tier >= keys && chunk.visible);
+			}
 
-			for (node, monster) in item.1 .3.iter_mut() {
-				node.set_visible(monster.update(distance(self.camera.eye(), &{
-					let monster_translation = node.data().local_translation();
-					Point3::new(
-						monster_translation.x,
-						monster_translation.y,
-						monster_translation.z,
-					)
-				})));
+			// Monsters in sections that are not simulated are too far away to
+			// have noticed the Agent.
+			let monsters = self.simulation.monsters(section);
+			for (i, node) in chunk.monsters.iter_mut().enumerate() {
+				let monster = monsters.get(i);
+				let visible = monster.map_or(true, |monster| monster.visible) && chunk.visible;
+				node.set_visible(visible);
+				if let Some(monster) = monster.filter(|_| visible) {
+					closest_monster = closest_monster.min(monster.distance);
+				}
 			}
 		}
+		for (id, (door, hinge)) in self.chunks.hinged_doors_mut() {
+			swing_door(door, hinge, self.simulation.door(id).openness());
+		}
 
-		let mut action_text = None;
-		if let Some((_, _, elem, _)) = self.chunks.get_mut(&self.position) {
-			if let Some((item, kind)) = elem {
-				if distance(self.camera.eye(), &{
-					let item_translation = item.data().local_translation();
-					Point3::new(item_translation.x, item_translation.y, item_translation.z)
-				}) < MAZE_SIZE_HALF
-				{
-					let lmb_pressed = mouse_buttons.lmb == MouseAction::Pressed;
-					if kind == &ItemKind::Lock {
-						if self.has_key {
-							if lmb_pressed {
-								return Some(Box::new(super::EndState::new(
-									window,
-									self.collected_items.len() - self.has_key as usize,
-								)));
-							} else {
-								action_text = Some("Press LMB to unlock and escape");
-							}
-						} else {
-							action_text = Some("Come back here once you've found the key to escape");
-						}
-					} else if kind == &ItemKind::Key {
-						if lmb_pressed {
-							self.has_key = true;
-							self.collected_items.insert(self.position);
-							window.remove_node(item);
-							*elem = None;
-						} else {
-							action_text = Some("Press LMB to collect key");
-						}
-					} else if kind == &ItemKind::Coin {
-						if lmb_pressed {
-							self.collected_items.insert(self.position);
-							window.remove_node(item);
-							*elem = None;
-						} else {
-							action_text = Some("Press LMB to collect coin");
-						}
-					}
+		let eye = *self.simulation.camera().eye();
+		if let Some(ghost) = &mut self.ghost {
+			let visible = self
+				.chunks
+				.get(&ghost.position())
+				.map_or(false, |chunk| chunk.visible);
+			ghost.place(&eye);
+			ghost.set_visible(visible);
+		}
+		if let (Some(trajectory), Some(ghost)) = (&self.best_trajectory, &mut self.best_ghost) {
+			match trajectory.position_at(self.simulation.tick()) {
+				Some((x, z)) => {
+					let section = WorldCell::containing(&Point3::new(x, 0.0, z)).section();
+					let visible = self
+						.chunks
+						.get(&section)
+						.map_or(false, |chunk| chunk.visible);
+					ghost.place((x, z), &eye);
+					ghost.set_visible(visible);
+				}
+				None => ghost.set_visible(false),
+			}
+		}
+		if let (Some(partner), Some(node)) = (&self.partner, &mut self.partner_node) {
+			match partner.position() {
+				Some((x, z)) => {
+					let section = WorldCell::containing(&Point3::new(x, 0.0, z)).section();
+					let visible = self
+						.chunks
+						.get(&section)
+						.map_or(false, |chunk| chunk.visible);
+					node.place((x, z), &eye);
+					node.set_visible(visible);
 				}
+				None => node.set_visible(false),
 			}
 		}
 
+		self.effects.apply_settings(&settings);
+		drive_effects(
+			&mut self.effects,
+			1.0 - (closest_monster / MAZE_CHUNK_SIZE).min(1.0),
+			exposure(position),
+		);
+
+		let action_text = match self.simulation.nearby_item() {
+			Some(ItemKind::Lock) if self.simulation.has_key() => Some("action-unlock"),
+			Some(ItemKind::Lock) => Some("action-find-key"),
+			Some(ItemKind::Key(_)) => Some("action-collect-key"),
+			Some(ItemKind::Coin) => Some("action-collect-coin"),
+			None if self.simulation.nearby_door().is_some() => Some("action-door"),
+			None if self.simulation.nearby_hinged_door().is_some() => Some("action-open-door"),
+			None => self.simulation.nearby_note(),
+		};
+
 		let mut ui = window.conrod_ui_mut().set_widgets();
 
-		let text_time = self.section_name_start_time.elapsed().as_secs_f32();
+		let text_time = (self.simulation.tick() - self.section_name_tick) as f32 / TICKS_PER_SECOND;
 		if text_time < TEXT_VISIBLE_SECONDS {
 			widget::Text::new(&self.section_name)
 				.font_size(50)
-				.rgba(
-					1.0,
-					1.0,
-					1.0,
-					(1.5625 - (2.5 * text_time / TEXT_VISIBLE_SECONDS - 1.25).powi(2)).min(1.0),
-				)
+				.rgba(1.0, 1.0, 1.0, fade(text_time, TEXT_VISIBLE_SECONDS))
 				.mid_top_with_margin(100.0)
 				.center_justify()
 				.set(self.ui_ids.section_name, &mut ui);
 		}
 
-		if let Some(t) = action_text {
-			widget::Text::new(t)
+		if let Some((text, time)) = self.radio.on_air(self.simulation.tick()) {
+			widget::Text::new(text)
+				.font_size(24)
+				.rgba(0.7, 1.0, 0.7, fade(time, TRANSMISSION_SECONDS))
+				.mid_bottom_with_margin(150.0)
+				.center_justify()
+				.set(self.ui_ids.radio_text, &mut ui);
+		}
+
+		if let Some(id) = action_text {
+			widget::Text::new(&tr(id))
 				.font_size(20)
 				.rgba(1.0, 1.0, 1.0, 1.0)
 				.bottom_right_with_margin(50.0)
@@ -290,11 +706,11 @@ impl InnerGameState for PlayingState {
 		}
 
 		widget::Text::new(&{
-			let coins = self.collected_items.len() - self.has_key as usize;
+			let coins = self.simulation.coins();
 			if coins == 0 {
 				"".to_string()
 			} else {
-				format!("Coins collected: {}", coins)
+				tr_with("hud-coins", &[("coins", coins.into())])
 			}
 		})
 		.font_size(20)
@@ -302,19 +718,61 @@ impl InnerGameState for PlayingState {
 		.bottom_left_with_margin(50.0)
 		.set(self.ui_ids.coins_collected_text, &mut ui);
 
-		if self.has_key {
-			widget::Text::new("Carrying key")
+		if let Some(race) = &self.race {
+			widget::Text::new(&tr_with("hud-player", &[(
+				"player",
+				(race.player() + 1).into(),
+			)]))
+			.font_size(20)
+			.rgba(1.0, 1.0, 1.0, 1.0)
+			.top_left_with_margin(50.0)
+			.set(self.ui_ids.player_text, &mut ui);
+		}
+
+		if let Some(status) = self.partner.as_ref().and_then(Partner::status) {
+			widget::Text::new(&status)
 				.font_size(20)
 				.rgba(1.0, 1.0, 1.0, 1.0)
-				.top_left_with_margins_on(self.ui_ids.coins_collected_text, -25.0, 0.0)
-				.set(self.ui_ids.carrying_key_text, &mut ui);
+				.top_left_with_margin(50.0)
+				.set(self.ui_ids.partner_text, &mut ui);
+		}
+
+		if let Some(remaining_ticks) = self.simulation.remaining_ticks() {
+			let seconds = countdown::seconds(remaining_ticks);
+			widget::Text::new(&format!("{}:{:02}", seconds / 60, seconds % 60))
+				.font_size(30)
+				.color(if seconds <= HURRY_SECONDS {
+					Color::Rgba(1.0, 0.2, 0.2, 1.0)
+				} else {
+					Color::Rgba(1.0, 1.0, 1.0, 1.0)
+				})
+				.top_right_with_margin(50.0)
+				.right_justify()
+				.set(self.ui_ids.timer_text, &mut ui);
+		}
+
+		if keys > 0 {
+			let total = self.simulation.params().locked_rings + 1;
+			widget::Text::new(&tr_with("hud-keys", &[
+				("keys", keys.into()),
+				("total", total.into()),
+			]))
+			.font_size(20)
+			.rgba(1.0, 1.0, 1.0, 1.0)
+			.top_left_with_margins_on(self.ui_ids.coins_collected_text, -25.0, 0.0)
+			.set(self.ui_ids.keys_found_text, &mut ui);
 		}
 
 		None
 	}
 
 	fn cameras_and_effect_and_renderer(&mut self) -> CamerasEffectRenderer {
-		(Some(&mut self.camera), None, None, None)
+		(
+			Some(self.simulation.camera_mut()),
+			None,
+			None,
+			Some(&mut self.effects),
+		)
 	}
 
 	fn clean(&mut self, window: &mut Window) {
@@ -326,293 +784,82 @@ impl InnerGameState for PlayingState {
 		{
 			window.hide_cursor(false);
 		}
-		for (_, (_, mut node, item, monsters)) in self.chunks.drain() {
-			window.remove_node(&mut node);
-			if let Some((mut i, _)) = item {
-				window.remove_node(&mut i);
-			}
-			for (mut node, _) in monsters {
-				window.remove_node(&mut node);
-			}
+		set_fog(FogSettings::default());
+		window.set_background_color(0.0, 0.0, 0.0);
+		self.chunks.clear(window);
+		if let Some(ghost) = &mut self.ghost {
+			ghost.remove(window);
+		}
+		if let Some(mut ghost) = self.best_ghost.take() {
+			ghost.remove(window);
+		}
+		if let Some(mut node) = self.partner_node.take() {
+			node.remove(window);
 		}
 	}
 }
 
 const TEXT_VISIBLE_SECONDS: f32 = 5.0;
+/// The timer turns red when there are this many seconds left.
+const HURRY_SECONDS: u64 = 30;
+const FOG_BLEND: f32 = 0.02;
+const DITHER_LEVELS: u8 = 8;
+const GENERATION_BUDGET: Duration = Duration::from_millis(2);
+const TARGET_FPS: f32 = 60.0;
 
 widget_ids! {
 	struct UiIds {
 		section_name,
 		coins_collected_text,
-		carrying_key_text,
+		keys_found_text,
 		action_text,
+		timer_text,
+		player_text,
+		partner_text,
+		radio_text,
 	}
 }
 
-fn get_section_name(seed: u64, position: (i64, i64)) -> String {
-	let mut name = "– ".to_string();
-	generate_name(seed, position, &mut name);
-	name.push_str(" –");
-	name
+/// The name of the section at `position`, made like the settings say, and
+/// told as the `landmark` laid out in it if there is one.
+fn get_section_name(seed: u64, position: (i64, i64), landmark: Option<&Landmark>) -> String {
+	let name = match (settings().name_theme, language().syllables()) {
+		(None, Some(syllables)) => section_name(syllables, seed, position),
+		(theme, _) => section_name(
+			&*theme.unwrap_or(NameTheme::English).model(),
+			seed,
+			position,
+		),
+	};
+	let name = match landmark {
+		Some(landmark) => tr_with(&landmark.name, &[("name", name.into())]),
+		None => name,
+	};
+	format!("– {} –", name)
 }
 
-const CHUNK_RANGE: i64 = 2;
-
-fn update_chunks(
-	seed: u64,
-	position: (i64, i64),
-	window: &mut Window,
-	chunks: &mut Chunks,
-	collected_items: &HashSet<(i64, i64)>,
-) {
-	for (_, (_, mut node, item, monsters)) in chunks
-		.drain_filter(|p, _| ((p.0 - position.0).abs() + (p.1 - position.1).abs()) > CHUNK_RANGE)
-	{
-		window.remove_node(&mut node);
-		if let Some((mut i, _)) = item {
-			window.remove_node(&mut i);
-		}
-		for (mut node, _) in monsters {
-			window.remove_node(&mut node);
-		}
-	}
-
-	for y in -CHUNK_RANGE..=CHUNK_RANGE {
-		let width = CHUNK_RANGE - y.abs();
-		for x in -width..=width {
-			let position = (position.0 + x, position.1 + y);
-			chunks.entry(position).or_insert_with(|| {
-				add_maze(window, seed, position, !collected_items.contains(&position))
-			});
-		}
-	}
+/// How visible a text that shows for `length` seconds is `time` seconds in,
+/// fading in at the start and out at the end.
+fn fade(time: f32, length: f32) -> f32 {
+	(1.5625 - (2.5 * time / length - 1.25).powi(2)).min(1.0)
 }
 
-const MAZE_HEIGHT: f32 = 2.0;
-pub const MAZE_SIZE: f32 = 1.75;
-pub const MAZE_SIZE_HALF: f32 = MAZE_SIZE / 2.0;
-pub const MAZE_CHUNK_SIZE: f32 = (ROOM_SIZE as f32 + 0.5) * MAZE_SIZE;
-const MAZE_OFFSET: f32 = ROOM_CENTER as f32 * -MAZE_SIZE;
-const MAZE_ABOVE: Translation3<f32> = Translation3::new(-MAZE_SIZE_HALF, 0.0, 0.0);
-const MAZE_LEFT: Translation3<f32> = Translation3::new(0.0, 0.0, MAZE_SIZE_HALF);
-const MAZE_RIGHT: Translation3<f32> = Translation3::new(0.0, 0.0, -MAZE_SIZE_HALF);
-const MAZE_BELOW: Translation3<f32> = Translation3::new(MAZE_SIZE_HALF, 0.0, 0.0);
-const MAZE_FLOOR: Translation3<f32> = Translation3::new(0.0, -MAZE_HEIGHT / 2.0, 0.0);
-const MAZE_CEILING: Translation3<f32> = Translation3::new(0.0, MAZE_HEIGHT / 2.0, 0.0);
-const MONSTER_DISTANCE: f32 = 5.0;
-
-fn add_maze(
-	window: &mut Window,
-	seed: u64,
-	position: (i64, i64),
-	should_add_item: bool,
-) -> (
-	Vec<Wall>,
-	SceneNode,
-	Option<(SceneNode, ItemKind)>,
-	Vec<(SceneNode, Monster)>,
-) {
-	let half_turn = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI);
-	let quarter_turn = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI / 2.0);
-	let three_quarter_turn =
-		UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI * 1.5);
-	let floor_turn = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -f32::consts::PI / 2.0);
-	let ceiling_turn = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), f32::consts::PI / 2.0);
-	let x_offset = MAZE_OFFSET + (position.1 * ROOM_SIZE as i64) as f32 * MAZE_SIZE;
-	let z_offset = -(MAZE_OFFSET + (position.0 * ROOM_SIZE as i64) as f32 * MAZE_SIZE);
-
-	fn create_maze_quad(parent: &mut SceneNode) -> SceneNode {
-		parent.add_quad(MAZE_SIZE, MAZE_HEIGHT, 1, 1)
+fn section_fog(seed: u64, position: (i64, i64), view_distance: i64) -> FogSettings {
+	let mut rng: StdRng = rng_for_maze(seed, position);
+	FogSettings {
+		end: view_end(view_distance),
+		..FogSettings::for_section(rng.gen(), exposure(position))
 	}
-	let create_double_maze_quad = |parent: &mut SceneNode| -> SceneNode {
-		let mut quad = create_maze_quad(parent);
-		let mut quad2 = quad.add_quad(MAZE_SIZE, MAZE_HEIGHT, 1, 1);
-		quad2.prepend_to_local_rotation(&half_turn);
-		quad
-	};
-	let create_monster_quad = |window: &mut Window| -> SceneNode {
-		let mut quad = window.add_quad(MAZE_SIZE * 0.408, MAZE_HEIGHT * 0.861, 1, 1);
-		let mut quad2 = quad.add_quad(MAZE_SIZE * 0.408, MAZE_HEIGHT * 0.861, 1, 1);
-		quad2.prepend_to_local_rotation(&half_turn);
-		quad.set_texture_with_name("monster");
-		quad.set_material_with_name("pixel");
-		quad.append_translation(&Translation3::new(x_offset, 0.0, z_offset));
-		quad.set_visible(false);
-		quad
-	};
+}
 
-	let mut rng: StdRng = rng_for_maze(seed, position);
-	let (r, g, b) = hsl_to_rgb(rng.gen(), 0.5, 0.5);
-	let map = Map::generate_prim(&mut rng);
-	let mut walls = Vec::new();
-	let mut group = window.add_group();
-	group.append_translation(&Translation3::new(x_offset, 0.0, z_offset));
-	let mut ceiling_group = group.add_group();
-	let mut wall_group = group.add_group();
-	let mut floor_group = group.add_group();
-	let mut monsters = Vec::new();
-	let wall_up_opening = rand_for_border_walls::<StdRng>(seed, position, Direction::Up, ROOM_SIZE);
-	let wall_left_opening =
-		rand_for_border_walls::<StdRng>(seed, position, Direction::Left, ROOM_SIZE);
-	let wall_right_opening =
-		rand_for_border_walls::<StdRng>(seed, position, Direction::Right, ROOM_SIZE);
-	let wall_down_opening =
-		rand_for_border_walls::<StdRng>(seed, position, Direction::Down, ROOM_SIZE);
-
-	let mut item_translation: Option<Translation3<f32>> = None;
-	let item_creator: Option<(Box<MeshGenerator>, (usize, usize), ItemKind)> = {
-		if should_add_item {
-			if position == (0, 0) {
-				Some((
-					Box::new(generate_lock),
-					(ROOM_CENTER, ROOM_CENTER),
-					ItemKind::Lock,
-				))
-			} else {
-				let mut rng: StdRng = rng_for_maze(seed, position);
-				let kind = if position == rand_for_key::<StdRng>(seed) {
-					ItemKind::Key
-				} else {
-					ItemKind::Coin
-				};
-				Some((
-					match kind {
-						ItemKind::Key => Box::new(move |p| generate_key(p, seed, (0, 0))),
-						ItemKind::Coin => Box::new(generate_coin),
-						ItemKind::Lock => panic!(),
-					},
-					(rng.gen_range(0..ROOM_SIZE), rng.gen_range(0..ROOM_SIZE)),
-					kind,
-				))
-			}
-		} else {
-			None
-		}
-	};
+/// Distance from the Agent within which nothing further than `view_distance`
+/// sections away can be seen, wherever in its section the Agent is.
+fn view_end(view_distance: i64) -> f32 {
+	view_distance as f32 * ROOM_SIZE as f32 * MAZE_SIZE * f32::consts::FRAC_1_SQRT_2
+}
 
-	for row in 0..ROOM_SIZE {
-		for col in 0..ROOM_SIZE {
-			let mut grid_group = wall_group.add_group();
-			let pos = Position(row, col);
-			let grid_translation =
-				Translation3::new(row as f32 * MAZE_SIZE, 0.0, col as f32 * -MAZE_SIZE);
-			if row == 0 && Some(col) != wall_up_opening {
-				let mut quad = create_maze_quad(&mut grid_group);
-				quad.prepend_to_local_rotation(&quarter_turn);
-				quad.append_translation(&MAZE_ABOVE);
-
-				walls.push(Wall::Horizontal(Point3::new(
-					x_offset + row as f32 * MAZE_SIZE - MAZE_SIZE_HALF,
-					0.0,
-					z_offset + col as f32 * -MAZE_SIZE,
-				)));
-			}
-			if col == 0 && Some(row) != wall_left_opening {
-				let mut quad = create_maze_quad(&mut grid_group);
-				quad.prepend_to_local_rotation(&half_turn);
-				quad.append_translation(&MAZE_LEFT);
-
-				walls.push(Wall::Vertical(Point3::new(
-					x_offset + row as f32 * MAZE_SIZE,
-					0.0,
-					z_offset + col as f32 * -MAZE_SIZE + MAZE_SIZE_HALF,
-				)));
-			}
-			if col + 1 == ROOM_SIZE {
-				if Some(row) != wall_right_opening {
-					let mut quad = create_maze_quad(&mut grid_group);
-					quad.append_translation(&MAZE_RIGHT);
-
-					walls.push(Wall::Vertical(Point3::new(
-						x_offset + row as f32 * MAZE_SIZE,
-						0.0,
-						z_offset + col as f32 * -MAZE_SIZE - MAZE_SIZE_HALF,
-					)));
-				}
-			} else if map.is_right(&pos) {
-				let mut quad = create_double_maze_quad(&mut grid_group);
-				quad.append_translation(&MAZE_RIGHT);
-
-				walls.push(Wall::Vertical(Point3::new(
-					x_offset + row as f32 * MAZE_SIZE,
-					0.0,
-					z_offset + col as f32 * -MAZE_SIZE - MAZE_SIZE_HALF,
-				)));
-			} else if rng.gen::<f32>()
-				< ((position.0 as f32).powi(2) + (position.1 as f32).powi(2)).sqrt()
-					/ MONSTER_DISTANCE
-			{
-				let mut monster = create_monster_quad(window);
-				monster.append_translation(&MAZE_RIGHT);
-				monster.append_translation(&grid_translation);
-				monsters.push((monster, Monster::default()));
-			}
-			if row + 1 == ROOM_SIZE {
-				if Some(col) != wall_down_opening {
-					let mut quad = create_maze_quad(&mut grid_group);
-					quad.prepend_to_local_rotation(&three_quarter_turn);
-					quad.append_translation(&MAZE_BELOW);
-
-					walls.push(Wall::Horizontal(Point3::new(
-						x_offset + row as f32 * MAZE_SIZE + MAZE_SIZE_HALF,
-						0.0,
-						z_offset + col as f32 * -MAZE_SIZE,
-					)));
-				}
-			} else if map.is_below(&pos) {
-				let mut quad = create_double_maze_quad(&mut grid_group);
-				quad.prepend_to_local_rotation(&quarter_turn);
-				quad.append_translation(&MAZE_BELOW);
-
-				walls.push(Wall::Horizontal(Point3::new(
-					x_offset + row as f32 * MAZE_SIZE + MAZE_SIZE_HALF,
-					0.0,
-					z_offset + col as f32 * -MAZE_SIZE,
-				)));
-			} else if rng.gen::<f32>()
-				< ((position.0 as f32).powi(2) + (position.1 as f32).powi(2)).sqrt()
-					/ MONSTER_DISTANCE
-			{
-				let mut monster = create_monster_quad(window);
-				monster.prepend_to_local_rotation(&three_quarter_turn);
-				monster.append_translation(&MAZE_BELOW);
-				monster.append_translation(&grid_translation);
-				monsters.push((monster, Monster::default()));
-			}
-			let mut floor = floor_group.add_quad(MAZE_SIZE, MAZE_SIZE, 1, 1);
-			floor.prepend_to_local_rotation(&floor_turn);
-			floor.append_translation(&MAZE_FLOOR);
-			floor.append_translation(&grid_translation);
-			let mut ceiling = ceiling_group.add_quad(MAZE_SIZE, MAZE_SIZE, 1, 1);
-			ceiling.prepend_to_local_rotation(&ceiling_turn);
-			ceiling.append_translation(&MAZE_CEILING);
-			ceiling.append_translation(&grid_translation);
-			grid_group.append_translation(&grid_translation);
-			if item_creator
-				.iter()
-				.next()
-				.map_or(false, |ic| ic.1 .0 == row && ic.1 .1 == col)
-			{
-				item_translation = Some(grid_translation);
-			}
-		}
+fn drive_effects(effects: &mut EffectChain, monster_proximity: f32, exposure: f32) {
+	for effect in SCREEN_EFFECTS.iter() {
+		effects.set_intensity(*effect, effect.intensity(monster_proximity, exposure));
 	}
-
-	ceiling_group.set_texture_with_name("ceiling");
-	wall_group.set_texture_with_name("wall");
-	floor_group.set_texture_with_name("floor");
-	group.set_material_with_name("pixel");
-	group.set_color(r, g, b);
-
-	(
-		walls,
-		group,
-		item_creator.map(|ic| {
-			let mut item = ic.0(window.scene_mut());
-			item.append_translation(&Translation3::new(x_offset, -0.1, z_offset));
-			item.append_translation(&item_translation.unwrap());
-			(item, ic.2)
-		}),
-		monsters,
-	)
 }
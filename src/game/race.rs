@@ -0,0 +1,103 @@
+use kiss3d::{nalgebra::Point3, window::Window};
+
+use super::{ghost::GhostNode, input::TickInput, replay::Replay, simulation::Simulation};
+
+/// Whose turn it is in a race between two players taking turns on the same
+/// seed. The first player's run is played back as a ghost for the second to
+/// race against, and whoever gets back to the lock first wins.
+#[derive(Clone)]
+pub enum Race {
+	First,
+	Second(Replay),
+}
+
+impl Race {
+	/// Number of the player whose turn it is, starting from zero.
+	pub fn player(&self) -> usize {
+		match self {
+			Self::First => 0,
+			Self::Second(_) => 1,
+		}
+	}
+}
+
+/// Number of the player who won a race where the first player escaped after
+/// `first_ticks`, and the second after `second_ticks` if they did before the
+/// race was over. `None` if they escaped on the same tick.
+pub fn winner(first_ticks: u64, second_ticks: Option<u64>) -> Option<usize> {
+	match second_ticks {
+		Some(ticks) if ticks < first_ticks => Some(1),
+		Some(ticks) if ticks == first_ticks => None,
+		_ => Some(0),
+	}
+}
+
+/// The first player's run, played back tick by tick alongside the second's.
+pub struct Ghost {
+	simulation: Simulation,
+	ticks: Vec<TickInput>,
+	node: GhostNode,
+}
+
+impl Ghost {
+	/// The ghost of `replay`, caught up to `tick`.
+	pub fn new(window: &mut Window, replay: &Replay, tick: u64) -> Self {
+		let mut ghost = Self {
+			simulation: Simulation::new(replay.seed),
+			ticks: replay.ticks.clone(),
+			node: GhostNode::new(window),
+		};
+		while ghost.simulation.tick() < tick && !ghost.has_escaped() {
+			ghost.step();
+		}
+		ghost
+	}
+
+	/// Runs the next tick of the recorded run.
+	pub fn step(&mut self) {
+		if let Some(input) = self.ticks.get(self.simulation.tick() as usize) {
+			self.simulation.step(*input);
+		}
+	}
+
+	/// Moves the ghost to where the first player is, facing `eye`.
+	pub fn place(&mut self, eye: &Point3<f32>) {
+		let ghost_eye = self.simulation.camera().eye();
+		self.node.place((ghost_eye.x, ghost_eye.z), eye);
+	}
+
+	pub fn has_escaped(&self) -> bool {
+		self.simulation.has_escaped()
+	}
+
+	/// Number of ticks the first player took to escape.
+	pub fn escape_ticks(&self) -> u64 {
+		self.ticks.len() as u64
+	}
+
+	/// The section the ghost is in.
+	pub fn position(&self) -> (i64, i64) {
+		self.simulation.position()
+	}
+
+	pub fn set_visible(&mut self, visible: bool) {
+		self.node.set_visible(visible);
+	}
+
+	pub fn remove(&mut self, window: &mut Window) {
+		self.node.remove(window);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn first_back_wins() {
+		assert_eq!(winner(600, Some(599)), Some(1));
+		assert_eq!(winner(600, Some(600)), None);
+		assert_eq!(winner(600, Some(601)), Some(0));
+		assert_eq!(winner(600, None), Some(0));
+	}
+}
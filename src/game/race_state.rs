@@ -0,0 +1,173 @@
+use kiss3d::{
+	conrod::{
+		color::{Color, Colorable},
+		position::{Positionable, Sizeable},
+		widget::{self, Widget},
+		widget_ids,
+		Borderable,
+		Labelable,
+	},
+	window::Window,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+	super::{
+		locale::{tr, tr_with},
+		GAME_NAME,
+	},
+	input::TICKS_PER_SECOND,
+	race::{winner, Race},
+	replay::Replay,
+	InnerGameState,
+	MouseButtons,
+};
+
+/// The screens between and after the turns of a race.
+pub struct RaceState {
+	ui_ids: UiIds,
+	stage: Stage,
+}
+
+enum Stage {
+	/// The first player has escaped, and the second is up next.
+	Handover(Replay),
+	Finished {
+		first_ticks: u64,
+		second_ticks: Option<u64>,
+	},
+}
+
+impl RaceState {
+	/// Hands the game over to the second player, who races against the
+	/// first player's run in `replay`.
+	pub fn handover(window: &mut Window, replay: Replay) -> Self {
+		Self {
+			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
+			stage: Stage::Handover(replay),
+		}
+	}
+
+	/// The result of a race where the first player escaped after
+	/// `first_ticks`, and the second after `second_ticks` if they did.
+	pub fn finished(window: &mut Window, first_ticks: u64, second_ticks: Option<u64>) -> Self {
+		Self {
+			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
+			stage: Stage::Finished {
+				first_ticks,
+				second_ticks,
+			},
+		}
+	}
+}
+
+impl InnerGameState for RaceState {
+	fn step(&mut self, window: &mut Window, _: &MouseButtons) -> Option<Box<dyn InnerGameState>> {
+		let continue_clicked;
+		let menu_clicked;
+		{
+			let mut ui = window.conrod_ui_mut().set_widgets();
+
+			widget::Text::new(GAME_NAME)
+				.font_size(75)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.mid_top_with_margin(150.0)
+				.center_justify()
+				.set(self.ui_ids.title, &mut ui);
+
+			let (text, continue_label) = match &self.stage {
+				Stage::Handover(replay) => (
+					tr_with("race-handover", &[(
+						"time",
+						clock(replay.ticks.len() as u64).into(),
+					)]),
+					tr("race-start"),
+				),
+				Stage::Finished {
+					first_ticks,
+					second_ticks,
+				} => (
+					format!(
+						"{}\n{}\n{}",
+						match winner(*first_ticks, *second_ticks) {
+							Some(player) =>
+								tr_with("race-winner", &[("player", (player + 1).into())]),
+							None => tr("race-draw"),
+						},
+						tr_with("race-time", &[
+							("player", 1i64.into()),
+							("time", clock(*first_ticks).into()),
+						]),
+						tr_with("race-time", &[
+							("player", 2i64.into()),
+							(
+								"time",
+								second_ticks
+									.map_or_else(|| tr("race-still-in-maze"), clock)
+									.into(),
+							),
+						]),
+					),
+					tr("race-again"),
+				),
+			};
+			widget::Text::new(&text)
+				.font_size(24)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.middle()
+				.center_justify()
+				.set(self.ui_ids.results_text, &mut ui);
+
+			continue_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&continue_label)
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(200.0)
+				.set(self.ui_ids.continue_button, &mut ui);
+			menu_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("main-menu"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(100.0)
+				.set(self.ui_ids.menu_button, &mut ui);
+		}
+
+		if continue_clicked.was_clicked() {
+			Some(Box::new(match &self.stage {
+				Stage::Handover(replay) => {
+					super::PlayingState::race(window, replay.seed, Race::Second(replay.clone()))
+				}
+				Stage::Finished {
+					..
+				} => super::PlayingState::race(window, StdRng::from_entropy().gen(), Race::First),
+			}))
+		} else if menu_clicked.was_clicked() {
+			Some(Box::new(super::MenuState::new(window)))
+		} else {
+			None
+		}
+	}
+}
+
+/// `ticks` as minutes and seconds.
+fn clock(ticks: u64) -> String {
+	let seconds = (ticks as f32 / TICKS_PER_SECOND) as u64;
+	format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+widget_ids! {
+	struct UiIds {
+		title,
+		results_text,
+		continue_button,
+		menu_button,
+	}
+}
@@ -0,0 +1,65 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{
+	super::dialogue::{dialogue, Event, Facts},
+	input::TICKS_PER_SECOND,
+};
+
+/// Seconds a transmission stays on screen, fading in and out.
+pub const TRANSMISSION_SECONDS: f32 = 5.0;
+
+/// What base says over the radio during a run, one transmission at a time.
+#[derive(Clone, Default)]
+pub struct Radio {
+	/// Transmissions that have been sent and are not sent again.
+	sent: HashSet<usize>,
+	queue: VecDeque<String>,
+	/// The transmission on air, and the tick it started on.
+	on_air: Option<(String, u64)>,
+}
+
+impl Radio {
+	/// Queues what base has to say about `event`.
+	pub fn hear(&mut self, event: Event, facts: &Facts) {
+		let told = dialogue().radio(event, facts, &mut self.sent);
+		self.queue.extend(told);
+	}
+
+	/// The transmission on air on `tick`, and the seconds since it started.
+	/// The next one in the queue goes on air once it is over.
+	pub fn on_air(&mut self, tick: u64) -> Option<(&str, f32)> {
+		let seconds = |start: u64| tick.saturating_sub(start) as f32 / TICKS_PER_SECOND;
+		if self
+			.on_air
+			.as_ref()
+			.map_or(true, |(_, start)| seconds(*start) >= TRANSMISSION_SECONDS)
+		{
+			self.on_air = self.queue.pop_front().map(|text| (text, tick));
+		}
+		self.on_air
+			.as_ref()
+			.map(|(text, start)| (text.as_str(), seconds(*start)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn transmissions_wait_their_turn() {
+		let mut radio = Radio::default();
+		assert_eq!(radio.on_air(0), None);
+		radio.queue.extend(vec!["a".to_string(), "b".to_string()]);
+		assert_eq!(radio.on_air(10), Some(("a", 0.0)));
+		let length = (TRANSMISSION_SECONDS * TICKS_PER_SECOND) as u64;
+		assert_eq!(
+			radio.on_air(10 + length - 1).map(|(text, _)| text),
+			Some("a")
+		);
+		assert_eq!(radio.on_air(10 + length), Some(("b", 0.0)));
+		// Ticks from before it went on air, as after going back to a save.
+		assert_eq!(radio.on_air(10), Some(("b", 0.0)));
+		assert_eq!(radio.on_air(10 + 2 * length), None);
+	}
+}
@@ -0,0 +1,336 @@
+use std::{cell::RefCell, fmt, fs, path::PathBuf};
+
+use super::input::{Actions, TickInput};
+
+const MAGIC: &[u8; 4] = b"LMRP";
+/// Replays only store the seed, so this changes whenever the world made from
+/// a seed does.
+pub const VERSION: u8 = 5;
+/// More ticks than any run could have, about a month at sixty ticks a second.
+const MAX_TICKS: u64 = 1 << 28;
+
+thread_local! {
+	static RECORD_PATH: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Records every finished run to `path`.
+pub fn set_record_path(path: PathBuf) {
+	RECORD_PATH.with(|p| *p.borrow_mut() = Some(path));
+}
+
+pub fn is_recording() -> bool {
+	RECORD_PATH.with(|p| p.borrow().is_some())
+}
+
+/// Writes a finished run to the record path, if there is one.
+pub fn save_recording(replay: &Replay) {
+	RECORD_PATH.with(|p| {
+		if let Some(path) = &*p.borrow() {
+			match fs::write(path, replay.encode()) {
+				Ok(()) => println!(
+					"Recorded {} ticks to {}",
+					replay.ticks.len(),
+					path.display()
+				),
+				Err(e) => eprintln!("Could not write replay to {}: {}", path.display(), e),
+			}
+		}
+	});
+}
+
+/// The input of every tick of a run, which together with the seed is enough
+/// to play the run back.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Replay {
+	pub seed: u64,
+	/// Whether the run had to escape before a countdown ran out.
+	pub timed: bool,
+	pub ticks: Vec<TickInput>,
+	/// Hash of the state the run ended in, to verify a play back against.
+	pub final_hash: u64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ReplayError {
+	NotAReplay,
+	UnsupportedVersion(u8),
+	Truncated,
+	TooLong,
+	UnknownActions(u8),
+	UnknownMode(u8),
+}
+
+impl fmt::Display for ReplayError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::NotAReplay => write!(f, "not a replay"),
+			Self::UnsupportedVersion(v) => write!(f, "unsupported replay version {}", v),
+			Self::Truncated => write!(f, "replay ends unexpectedly"),
+			Self::TooLong => write!(f, "replay is too long"),
+			Self::UnknownActions(a) => write!(f, "unknown actions {:#010b}", a),
+			Self::UnknownMode(m) => write!(f, "unknown mode {}", m),
+		}
+	}
+}
+
+impl Replay {
+	/// Encodes the replay with runs of equal ticks stored once, which is most
+	/// of them since few keys change and the cursor often stays still.
+	pub fn encode(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(MAGIC);
+		bytes.push(VERSION);
+		bytes.extend_from_slice(&self.seed.to_be_bytes());
+		bytes.push(self.timed as u8);
+		bytes.extend_from_slice(&self.final_hash.to_be_bytes());
+		let mut ticks = self.ticks.iter().peekable();
+		while let Some(tick) = ticks.next() {
+			let mut count = 1;
+			while ticks.peek() == Some(&tick) {
+				ticks.next();
+				count += 1;
+			}
+			write_varint(&mut bytes, count);
+			bytes.push(tick.actions.bits());
+			write_varint(&mut bytes, zigzag(tick.look.0 as i64));
+			write_varint(&mut bytes, zigzag(tick.look.1 as i64));
+		}
+		bytes
+	}
+
+	pub fn decode(bytes: &[u8]) -> Result<Self, ReplayError> {
+		if bytes.len() < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+			return Err(ReplayError::NotAReplay);
+		}
+		let mut reader = Reader::new(&bytes[MAGIC.len()..]);
+		match reader.byte()? {
+			VERSION => {}
+			version => return Err(ReplayError::UnsupportedVersion(version)),
+		}
+		let seed = reader.u64()?;
+		let timed = match reader.byte()? {
+			0 => false,
+			1 => true,
+			mode => return Err(ReplayError::UnknownMode(mode)),
+		};
+		let final_hash = reader.u64()?;
+		let mut ticks = Vec::new();
+		while !reader.is_empty() {
+			let count = reader.varint()?;
+			if count > MAX_TICKS - ticks.len() as u64 {
+				return Err(ReplayError::TooLong);
+			}
+			let bits = reader.byte()?;
+			let actions = Actions::from_bits(bits).ok_or(ReplayError::UnknownActions(bits))?;
+			let look = (
+				unzigzag(reader.varint()?) as i16,
+				unzigzag(reader.varint()?) as i16,
+			);
+			ticks.extend((0..count).map(|_| TickInput {
+				actions,
+				look,
+			}));
+		}
+		Ok(Self {
+			seed,
+			timed,
+			ticks,
+			final_hash,
+		})
+	}
+}
+
+/// FNV-1a hash of the parts of the simulation state that must match between
+/// a run and its play back. Unlike the standard library's hasher, it is the
+/// same on every build.
+pub struct StateHasher(u64);
+
+impl StateHasher {
+	pub fn new() -> Self {
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+
+	pub fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.0 ^= *byte as u64;
+			self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+
+	#[inline]
+	pub fn write_u64(&mut self, value: u64) {
+		self.write(&value.to_be_bytes());
+	}
+
+	#[inline]
+	pub fn write_i64(&mut self, value: i64) {
+		self.write(&value.to_be_bytes());
+	}
+
+	#[inline]
+	pub fn write_f32(&mut self, value: f32) {
+		self.write(&value.to_bits().to_be_bytes());
+	}
+
+	#[inline]
+	pub fn finish(&self) -> u64 {
+		self.0
+	}
+}
+
+impl Default for StateHasher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+pub fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+	while value >= 0x80 {
+		bytes.push(value as u8 | 0x80);
+		value >>= 7;
+	}
+	bytes.push(value as u8);
+}
+
+/// Maps signed values to unsigned ones with small magnitudes kept small, so
+/// that they take few bytes as varints.
+pub fn zigzag(value: i64) -> u64 {
+	((value << 1) ^ (value >> 63)) as u64
+}
+
+pub fn unzigzag(value: u64) -> i64 {
+	((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+pub struct Reader<'a> {
+	bytes: &'a [u8],
+	index: usize,
+}
+
+impl<'a> Reader<'a> {
+	pub fn new(bytes: &'a [u8]) -> Self {
+		Self {
+			bytes,
+			index: 0,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.index >= self.bytes.len()
+	}
+
+	fn byte(&mut self) -> Result<u8, ReplayError> {
+		let byte = *self.bytes.get(self.index).ok_or(ReplayError::Truncated)?;
+		self.index += 1;
+		Ok(byte)
+	}
+
+	fn u64(&mut self) -> Result<u64, ReplayError> {
+		let mut value = [0; 8];
+		for byte in value.iter_mut() {
+			*byte = self.byte()?;
+		}
+		Ok(u64::from_be_bytes(value))
+	}
+
+	pub fn varint(&mut self) -> Result<u64, ReplayError> {
+		let mut value = 0;
+		for shift in (0..64).step_by(7) {
+			let byte = self.byte()?;
+			value |= ((byte & 0x7f) as u64) << shift;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+		}
+		Err(ReplayError::Truncated)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn replay() -> Replay {
+		let mut ticks = vec![TickInput::default(); 100];
+		ticks.extend(vec![
+			TickInput {
+				actions: Actions::FORWARD,
+				look: (3, -2),
+			};
+			500
+		]);
+		ticks.push(TickInput {
+			actions: Actions::USE,
+			look: (i16::MIN, i16::MAX),
+		});
+		Replay {
+			seed: 0xdead_beef,
+			timed: true,
+			ticks,
+			final_hash: 42,
+		}
+	}
+
+	#[test]
+	fn replays_round_trip() {
+		let replay = replay();
+		assert_eq!(Replay::decode(&replay.encode()), Ok(replay));
+	}
+
+	#[test]
+	fn equal_ticks_are_stored_once() {
+		assert!(replay().encode().len() < 64);
+	}
+
+	#[test]
+	fn broken_replays_are_rejected() {
+		let bytes = replay().encode();
+		assert_eq!(Replay::decode(b"PNG"), Err(ReplayError::NotAReplay));
+		assert_eq!(
+			Replay::decode(&bytes[..bytes.len() - 1]),
+			Err(ReplayError::Truncated)
+		);
+		let mut newer = bytes.clone();
+		newer[MAGIC.len()] = VERSION + 1;
+		assert_eq!(
+			Replay::decode(&newer),
+			Err(ReplayError::UnsupportedVersion(VERSION + 1))
+		);
+		let mut unknown_mode = bytes.clone();
+		unknown_mode[MAGIC.len() + 9] = 2;
+		assert_eq!(
+			Replay::decode(&unknown_mode),
+			Err(ReplayError::UnknownMode(2))
+		);
+	}
+
+	#[test]
+	fn huge_runs_are_rejected() {
+		let mut huge = replay().encode()[..MAGIC.len() + 18].to_vec();
+		for count in [1, u64::MAX].iter() {
+			write_varint(&mut huge, *count);
+			huge.extend_from_slice(&[0, 0, 0]);
+		}
+		assert_eq!(Replay::decode(&huge), Err(ReplayError::TooLong));
+	}
+
+	#[test]
+	fn replays_of_older_worlds_are_rejected() {
+		let mut older = replay().encode();
+		for version in 0..VERSION {
+			older[MAGIC.len()] = version;
+			assert_eq!(
+				Replay::decode(&older),
+				Err(ReplayError::UnsupportedVersion(version))
+			);
+		}
+	}
+
+	#[test]
+	fn zigzag_round_trips() {
+		for value in [0, 1, -1, 300, -300, i64::MIN, i64::MAX].iter() {
+			assert_eq!(unzigzag(zigzag(*value)), *value);
+		}
+		assert_eq!(zigzag(i16::MIN as i64), u16::MAX as u64);
+	}
+}
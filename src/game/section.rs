@@ -0,0 +1,529 @@
+use std::{f32, rc::Rc};
+
+use kiss3d::nalgebra::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3};
+use rand::{rngs::StdRng, Rng};
+
+use super::{
+	super::{
+		map::{Direction, Map, Position, DIRECTIONS, ROOM_CENTER, ROOM_SIZE},
+		meshes::ItemKind,
+		rng::{
+			rand_for_border_walls,
+			rand_for_hinged_door,
+			rand_for_key,
+			ring,
+			rng_for_maze,
+			shared_border,
+			WorldParams,
+		},
+		textures::hsl_to_rgb,
+	},
+	landmark::{pick_landmark, Landmark, LANDMARK_ODDS},
+	wall::Wall,
+};
+
+pub const MAZE_HEIGHT: f32 = 2.0;
+pub const MAZE_SIZE: f32 = 1.75;
+pub const MAZE_SIZE_HALF: f32 = MAZE_SIZE / 2.0;
+pub const MAZE_CHUNK_SIZE: f32 = (ROOM_SIZE as f32 + 0.5) * MAZE_SIZE;
+const MAZE_OFFSET: f32 = ROOM_CENTER as f32 * -MAZE_SIZE;
+const MAZE_ABOVE: Translation3<f32> = Translation3::new(-MAZE_SIZE_HALF, 0.0, 0.0);
+const MAZE_LEFT: Translation3<f32> = Translation3::new(0.0, 0.0, MAZE_SIZE_HALF);
+const MAZE_RIGHT: Translation3<f32> = Translation3::new(0.0, 0.0, -MAZE_SIZE_HALF);
+const MAZE_BELOW: Translation3<f32> = Translation3::new(MAZE_SIZE_HALF, 0.0, 0.0);
+const MONSTER_DISTANCE: f32 = 5.0;
+const ITEM_HEIGHT: f32 = -0.1;
+/// Odds of an opening without a locked door having a door that swings open.
+const HINGED_DOOR_ODDS: f32 = 0.3;
+
+/// An item, by its section and the cell it rests in.
+pub type ItemId = ((i64, i64), Position);
+/// A door that swings open, by the border it is in, as in [`shared_border`].
+pub type DoorId = ((i64, i64), Direction);
+
+/// Number of places in a section where there can be a wall.
+pub const WALL_SLOTS: usize = 2 * ROOM_SIZE + 2 * ROOM_SIZE * ROOM_SIZE;
+
+/// A place in a section where there can be a wall.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WallSlot {
+	/// Above the column on the first row.
+	Above(usize),
+	/// Left of the row in the first column.
+	Left(usize),
+	Right(Position),
+	Below(Position),
+}
+
+impl WallSlot {
+	pub fn index(&self) -> usize {
+		match self {
+			Self::Above(col) => *col,
+			Self::Left(row) => ROOM_SIZE + row,
+			Self::Right(pos) => 2 * ROOM_SIZE + pos.0 * ROOM_SIZE + pos.1,
+			Self::Below(pos) => 2 * ROOM_SIZE + ROOM_SIZE * ROOM_SIZE + pos.0 * ROOM_SIZE + pos.1,
+		}
+	}
+
+	pub fn from_index(index: usize) -> Self {
+		if index < ROOM_SIZE {
+			Self::Above(index)
+		} else if index < 2 * ROOM_SIZE {
+			Self::Left(index - ROOM_SIZE)
+		} else if index < 2 * ROOM_SIZE + ROOM_SIZE * ROOM_SIZE {
+			let i = index - 2 * ROOM_SIZE;
+			Self::Right(Position(i / ROOM_SIZE, i % ROOM_SIZE))
+		} else {
+			let i = index - 2 * ROOM_SIZE - ROOM_SIZE * ROOM_SIZE;
+			Self::Below(Position(i / ROOM_SIZE, i % ROOM_SIZE))
+		}
+	}
+
+	/// Interior walls are seen from both sides.
+	pub fn is_interior(&self) -> bool {
+		match self {
+			Self::Above(_) | Self::Left(_) => false,
+			Self::Right(pos) => pos.1 + 1 < ROOM_SIZE,
+			Self::Below(pos) => pos.0 + 1 < ROOM_SIZE,
+		}
+	}
+
+	/// Placement of a wall quad in this slot, relative to its section.
+	pub fn transformation(&self) -> Isometry3<f32> {
+		let (translation, rotation) = match self {
+			Self::Above(col) => (
+				MAZE_ABOVE.vector + grid_translation(0, *col).vector,
+				quarter_turn(),
+			),
+			Self::Left(row) => (
+				MAZE_LEFT.vector + grid_translation(*row, 0).vector,
+				UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI),
+			),
+			Self::Right(pos) => (
+				MAZE_RIGHT.vector + grid_translation(pos.0, pos.1).vector,
+				UnitQuaternion::identity(),
+			),
+			Self::Below(pos) => (
+				MAZE_BELOW.vector + grid_translation(pos.0, pos.1).vector,
+				if self.is_interior() {
+					quarter_turn()
+				} else {
+					three_quarter_turn()
+				},
+			),
+		};
+		Isometry3::from_parts(Translation3::from(translation), rotation)
+	}
+
+	/// Placement of a monster standing in this slot, relative to its section.
+	pub fn monster_transformation(&self) -> Isometry3<f32> {
+		let mut transformation = self.transformation();
+		if let Self::Below(_) = self {
+			transformation.rotation = three_quarter_turn();
+		}
+		transformation
+	}
+}
+
+/// A door in an opening of a border wall, locked until the key of its tier
+/// has been found.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LockedDoor {
+	pub slot: WallSlot,
+	pub tier: usize,
+}
+
+/// A door in an opening of a border wall that has to be opened to get
+/// through, and that slams shut when a monster comes near. The sections on
+/// both sides of it have it, with the same id.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HingedDoor {
+	pub slot: WallSlot,
+	pub id: DoorId,
+}
+
+/// Everything about a section that can be derived from the seed, without
+/// touching the scene.
+pub struct SectionLayout {
+	pub position: (i64, i64),
+	pub offset: Translation3<f32>,
+	pub color: (f32, f32, f32),
+	pub map: Map,
+	/// Openings in the border walls, as in [`rand_for_border_walls`].
+	pub openings: [Option<usize>; 4],
+	pub closed: [bool; WALL_SLOTS],
+	pub walls: Vec<Wall>,
+	pub items: Vec<(Position, ItemKind)>,
+	pub monsters: Vec<WallSlot>,
+	pub doors: Vec<LockedDoor>,
+	pub hinged_doors: Vec<HingedDoor>,
+	/// The landmark laid out in the section, if it is one.
+	pub landmark: Option<Rc<Landmark>>,
+}
+
+impl SectionLayout {
+	pub fn generate(seed: u64, position: (i64, i64), should_add_item: bool) -> Self {
+		Self::generate_with_params(seed, &WorldParams::default(), position, should_add_item)
+	}
+
+	pub fn generate_with_params(
+		seed: u64,
+		params: &WorldParams,
+		position: (i64, i64),
+		should_add_item: bool,
+	) -> Self {
+		let mut rng: StdRng = rng_for_maze(seed, position);
+		let color = hsl_to_rgb(rng.gen(), 0.5, 0.5);
+		let map = params.generator.generate(&mut rng);
+		let opening = |direction| {
+			let inside = |p| params.locked_rings > 0 && ring(p) <= params.key_rings();
+			let door_odds = if inside(position) && inside(neighbour(position, direction)) {
+				1.0
+			} else {
+				params.door_odds
+			};
+			rand_for_border_walls::<StdRng>(seed, position, direction, ROOM_SIZE, door_odds)
+		};
+		let openings = [
+			opening(Direction::Up),
+			opening(Direction::Left),
+			opening(Direction::Right),
+			opening(Direction::Down),
+		];
+
+		let key = (0..=params.locked_rings)
+			.find(|tier| position == rand_for_key::<StdRng>(seed, params, *tier));
+
+		let mut layout = Self {
+			position,
+			offset: section_offset(position),
+			color,
+			map,
+			openings,
+			closed: [false; WALL_SLOTS],
+			walls: Vec::new(),
+			items: Vec::new(),
+			monsters: Vec::new(),
+			doors: Vec::new(),
+			hinged_doors: Vec::new(),
+			landmark: None,
+		};
+
+		for (direction, opening) in DIRECTIONS.iter().zip(openings.iter()) {
+			let tier = params.door_tier(position, neighbour(position, *direction));
+			match (opening, tier) {
+				(Some(index), Some(tier)) => layout.doors.push(LockedDoor {
+					slot: border_slot(*direction, *index),
+					tier,
+				}),
+				(Some(index), None)
+					if rand_for_hinged_door::<StdRng>(
+						seed,
+						position,
+						*direction,
+						HINGED_DOOR_ODDS,
+					) =>
+				{
+					layout.hinged_doors.push(HingedDoor {
+						slot: border_slot(*direction, *index),
+						id: shared_border(position, *direction),
+					})
+				}
+				_ => {}
+			}
+		}
+
+		let monster_odds = exposure(position);
+		for slot in layout.close_walls() {
+			if rng.gen::<f32>() < monster_odds {
+				layout.monsters.push(slot);
+			}
+		}
+
+		// The lock and the keys are never in a landmark, so that the way to
+		// them is always carved at random.
+		if position != (0, 0) && key.is_none() && rng.gen::<f32>() < LANDMARK_ODDS {
+			let landmark = pick_landmark(&mut rng);
+			layout.map = landmark.map.clone();
+			layout.closed = [false; WALL_SLOTS];
+			layout.walls.clear();
+			layout.monsters.clear();
+			layout.close_walls();
+			layout.landmark = Some(landmark);
+		}
+
+		if should_add_item {
+			layout.items = if position == (0, 0) {
+				vec![(Position(ROOM_CENTER, ROOM_CENTER), ItemKind::Lock)]
+			} else if let Some(landmark) = &layout.landmark {
+				landmark
+					.coins
+					.iter()
+					.map(|pos| (*pos, ItemKind::Coin))
+					.collect()
+			} else {
+				let mut rng: StdRng = rng_for_maze(seed, position);
+				vec![(
+					Position(rng.gen_range(0..ROOM_SIZE), rng.gen_range(0..ROOM_SIZE)),
+					key.map_or(ItemKind::Coin, ItemKind::Key),
+				)]
+			};
+		}
+
+		layout
+	}
+
+	/// Closes the border walls outside of the openings, and the walls of the
+	/// map. Returns the interior slots left open.
+	fn close_walls(&mut self) -> Vec<WallSlot> {
+		let [up_opening, left_opening, right_opening, down_opening] = self.openings;
+		let mut open = Vec::new();
+		for row in 0..ROOM_SIZE {
+			for col in 0..ROOM_SIZE {
+				let pos = Position(row, col);
+				if row == 0 && Some(col) != up_opening {
+					self.close(WallSlot::Above(col));
+				}
+				if col == 0 && Some(row) != left_opening {
+					self.close(WallSlot::Left(row));
+				}
+				if col + 1 == ROOM_SIZE {
+					if Some(row) != right_opening {
+						self.close(WallSlot::Right(pos));
+					}
+				} else if self.map.is_right(&pos) {
+					self.close(WallSlot::Right(pos));
+				} else {
+					open.push(WallSlot::Right(pos));
+				}
+				if row + 1 == ROOM_SIZE {
+					if Some(col) != down_opening {
+						self.close(WallSlot::Below(pos));
+					}
+				} else if self.map.is_below(&pos) {
+					self.close(WallSlot::Below(pos));
+				} else {
+					open.push(WallSlot::Below(pos));
+				}
+			}
+		}
+		open
+	}
+
+	fn close(&mut self, slot: WallSlot) {
+		self.closed[slot.index()] = true;
+		self.walls.push(self.wall_at(slot));
+	}
+
+	/// The wall that keeps the Agent from walking through `slot`.
+	pub fn wall_at(&self, slot: WallSlot) -> Wall {
+		let x = self.offset.x;
+		let z = self.offset.z;
+		match slot {
+			WallSlot::Above(col) => Wall::Horizontal(Point3::new(
+				x - MAZE_SIZE_HALF,
+				0.0,
+				z + col as f32 * -MAZE_SIZE,
+			)),
+			WallSlot::Left(row) => Wall::Vertical(Point3::new(
+				x + row as f32 * MAZE_SIZE,
+				0.0,
+				z + MAZE_SIZE_HALF,
+			)),
+			WallSlot::Right(Position(row, col)) => Wall::Vertical(Point3::new(
+				x + row as f32 * MAZE_SIZE,
+				0.0,
+				z + col as f32 * -MAZE_SIZE - MAZE_SIZE_HALF,
+			)),
+			WallSlot::Below(Position(row, col)) => Wall::Horizontal(Point3::new(
+				x + row as f32 * MAZE_SIZE + MAZE_SIZE_HALF,
+				0.0,
+				z + col as f32 * -MAZE_SIZE,
+			)),
+		}
+	}
+
+	#[inline]
+	pub fn is_closed(&self, slot: WallSlot) -> bool {
+		self.closed[slot.index()]
+	}
+
+	/// The locked door on the way out of `position` in `direction`, if there
+	/// is one.
+	pub fn door(&self, position: Position, direction: Direction) -> Option<&LockedDoor> {
+		let slot = exit_slot(position, direction)?;
+		self.doors.iter().find(|door| door.slot == slot)
+	}
+
+	/// The door that swings open on the way out of `position` in
+	/// `direction`, if there is one.
+	pub fn hinged_door(&self, position: Position, direction: Direction) -> Option<&HingedDoor> {
+		let slot = exit_slot(position, direction)?;
+		self.hinged_doors.iter().find(|door| door.slot == slot)
+	}
+
+	/// Where an item in the cell `pos` rests, before it starts to float.
+	pub fn item_translation(&self, pos: Position) -> Translation3<f32> {
+		Translation3::new(self.offset.x, ITEM_HEIGHT, self.offset.z)
+			* grid_translation(pos.0, pos.1)
+	}
+}
+
+/// The section next to `position` in `direction`.
+pub fn neighbour(position: (i64, i64), direction: Direction) -> (i64, i64) {
+	match direction {
+		Direction::Up => (position.0, position.1 - 1),
+		Direction::Left => (position.0 - 1, position.1),
+		Direction::Right => (position.0 + 1, position.1),
+		Direction::Down => (position.0, position.1 + 1),
+	}
+}
+
+/// The border slot on the way out of `position` in `direction`, if the way
+/// leads out of the section.
+fn exit_slot(position: Position, direction: Direction) -> Option<WallSlot> {
+	let Position(row, col) = position;
+	match direction {
+		Direction::Up if row == 0 => Some(border_slot(direction, col)),
+		Direction::Left if col == 0 => Some(border_slot(direction, row)),
+		Direction::Right if col + 1 == ROOM_SIZE => Some(border_slot(direction, row)),
+		Direction::Down if row + 1 == ROOM_SIZE => Some(border_slot(direction, col)),
+		_ => None,
+	}
+}
+
+/// The slot of the opening `index` cells along the border wall in
+/// `direction`.
+fn border_slot(direction: Direction, index: usize) -> WallSlot {
+	match direction {
+		Direction::Up => WallSlot::Above(index),
+		Direction::Left => WallSlot::Left(index),
+		Direction::Right => WallSlot::Right(Position(index, ROOM_SIZE - 1)),
+		Direction::Down => WallSlot::Below(Position(ROOM_SIZE - 1, index)),
+	}
+}
+
+/// Translation of a section's first cell.
+pub fn section_offset(position: (i64, i64)) -> Translation3<f32> {
+	Translation3::new(
+		MAZE_OFFSET + (position.1 * ROOM_SIZE as i64) as f32 * MAZE_SIZE,
+		0.0,
+		-(MAZE_OFFSET + (position.0 * ROOM_SIZE as i64) as f32 * MAZE_SIZE),
+	)
+}
+
+/// Translation of a cell, relative to its section.
+pub fn grid_translation(row: usize, col: usize) -> Translation3<f32> {
+	Translation3::new(row as f32 * MAZE_SIZE, 0.0, col as f32 * -MAZE_SIZE)
+}
+
+/// How exposed the Agent is in a section, based on its distance from base.
+pub fn exposure(position: (i64, i64)) -> f32 {
+	((position.0 as f32).powi(2) + (position.1 as f32).powi(2)).sqrt() / MONSTER_DISTANCE
+}
+
+fn quarter_turn() -> UnitQuaternion<f32> {
+	UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI / 2.0)
+}
+
+fn three_quarter_turn() -> UnitQuaternion<f32> {
+	UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI * 1.5)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn wall_slot_indices_round_trip() {
+		for index in 0..WALL_SLOTS {
+			assert_eq!(WallSlot::from_index(index).index(), index);
+		}
+	}
+
+	#[test]
+	fn locked_doors_match_on_both_sides() {
+		let params = WorldParams::default();
+		let rings = params.key_rings();
+		for row in -rings..=rings {
+			for col in -rings..=rings {
+				let layout = SectionLayout::generate_with_params(4, &params, (row, col), false);
+				for (direction, opening) in DIRECTIONS.iter().zip(layout.openings.iter()) {
+					let other = neighbour((row, col), *direction);
+					if ring(other) > rings {
+						continue;
+					}
+					let index = opening.expect("borders inside the key rings have doors");
+					let position = match direction {
+						Direction::Up => Position(0, index),
+						Direction::Left => Position(index, 0),
+						Direction::Right => Position(index, ROOM_SIZE - 1),
+						Direction::Down => Position(ROOM_SIZE - 1, index),
+					};
+					assert_eq!(
+						layout.door(position, *direction).map(|door| door.tier),
+						params.door_tier((row, col), other)
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn hinged_doors_match_on_both_sides() {
+		for row in -5..5 {
+			for col in -5..5 {
+				let layout = SectionLayout::generate(9, (row, col), false);
+				for door in layout.hinged_doors.iter() {
+					let (section, direction) = door.id;
+					let other = if section == (row, col) {
+						neighbour(section, direction)
+					} else {
+						section
+					};
+					let other = SectionLayout::generate(9, other, false);
+					assert!(other.hinged_doors.iter().any(|d| d.id == door.id));
+					assert!(layout.doors.iter().all(|d| d.slot != door.slot));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn interior_walls_match_map() {
+		let layout = SectionLayout::generate(0, (1, 2), true);
+		for row in 0..ROOM_SIZE {
+			for col in 0..ROOM_SIZE - 1 {
+				let pos = Position(row, col);
+				assert_eq!(
+					layout.is_closed(WallSlot::Right(pos)),
+					layout.map.is_right(&pos)
+				);
+			}
+		}
+		assert_eq!(
+			layout.walls.len(),
+			layout.closed.iter().filter(|c| **c).count()
+		);
+	}
+
+	#[test]
+	fn landmarks_are_laid_out_as_drawn() {
+		let layout = (1..1000)
+			.map(|col| SectionLayout::generate(0, (4, col), true))
+			.find(|layout| layout.landmark.is_some())
+			.expect("some section is a landmark");
+		let landmark = layout.landmark.as_ref().unwrap();
+		for index in 0..WALL_SLOTS {
+			let slot = WallSlot::from_index(index);
+			let drawn = match slot {
+				WallSlot::Right(pos) if slot.is_interior() => landmark.map.is_right(&pos),
+				WallSlot::Below(pos) if slot.is_interior() => landmark.map.is_below(&pos),
+				_ => continue,
+			};
+			assert_eq!(layout.is_closed(slot), drawn);
+		}
+		assert!(layout.monsters.is_empty());
+		assert_eq!(layout.items.len(), landmark.coins.len());
+	}
+}
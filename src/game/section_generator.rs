@@ -0,0 +1,183 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	time::Duration,
+};
+
+use instant::Instant;
+
+use super::section::SectionLayout;
+
+/// How far a direction has to lean towards an axis to count as moving along
+/// it.
+const DIRECTION_THRESHOLD: f32 = 0.3;
+
+/// Generates the layouts of sections before they come into range, a few at a
+/// time so that no single frame has to generate them all. There are no
+/// threads on the web, so the work is spread out over frames instead.
+pub struct SectionGenerator {
+	seed: u64,
+	range: i64,
+	queue: VecDeque<(i64, i64)>,
+	ready: HashMap<(i64, i64), SectionLayout>,
+}
+
+impl SectionGenerator {
+	pub fn new(seed: u64, range: i64) -> Self {
+		Self {
+			seed,
+			range,
+			queue: VecDeque::new(),
+			ready: HashMap::new(),
+		}
+	}
+
+	#[inline]
+	pub fn set_range(&mut self, range: i64) {
+		self.range = range;
+	}
+
+	/// Queues the sections that would come into range if the Agent kept
+	/// moving in `direction`, given as rows and columns, from `position`.
+	pub fn predict(&mut self, position: (i64, i64), direction: (f32, f32)) {
+		let length = (direction.0.powi(2) + direction.1.powi(2)).sqrt();
+		if length == 0.0 {
+			return;
+		}
+		let step = |d: f32| {
+			if d / length > DIRECTION_THRESHOLD {
+				1
+			} else if d / length < -DIRECTION_THRESHOLD {
+				-1
+			} else {
+				0
+			}
+		};
+		let (rows, cols) = (step(direction.0), step(direction.1));
+
+		let mut targets = vec![(position.0 + rows, position.1 + cols)];
+		if rows != 0 && cols != 0 {
+			targets.push((position.0 + rows, position.1));
+			targets.push((position.0, position.1 + cols));
+		}
+		for target in targets {
+			if target == position {
+				continue;
+			}
+			for section in diamond(target, self.range) {
+				let distance = manhattan(section, position);
+				if distance > self.range
+					&& distance <= self.range + 1
+					&& !self.ready.contains_key(&section)
+					&& !self.queue.contains(&section)
+				{
+					self.queue.push_back(section);
+				}
+			}
+		}
+	}
+
+	/// Generates queued sections until `budget` has been spent. Returns the
+	/// number of generated sections.
+	pub fn generate(&mut self, budget: Duration) -> usize {
+		let start = Instant::now();
+		let mut generated = 0;
+		while start.elapsed() < budget {
+			if let Some(position) = self.queue.pop_front() {
+				self.ready
+					.insert(position, SectionLayout::generate(self.seed, position, true));
+				generated += 1;
+			} else {
+				break;
+			}
+		}
+		generated
+	}
+
+	/// Takes the layout of a section, generating it right away if it was not
+	/// generated ahead of time.
+	pub fn take(&mut self, position: (i64, i64)) -> SectionLayout {
+		self.queue.retain(|p| p != &position);
+		self.ready
+			.remove(&position)
+			.unwrap_or_else(|| SectionLayout::generate(self.seed, position, true))
+	}
+
+	/// Drops sections that the Agent has moved too far away from to need.
+	pub fn forget_distant(&mut self, position: (i64, i64)) {
+		let limit = self.range + 1;
+		self.queue.retain(|p| manhattan(*p, position) <= limit);
+		self.ready.retain(|p, _| manhattan(*p, position) <= limit);
+	}
+}
+
+/// The sections within `range` of `center`.
+pub fn diamond(center: (i64, i64), range: i64) -> impl Iterator<Item = (i64, i64)> {
+	(-range..=range).flat_map(move |y| {
+		let width = range - y.abs();
+		(-width..=width).map(move |x| (center.0 + x, center.1 + y))
+	})
+}
+
+#[inline]
+pub fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+	(a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn predicts_sections_entering_range() {
+		let mut generator = SectionGenerator::new(0, 2);
+		generator.predict((0, 0), (0.0, 1.0));
+		assert_eq!(generator.queue.len(), 5);
+		assert!(generator
+			.queue
+			.iter()
+			.all(|p| manhattan(*p, (0, 1)) <= 2 && manhattan(*p, (0, 0)) > 2));
+
+		generator.predict((0, 0), (0.0, 1.0));
+		assert_eq!(generator.queue.len(), 5);
+
+		let mut generator = SectionGenerator::new(0, 2);
+		generator.predict((0, 0), (1.0, 1.0));
+		assert!(!generator.queue.is_empty());
+		assert!(generator.queue.iter().all(|p| manhattan(*p, (0, 0)) == 3));
+		let queued = generator.queue.len();
+		generator.forget_distant((0, 0));
+		assert_eq!(generator.queue.len(), queued);
+	}
+
+	#[test]
+	fn standing_still_predicts_nothing() {
+		let mut generator = SectionGenerator::new(0, 2);
+		generator.predict((3, 3), (0.0, 0.0));
+		assert!(generator.queue.is_empty());
+	}
+
+	#[test]
+	fn generation_respects_budget() {
+		let mut generator = SectionGenerator::new(0, 2);
+		generator.predict((0, 0), (1.0, 1.0));
+		let queued = generator.queue.len();
+		assert_eq!(generator.generate(Duration::from_secs(0)), 0);
+		assert_eq!(generator.generate(Duration::from_secs(60)), queued);
+		assert!(generator.queue.is_empty());
+	}
+
+	#[test]
+	fn generated_ahead_matches_generated_now() {
+		let mut generator = SectionGenerator::new(7, 2);
+		generator.predict((0, 0), (-1.0, 0.0));
+		generator.generate(Duration::from_secs(60));
+		assert!(generator.ready.contains_key(&(-3, 0)));
+
+		let ahead = generator.take((-3, 0));
+		let now = SectionLayout::generate(7, (-3, 0), true);
+		assert_eq!(&ahead.closed[..], &now.closed[..]);
+		assert_eq!(ahead.items, now.items);
+		assert_eq!(ahead.monsters, now.monsters);
+		assert!(!generator.ready.contains_key(&(-3, 0)));
+	}
+}
@@ -0,0 +1,141 @@
+use std::f32;
+
+use kiss3d::nalgebra::{Isometry3, Point2, Point3, Translation3, UnitQuaternion, Vector3};
+
+use super::{
+	super::map::ROOM_SIZE,
+	section::{grid_translation, SectionLayout, WallSlot, MAZE_HEIGHT, MAZE_SIZE, WALL_SLOTS},
+};
+
+/// Vertex and index buffers of a mesh, before it is uploaded.
+#[derive(Default)]
+pub struct MeshData {
+	pub coords: Vec<Point3<f32>>,
+	pub normals: Vec<Vector3<f32>>,
+	pub uvs: Vec<Point2<f32>>,
+	pub faces: Vec<Point3<u16>>,
+}
+
+impl MeshData {
+	/// Adds a one sided `width` by `height` quad, facing the positive z axis
+	/// before being placed by `transformation`.
+	pub fn add_quad(&mut self, transformation: &Isometry3<f32>, width: f32, height: f32) {
+		let first = self.coords.len() as u16;
+		let (w, h) = (width / 2.0, height / 2.0);
+		let normal = transformation.rotation * Vector3::z();
+		for (x, y, u, v) in [
+			(-w, -h, 1.0, 1.0),
+			(w, -h, 0.0, 1.0),
+			(w, h, 0.0, 0.0),
+			(-w, h, 1.0, 0.0),
+		]
+		.iter()
+		{
+			self.coords.push(transformation * Point3::new(*x, *y, 0.0));
+			self.normals.push(normal);
+			self.uvs.push(Point2::new(*u, *v));
+		}
+		self.faces.push(Point3::new(first, first + 1, first + 2));
+		self.faces.push(Point3::new(first, first + 2, first + 3));
+	}
+}
+
+/// All walls of a section merged into one mesh. Interior walls get a quad
+/// for each side.
+pub fn wall_mesh(layout: &SectionLayout) -> MeshData {
+	let half_turn = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), f32::consts::PI);
+	let mut mesh = MeshData::default();
+	for index in 0..WALL_SLOTS {
+		let slot = WallSlot::from_index(index);
+		if !layout.is_closed(slot) {
+			continue;
+		}
+		let transformation = slot.transformation();
+		mesh.add_quad(&transformation, MAZE_SIZE, MAZE_HEIGHT);
+		if slot.is_interior() {
+			mesh.add_quad(&(transformation * half_turn), MAZE_SIZE, MAZE_HEIGHT);
+		}
+	}
+	mesh
+}
+
+/// The floor of a section, the same for every section.
+pub fn floor_mesh() -> MeshData {
+	tile_mesh(-MAZE_HEIGHT / 2.0, -f32::consts::PI / 2.0)
+}
+
+/// The ceiling of a section, the same for every section.
+pub fn ceiling_mesh() -> MeshData {
+	tile_mesh(MAZE_HEIGHT / 2.0, f32::consts::PI / 2.0)
+}
+
+fn tile_mesh(height: f32, angle: f32) -> MeshData {
+	let turn = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), angle);
+	let mut mesh = MeshData::default();
+	for row in 0..ROOM_SIZE {
+		for col in 0..ROOM_SIZE {
+			let translation = grid_translation(row, col).vector + Vector3::new(0.0, height, 0.0);
+			mesh.add_quad(
+				&Isometry3::from_parts(Translation3::from(translation), turn),
+				MAZE_SIZE,
+				MAZE_SIZE,
+			);
+		}
+	}
+	mesh
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn wall_mesh_matches_map() {
+		for seed in 0..100 {
+			let layout = SectionLayout::generate(seed, (seed as i64 % 7, 3), false);
+			if layout.landmark.is_some() {
+				continue;
+			}
+			let openings = layout.openings.iter().filter(|o| o.is_some()).count();
+			let border_walls = 4 * ROOM_SIZE - openings;
+			// A perfect maze has one passage less than it has cells.
+			let interior_walls = 2 * ROOM_SIZE * (ROOM_SIZE - 1) - (ROOM_SIZE * ROOM_SIZE - 1);
+			let quads = border_walls + 2 * interior_walls;
+
+			let mesh = wall_mesh(&layout);
+			assert_eq!(mesh.coords.len(), 4 * quads);
+			assert_eq!(mesh.normals.len(), mesh.coords.len());
+			assert_eq!(mesh.uvs.len(), mesh.coords.len());
+			assert_eq!(mesh.faces.len(), 2 * quads);
+			assert!(mesh
+				.faces
+				.iter()
+				.all(|f| (f.x.max(f.y).max(f.z) as usize) < mesh.coords.len()));
+		}
+	}
+
+	#[test]
+	fn tile_meshes_cover_section() {
+		for mesh in [floor_mesh(), ceiling_mesh()].iter() {
+			assert_eq!(mesh.coords.len(), 4 * ROOM_SIZE * ROOM_SIZE);
+			assert_eq!(mesh.faces.len(), 2 * ROOM_SIZE * ROOM_SIZE);
+		}
+	}
+
+	#[test]
+	fn floor_faces_up_and_ceiling_faces_down() {
+		assert!(floor_mesh().normals.iter().all(|n| n.y > 0.99));
+		assert!(ceiling_mesh().normals.iter().all(|n| n.y < -0.99));
+	}
+
+	#[test]
+	fn walls_stay_within_section() {
+		let layout = SectionLayout::generate(0, (0, 0), false);
+		let limit = (ROOM_SIZE as f32 - 0.5) * MAZE_SIZE + 0.001;
+		for c in wall_mesh(&layout).coords {
+			assert!(-MAZE_SIZE / 2.0 - 0.001 <= c.x && c.x <= limit);
+			assert!(-limit <= c.z && c.z <= MAZE_SIZE / 2.0 + 0.001);
+			assert!(c.y.abs() <= MAZE_HEIGHT / 2.0 + 0.001);
+		}
+	}
+}
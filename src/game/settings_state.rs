@@ -0,0 +1,190 @@
+use kiss3d::{
+	conrod::{
+		color::{Color, Colorable},
+		position::{Positionable, Sizeable},
+		widget::{self, Widget},
+		widget_ids,
+		Borderable,
+		Labelable,
+	},
+	window::Window,
+};
+
+use super::{
+	super::{
+		effects::SCREEN_EFFECTS,
+		locale::{tr, tr_with},
+		settings::{set_settings, settings, Settings},
+		text::NameTheme,
+	},
+	playing_state::SavedPlayingState,
+	InnerGameState,
+	MouseButtons,
+};
+
+pub struct SettingsState {
+	ui_ids: UiIds,
+	settings: Settings,
+	playing_state: Option<SavedPlayingState>,
+}
+
+impl SettingsState {
+	/// Creates a settings menu that returns to the pause menu of
+	/// `playing_state` if there is one, or to the main menu otherwise.
+	pub fn new(window: &mut Window, playing_state: Option<SavedPlayingState>) -> Self {
+		let mut ui_ids = UiIds::new(window.conrod_ui_mut().widget_id_generator());
+		ui_ids.effect_buttons.resize(
+			SCREEN_EFFECTS.len(),
+			&mut window.conrod_ui_mut().widget_id_generator(),
+		);
+		Self {
+			ui_ids,
+			settings: settings(),
+			playing_state,
+		}
+	}
+}
+
+impl InnerGameState for SettingsState {
+	fn step(&mut self, window: &mut Window, _: &MouseButtons) -> Option<Box<dyn InnerGameState>> {
+		let mut effects_clicked = Vec::new();
+		let dithering_clicked;
+		let view_distance_clicked;
+		let adaptive_view_distance_clicked;
+		let language_clicked;
+		let name_theme_clicked;
+		let back_clicked;
+		{
+			let mut ui = window.conrod_ui_mut().set_widgets();
+
+			widget::Text::new(&tr("settings"))
+				.font_size(75)
+				.color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.mid_top_with_margin(150.0)
+				.center_justify()
+				.set(self.ui_ids.title, &mut ui);
+
+			for (i, effect) in SCREEN_EFFECTS.iter().enumerate() {
+				let label =
+					on_off_label(effect.message(), self.settings.is_effect_enabled(*effect));
+				if settings_button(&label)
+					.mid_top_with_margin(row(i))
+					.set(self.ui_ids.effect_buttons[i], &mut ui)
+					.was_clicked()
+				{
+					effects_clicked.push(*effect);
+				}
+			}
+			dithering_clicked =
+				settings_button(&on_off_label("setting-dithering", self.settings.dithering))
+					.mid_top_with_margin(row(SCREEN_EFFECTS.len()))
+					.set(self.ui_ids.dithering_button, &mut ui);
+			view_distance_clicked = settings_button(&tr_with("setting-view-distance", &[(
+				"distance",
+				self.settings.view_distance.into(),
+			)]))
+			.mid_top_with_margin(row(SCREEN_EFFECTS.len() + 1))
+			.set(self.ui_ids.view_distance_button, &mut ui);
+			adaptive_view_distance_clicked = settings_button(&on_off_label(
+				"setting-adaptive-view-distance",
+				self.settings.adaptive_view_distance,
+			))
+			.mid_top_with_margin(row(SCREEN_EFFECTS.len() + 2))
+			.set(self.ui_ids.adaptive_view_distance_button, &mut ui);
+			language_clicked = settings_button(&tr_with("setting-language", &[(
+				"language",
+				self.settings.language.name().into(),
+			)]))
+			.mid_top_with_margin(row(SCREEN_EFFECTS.len() + 3))
+			.set(self.ui_ids.language_button, &mut ui);
+			name_theme_clicked = settings_button(&tr_with("setting-section-names", &[(
+				"names",
+				tr(self
+					.settings
+					.name_theme
+					.map_or("names-of-language", NameTheme::message))
+				.into(),
+			)]))
+			.mid_top_with_margin(row(SCREEN_EFFECTS.len() + 4))
+			.set(self.ui_ids.name_theme_button, &mut ui);
+
+			back_clicked = widget::Button::new()
+				.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+				.border_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.label(&tr("back"))
+				.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+				.w(200.0)
+				.mid_bottom_with_margin(100.0)
+				.set(self.ui_ids.back_button, &mut ui);
+		}
+
+		for effect in effects_clicked {
+			self.settings.toggle_effect(effect);
+		}
+		if dithering_clicked.was_clicked() {
+			self.settings.dithering = !self.settings.dithering;
+		}
+		if view_distance_clicked.was_clicked() {
+			self.settings.cycle_view_distance();
+		}
+		if adaptive_view_distance_clicked.was_clicked() {
+			self.settings.adaptive_view_distance = !self.settings.adaptive_view_distance;
+		}
+		if language_clicked.was_clicked() {
+			self.settings.language = self.settings.language.next();
+		}
+		if name_theme_clicked.was_clicked() {
+			self.settings.cycle_name_theme();
+		}
+		set_settings(self.settings.clone());
+
+		if back_clicked.was_clicked() {
+			if let Some(playing_state) = self.playing_state.take() {
+				Some(Box::new(super::PauseState::new(window, playing_state)))
+			} else {
+				Some(Box::new(super::MenuState::new(window)))
+			}
+		} else {
+			None
+		}
+	}
+}
+
+/// Distance from the top to the `i`th setting.
+fn row(i: usize) -> f64 {
+	250.0 + 40.0 * i as f64
+}
+
+/// Label of a setting named by the message `name`, that is either on or off.
+fn on_off_label(name: &str, on: bool) -> String {
+	tr_with("setting-toggle", &[
+		("name", tr(name).into()),
+		("state", if on { "on" } else { "off" }.into()),
+	])
+}
+
+fn settings_button(label: &str) -> widget::Button<widget::button::Flat> {
+	widget::Button::new()
+		.color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+		.hover_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+		.press_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+		.border_color(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+		.label(label)
+		.label_color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+		.w(300.0)
+}
+
+widget_ids! {
+	struct UiIds {
+		title,
+		effect_buttons[],
+		dithering_button,
+		view_distance_button,
+		adaptive_view_distance_button,
+		language_button,
+		name_theme_button,
+		back_button,
+	}
+}
@@ -0,0 +1,875 @@
+use std::collections::{HashMap, HashSet};
+
+use kiss3d::nalgebra::{distance, Point3, Vector3};
+use serde::Serialize;
+
+use super::{
+	super::{
+		camera::FirstPerson,
+		map::{Direction, Position, DIRECTIONS, ROOM_SIZE},
+		meshes::ItemKind,
+		rng::WorldParams,
+	},
+	countdown::Countdown,
+	door::DoorState,
+	input::{Actions, TickInput},
+	landmark::Landmark,
+	monster::Monster,
+	replay::StateHasher,
+	section::{DoorId, ItemId, SectionLayout, MAZE_SIZE, MAZE_SIZE_HALF},
+	section_generator::{diamond, manhattan},
+	visibility::{is_open, WorldCell},
+	wall::Wall,
+};
+
+/// Sections within this many sections of the Agent are simulated. Monsters
+/// notice the Agent within
+/// [`MAZE_CHUNK_SIZE`](super::section::MAZE_CHUNK_SIZE), which never reaches
+/// further than this.
+const SIMULATED_RANGE: i64 = 3;
+/// How much slower the Agent wades through a flooded landmark than it walks.
+const WADING_SPEED: f32 = 0.6;
+/// Monsters that notice the Agent slam the doors this close to them shut.
+const SLAM_DISTANCE: f32 = 2.0 * MAZE_SIZE;
+
+/// Everything about a run that depends on its input: the Agent, the sections
+/// around it with their items and monsters, and what has been collected.
+/// Stepping it needs no window, so runs can be played without rendering them.
+pub struct Simulation {
+	seed: u64,
+	params: WorldParams,
+	/// Number of ticks run so far.
+	tick: u64,
+	camera: FirstPerson,
+	position: (i64, i64),
+	sections: HashMap<(i64, i64), SimulatedSection>,
+	/// Number of keys picked up. Each key is behind the doors the one before
+	/// it opens, so they are always picked up in order.
+	keys: usize,
+	coins: usize,
+	collected_items: HashSet<ItemId>,
+	/// Doors that swing open, kept when their sections are no longer
+	/// simulated. Doors that are not here are shut.
+	doors: HashMap<DoorId, DoorState>,
+	escaped: bool,
+	key_tick: Option<u64>,
+	visited_sections: HashSet<(i64, i64)>,
+	monster_encounters: u64,
+	/// The time left, if the run is timed.
+	countdown: Option<Countdown>,
+	timed_out: bool,
+	/// Where on the floor the other Agent is, if this is a co-op run.
+	/// Monsters notice whichever Agent is closer.
+	partner: Option<(f32, f32)>,
+}
+
+#[derive(Clone)]
+pub struct SavedSimulation {
+	camera_eye: Point3<f32>,
+	camera_look: (f32, f32),
+	seed: u64,
+	params: WorldParams,
+	tick: u64,
+	position: (i64, i64),
+	keys: usize,
+	coins: usize,
+	collected_items: HashSet<ItemId>,
+	doors: HashMap<DoorId, DoorState>,
+	key_tick: Option<u64>,
+	visited_sections: HashSet<(i64, i64)>,
+	monster_encounters: u64,
+	countdown: Option<Countdown>,
+}
+
+struct SimulatedSection {
+	layout: SectionLayout,
+	monsters: Vec<SimulatedMonster>,
+}
+
+/// A monster in a simulated section, as of the last tick.
+pub struct SimulatedMonster {
+	position: Point3<f32>,
+	monster: Monster,
+	/// Distance from the Agent.
+	pub distance: f32,
+	pub visible: bool,
+}
+
+/// What happened during a tick.
+#[derive(Default)]
+pub struct TickEvents {
+	/// How far the Agent tried to move, before walls pushed it back.
+	pub movement: Option<Vector3<f32>>,
+	pub entered_section: Option<(i64, i64)>,
+	/// The cell of the item that was picked up, and what it was.
+	pub collected: Option<(Position, ItemKind)>,
+	/// The door that swings open that the Agent started opening.
+	pub opened: Option<DoorId>,
+	/// A monster noticed the Agent.
+	pub noticed: bool,
+	pub escaped: bool,
+	/// The countdown of a timed run ran out.
+	pub timed_out: bool,
+}
+
+/// Statistics of a run, as reported by the `simulate` command.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct RunStats {
+	pub seed: u64,
+	pub ticks: u64,
+	pub escaped: bool,
+	/// Tick the key to the lock was picked up on.
+	pub key_tick: Option<u64>,
+	pub keys: usize,
+	pub coins: usize,
+	pub sections_visited: usize,
+	/// Number of times a monster noticed the Agent.
+	pub monster_encounters: u64,
+	/// Ticks left of the countdown, for timed runs.
+	pub remaining_ticks: Option<u64>,
+	pub timed_out: bool,
+}
+
+impl Simulation {
+	pub fn new(seed: u64) -> Self {
+		Self::with_params(seed, WorldParams::default())
+	}
+
+	pub fn with_params(seed: u64, params: WorldParams) -> Self {
+		let mut simulation = Self {
+			seed,
+			params,
+			tick: 0,
+			camera: FirstPerson::new(Point3::new(0.0, 0.25, 0.0), Point3::new(0.0, 0.25, -1.0)),
+			position: (0, 0),
+			sections: HashMap::new(),
+			keys: 0,
+			coins: 0,
+			collected_items: HashSet::new(),
+			doors: HashMap::new(),
+			escaped: false,
+			key_tick: None,
+			visited_sections: HashSet::new(),
+			monster_encounters: 0,
+			countdown: None,
+			timed_out: false,
+			partner: None,
+		};
+		simulation.enter((0, 0));
+		simulation
+	}
+
+	/// A run that has to escape before a countdown runs out.
+	pub fn timed(seed: u64) -> Self {
+		Self {
+			countdown: Some(Countdown::default()),
+			..Self::new(seed)
+		}
+	}
+
+	pub fn save(&self) -> SavedSimulation {
+		SavedSimulation {
+			camera_eye: *self.camera.eye(),
+			camera_look: self.camera.look_angles(),
+			seed: self.seed,
+			params: self.params,
+			tick: self.tick,
+			position: self.position,
+			keys: self.keys,
+			coins: self.coins,
+			collected_items: self.collected_items.clone(),
+			doors: self.doors.clone(),
+			key_tick: self.key_tick,
+			visited_sections: self.visited_sections.clone(),
+			monster_encounters: self.monster_encounters,
+			countdown: self.countdown,
+		}
+	}
+
+	pub fn restore(save: &SavedSimulation) -> Self {
+		let mut camera = FirstPerson::new(save.camera_eye, save.camera_eye - Vector3::z());
+		camera.set_look_angles(save.camera_look);
+		let mut simulation = Self {
+			seed: save.seed,
+			params: save.params,
+			tick: save.tick,
+			camera,
+			position: save.position,
+			sections: HashMap::new(),
+			keys: save.keys,
+			coins: save.coins,
+			collected_items: save.collected_items.clone(),
+			doors: save.doors.clone(),
+			escaped: false,
+			key_tick: save.key_tick,
+			visited_sections: save.visited_sections.clone(),
+			monster_encounters: save.monster_encounters,
+			countdown: save.countdown,
+			timed_out: false,
+			partner: None,
+		};
+		simulation.enter(save.position);
+		simulation
+	}
+
+	/// Runs one tick with `input`. Nothing happens once the Agent has
+	/// escaped or run out of time.
+	pub fn step(&mut self, input: TickInput) -> TickEvents {
+		let mut events = TickEvents::default();
+		if self.escaped || self.timed_out {
+			return events;
+		}
+		self.tick += 1;
+
+		self.camera
+			.handle_left_button_displacement(&input.look_vector());
+		let movement = self.camera.move_dir(
+			input.actions.contains(Actions::FORWARD),
+			input.actions.contains(Actions::BACKWARD),
+			input.actions.contains(Actions::RIGHT),
+			input.actions.contains(Actions::LEFT),
+		);
+		if let Some(mut dir) = movement {
+			let position = section_at(self.camera.eye());
+			if position != self.position {
+				self.enter(position);
+				events.entered_section = Some(position);
+			}
+
+			let layout = &self.sections[&position].layout;
+			if layout
+				.landmark
+				.as_ref()
+				.map_or(false, |landmark| landmark.flooded)
+			{
+				dir *= WADING_SPEED;
+			}
+			let mut next_camera_eye = self.camera.eye() + dir;
+			for wall in layout.walls.iter().chain(&self.blockers(&position)) {
+				wall.push_back(&mut next_camera_eye);
+			}
+			self.camera.set_eye(next_camera_eye);
+			events.movement = Some(dir);
+		}
+
+		let eye = *self.camera.eye();
+		let partner = self.partner.map(|(x, z)| Point3::new(x, eye.y, z));
+		let mut noticing = Vec::new();
+		for section in self.sections.values_mut() {
+			for monster in section.monsters.iter_mut() {
+				let was_near = monster.monster.is_near();
+				monster.distance = distance(&eye, &monster.position).min(
+					partner.map_or(f32::INFINITY, |partner| {
+						distance(&partner, &monster.position)
+					}),
+				);
+				monster.visible = monster.monster.update(monster.distance, self.tick);
+				if !was_near && monster.monster.is_near() {
+					self.monster_encounters += 1;
+					events.noticed = true;
+					noticing.push(monster.position);
+				}
+			}
+		}
+
+		for door in self.doors.values_mut() {
+			door.tick();
+		}
+		// The doors by a monster slam shut as it notices the Agent.
+		if !noticing.is_empty() {
+			for section in self.sections.values() {
+				for door in section.layout.hinged_doors.iter() {
+					let center = Point3::from(
+						section.layout.offset.vector
+							+ door.slot.transformation().translation.vector,
+					);
+					if noticing
+						.iter()
+						.any(|monster| distance(&center, monster) < SLAM_DISTANCE)
+					{
+						// Doors that are not kept are shut.
+						self.doors.remove(&door.id);
+					}
+				}
+			}
+		}
+
+		if input.actions.contains(Actions::USE) {
+			if let Some(id) = self.nearby_hinged_door() {
+				self.doors.entry(id).or_default().open();
+				events.opened = Some(id);
+			}
+			match self.reachable_item() {
+				Some((_, ItemKind::Lock)) if self.has_key() => {
+					self.escaped = true;
+					events.escaped = true;
+				}
+				Some((_, ItemKind::Lock)) | None => {}
+				Some((cell, kind)) => {
+					self.collect((self.position, cell), kind);
+					if let Some(countdown) = &mut self.countdown {
+						countdown.add_bonus(kind);
+					}
+					events.collected = Some((cell, kind));
+				}
+			}
+		}
+
+		if !self.escaped && self.countdown.map_or(false, |c| c.is_over(self.tick)) {
+			self.timed_out = true;
+			events.timed_out = true;
+		}
+
+		events
+	}
+
+	/// Picks up the item in `cell` of `section` for the other Agent of a
+	/// co-op run, who got to it first. What the item is comes from the seed
+	/// rather than from the other Agent. Returns whether it was still there.
+	pub fn collect_shared(&mut self, section: (i64, i64), cell: Position) -> bool {
+		if self.collected_items.contains(&(section, cell)) {
+			return false;
+		}
+		let layout = SectionLayout::generate_with_params(self.seed, &self.params, section, true);
+		match layout.items.iter().find(|(pos, _)| *pos == cell) {
+			Some((_, ItemKind::Lock)) | None => false,
+			Some((_, kind)) => {
+				self.collect((section, cell), *kind);
+				true
+			}
+		}
+	}
+
+	/// Starts opening the door `id` for the other Agent of a co-op run.
+	/// Returns whether there is such a door and it was shut.
+	pub fn open_shared(&mut self, id: DoorId) -> bool {
+		if !self.door(&id).is_shut() {
+			return false;
+		}
+		let layout = SectionLayout::generate_with_params(self.seed, &self.params, id.0, false);
+		if !layout.hinged_doors.iter().any(|door| door.id == id) {
+			return false;
+		}
+		self.doors.entry(id).or_default().open();
+		true
+	}
+
+	/// Takes the item `kind` at `item` out of the world.
+	fn collect(&mut self, item: ItemId, kind: ItemKind) {
+		match kind {
+			ItemKind::Key(tier) => {
+				self.keys = self.keys.max(tier + 1);
+				if self.has_key() && self.key_tick.is_none() {
+					self.key_tick = Some(self.tick);
+				}
+			}
+			ItemKind::Coin => self.coins += 1,
+			ItemKind::Lock => {}
+		}
+		self.collected_items.insert(item);
+		let (section, cell) = item;
+		if let Some(section) = self.sections.get_mut(&section) {
+			section.layout.items.retain(|(pos, _)| *pos != cell);
+		}
+	}
+
+	/// Moves the simulated sections to the ones around `position`.
+	fn enter(&mut self, position: (i64, i64)) {
+		self.position = position;
+		self.visited_sections.insert(position);
+		self.sections
+			.retain(|p, _| manhattan(*p, position) <= SIMULATED_RANGE);
+		for p in diamond(position, SIMULATED_RANGE) {
+			if !self.sections.contains_key(&p) {
+				let mut layout =
+					SectionLayout::generate_with_params(self.seed, &self.params, p, true);
+				layout
+					.items
+					.retain(|(cell, _)| !self.collected_items.contains(&(p, *cell)));
+				let monsters = layout
+					.monsters
+					.iter()
+					.map(|slot| SimulatedMonster {
+						position: Point3::from(
+							layout.offset.vector + slot.monster_transformation().translation.vector,
+						),
+						monster: Monster::default(),
+						distance: f32::INFINITY,
+						visible: false,
+					})
+					.collect();
+				self.sections.insert(p, SimulatedSection {
+					layout,
+					monsters,
+				});
+			}
+		}
+	}
+
+	/// The item close enough to the Agent to be used, if any.
+	pub fn nearby_item(&self) -> Option<ItemKind> {
+		self.reachable_item().map(|(_, kind)| kind)
+	}
+
+	/// The cell and kind of the item close enough to the Agent to be used.
+	/// The resting place of the item is used rather than where it floats,
+	/// which depends on when its section was loaded.
+	fn reachable_item(&self) -> Option<(Position, ItemKind)> {
+		let layout = &self.sections.get(&self.position)?.layout;
+		layout.items.iter().copied().find(|(cell, _)| {
+			let position = Point3::from(layout.item_translation(*cell).vector);
+			distance(self.camera.eye(), &position) < MAZE_SIZE_HALF
+		})
+	}
+
+	/// Id of the message on the note by the fallen Agent, if ours is close
+	/// enough to read it.
+	pub fn nearby_note(&self) -> Option<&str> {
+		let layout = &self.sections.get(&self.position)?.layout;
+		let landmark = layout.landmark.as_ref()?;
+		let position = Point3::from(layout.item_translation(landmark.body?).vector);
+		if distance(self.camera.eye(), &position) < MAZE_SIZE_HALF {
+			landmark.note.as_deref()
+		} else {
+			None
+		}
+	}
+
+	/// The landmark laid out in `section`, if it is simulated and one.
+	pub fn landmark(&self, section: &(i64, i64)) -> Option<&Landmark> {
+		self.sections.get(section)?.layout.landmark.as_deref()
+	}
+
+	/// Whether the way out of `cell` in `direction` is open, not through a
+	/// locked door or a door that is not open yet, and leads into a simulated
+	/// section.
+	pub fn is_open(&self, cell: WorldCell, direction: Direction) -> bool {
+		self.is_passable(cell, direction) && !self.is_shut(cell, direction)
+	}
+
+	/// Like [`is_open`](Self::is_open), but also when the way is through a
+	/// door that swings open, which using it would open.
+	pub fn is_passable(&self, cell: WorldCell, direction: Direction) -> bool {
+		self.sections.contains_key(&cell.step(direction).section())
+			&& !self.is_locked(cell, direction)
+			&& self.sections.get(&cell.section()).map_or(false, |section| {
+				is_open(
+					&section.layout.map,
+					&section.layout.openings,
+					cell.position(),
+					direction,
+				)
+			})
+	}
+
+	/// Whether the way out of `cell` in `direction` is through a door the
+	/// Agent does not have the key to.
+	pub fn is_locked(&self, cell: WorldCell, direction: Direction) -> bool {
+		self.locked_door(cell, direction).is_some()
+	}
+
+	/// Whether the way out of `cell` in `direction` is through a door that
+	/// swings open and is not open yet.
+	pub fn is_shut(&self, cell: WorldCell, direction: Direction) -> bool {
+		self.sections
+			.get(&cell.section())
+			.and_then(|section| section.layout.hinged_door(cell.position(), direction))
+			.map_or(false, |door| !self.door(&door.id).is_open())
+	}
+
+	/// Walls that come and go in `section`: locked doors the Agent has no
+	/// key to, and doors that are not open.
+	fn blockers(&self, section: &(i64, i64)) -> Vec<Wall> {
+		let layout = match self.sections.get(section) {
+			Some(section) => &section.layout,
+			None => return Vec::new(),
+		};
+		let locked = layout
+			.doors
+			.iter()
+			.filter(|door| door.tier >= self.keys)
+			.map(|door| door.slot);
+		let shut = layout
+			.hinged_doors
+			.iter()
+			.filter(|door| !self.door(&door.id).is_open())
+			.map(|door| door.slot);
+		locked
+			.chain(shut)
+			.map(|slot| layout.wall_at(slot))
+			.collect()
+	}
+
+	/// The state of the door that swings open `id`.
+	pub fn door(&self, id: &DoorId) -> DoorState {
+		self.doors.get(id).copied().unwrap_or_default()
+	}
+
+	/// Tier of the locked door the Agent is up against, if any.
+	pub fn nearby_door(&self) -> Option<usize> {
+		self.door_ahead(|cell, direction| self.locked_door(cell, direction))
+	}
+
+	/// The shut door that swings open that the Agent is up against, if any.
+	pub fn nearby_hinged_door(&self) -> Option<DoorId> {
+		self.door_ahead(|cell, direction| {
+			let layout = &self.sections.get(&cell.section())?.layout;
+			let door = layout.hinged_door(cell.position(), direction)?;
+			Some(door.id).filter(|id| self.door(id).is_shut())
+		})
+	}
+
+	/// The first door found by `door` on the ways out of the Agent's cell
+	/// that the Agent is up against.
+	fn door_ahead<T, F>(&self, door: F) -> Option<T>
+	where
+		F: Fn(WorldCell, Direction) -> Option<T>,
+	{
+		let eye = self.camera.eye();
+		let cell = WorldCell::containing(eye);
+		let center = cell.center();
+		DIRECTIONS.iter().find_map(|direction| {
+			let (dx, dz) = match direction {
+				Direction::Up => (-1.0, 0.0),
+				Direction::Left => (0.0, 1.0),
+				Direction::Right => (0.0, -1.0),
+				Direction::Down => (1.0, 0.0),
+			};
+			let toward = (eye.x - center.x) * dx + (eye.z - center.z) * dz;
+			door(cell, *direction).filter(|_| toward > MAZE_SIZE_HALF / 2.0)
+		})
+	}
+
+	fn locked_door(&self, cell: WorldCell, direction: Direction) -> Option<usize> {
+		let layout = &self.sections.get(&cell.section())?.layout;
+		let door = layout.door(cell.position(), direction)?;
+		Some(door.tier).filter(|tier| *tier >= self.keys)
+	}
+
+	/// The monsters of a section, in the order of its layout. Sections that
+	/// are not simulated have none.
+	pub fn monsters(&self, section: &(i64, i64)) -> &[SimulatedMonster] {
+		self.sections
+			.get(section)
+			.map_or(&[], |section| &section.monsters[..])
+	}
+
+	pub fn set_partner(&mut self, partner: Option<(f32, f32)>) {
+		self.partner = partner;
+	}
+
+	#[inline]
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	#[inline]
+	pub fn params(&self) -> &WorldParams {
+		&self.params
+	}
+
+	#[inline]
+	pub fn tick(&self) -> u64 {
+		self.tick
+	}
+
+	#[inline]
+	pub fn camera(&self) -> &FirstPerson {
+		&self.camera
+	}
+
+	#[inline]
+	pub fn camera_mut(&mut self) -> &mut FirstPerson {
+		&mut self.camera
+	}
+
+	/// The section the Agent is in.
+	#[inline]
+	pub fn position(&self) -> (i64, i64) {
+		self.position
+	}
+
+	/// Whether the Agent has the key to the lock.
+	#[inline]
+	pub fn has_key(&self) -> bool {
+		self.keys > self.params.locked_rings
+	}
+
+	#[inline]
+	pub fn keys(&self) -> usize {
+		self.keys
+	}
+
+	/// The items that have been collected.
+	#[inline]
+	pub fn collected_items(&self) -> &HashSet<ItemId> {
+		&self.collected_items
+	}
+
+	#[inline]
+	pub fn coins(&self) -> usize {
+		self.coins
+	}
+
+	#[inline]
+	pub fn has_escaped(&self) -> bool {
+		self.escaped
+	}
+
+	pub fn is_timed(&self) -> bool {
+		self.countdown.is_some()
+	}
+
+	/// Ticks left before the countdown runs out, if the run is timed.
+	pub fn remaining_ticks(&self) -> Option<u64> {
+		self.countdown
+			.map(|countdown| countdown.remaining(self.tick))
+	}
+
+	pub fn has_timed_out(&self) -> bool {
+		self.timed_out
+	}
+
+	pub fn stats(&self) -> RunStats {
+		RunStats {
+			seed: self.seed,
+			ticks: self.tick,
+			escaped: self.escaped,
+			key_tick: self.key_tick,
+			keys: self.keys,
+			coins: self.coins(),
+			sections_visited: self.visited_sections.len(),
+			monster_encounters: self.monster_encounters,
+			remaining_ticks: self.remaining_ticks(),
+			timed_out: self.timed_out,
+		}
+	}
+
+	/// Hash of the state that depends on the input, not on rendering or
+	/// timing.
+	pub fn state_hash(&self) -> u64 {
+		let mut hasher = StateHasher::new();
+		hasher.write_u64(self.seed);
+		hasher.write_u64(self.tick);
+		for coordinate in self.camera.eye().iter() {
+			hasher.write_f32(*coordinate);
+		}
+		let (yaw, pitch) = self.camera.look_angles();
+		hasher.write_f32(yaw);
+		hasher.write_f32(pitch);
+		hasher.write_i64(self.position.0);
+		hasher.write_i64(self.position.1);
+		hasher.write_u64(self.keys as u64);
+		let mut collected_items: Vec<_> = self.collected_items.iter().collect();
+		collected_items.sort_by_key(|((row, col), cell)| (*row, *col, cell.0, cell.1));
+		for ((row, col), cell) in collected_items {
+			hasher.write_i64(*row);
+			hasher.write_i64(*col);
+			hasher.write_u64(cell.0 as u64);
+			hasher.write_u64(cell.1 as u64);
+		}
+		let mut doors: Vec<_> = self.doors.iter().collect();
+		doors.sort_by_key(|(((row, col), direction), _)| (*row, *col, *direction as u8));
+		for (((row, col), direction), door) in doors {
+			hasher.write_i64(*row);
+			hasher.write_i64(*col);
+			hasher.write_u64(*direction as u64);
+			hasher.write_f32(door.openness());
+		}
+		if let Some(countdown) = &self.countdown {
+			hasher.write_u64(countdown.deadline());
+		}
+		hasher.finish()
+	}
+}
+
+/// The section that `point` in the world is in.
+fn section_at(point: &Point3<f32>) -> (i64, i64) {
+	(
+		(-point.z / MAZE_SIZE / ROOM_SIZE as f32).round() as i64,
+		(point.x / MAZE_SIZE / ROOM_SIZE as f32).round() as i64,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use rand::rngs::StdRng;
+
+	use super::{
+		super::{
+			super::{map::ROOM_CENTER, rng::rand_for_key},
+			bot::{Bot, FrontierExplorer},
+			input::TICKS_PER_SECOND,
+			section::WallSlot,
+		},
+		*,
+	};
+
+	#[test]
+	fn walls_stop_the_agent() {
+		let mut simulation = Simulation::new(3);
+		let mut cell = WorldCell::containing(simulation.camera().eye());
+		while simulation.is_open(cell, Direction::Right) {
+			cell = cell.step(Direction::Right);
+		}
+
+		let forward = TickInput {
+			actions: Actions::FORWARD,
+			look: (0, 0),
+		};
+		for _ in 0..1000 {
+			simulation.step(forward);
+		}
+		assert_eq!(WorldCell::containing(simulation.camera().eye()), cell);
+	}
+
+	#[test]
+	fn same_input_reaches_same_state() {
+		let mut simulation = Simulation::new(5);
+		let mut bot = FrontierExplorer::default();
+		let ticks: Vec<_> = (0..2000)
+			.map(|_| {
+				let input = bot.next_input(&simulation);
+				simulation.step(input);
+				input
+			})
+			.collect();
+
+		let mut replayed = Simulation::new(5);
+		for input in ticks {
+			replayed.step(input);
+		}
+		assert_eq!(replayed.state_hash(), simulation.state_hash());
+		assert_eq!(replayed.stats(), simulation.stats());
+	}
+
+	#[test]
+	fn timed_runs_end_when_time_runs_out() {
+		let mut simulation = Simulation::timed(3);
+		let deadline = simulation.remaining_ticks().unwrap();
+		for _ in 1..deadline {
+			assert!(!simulation.step(TickInput::default()).timed_out);
+		}
+		assert!(simulation.step(TickInput::default()).timed_out);
+		simulation.step(TickInput::default());
+		assert_eq!(simulation.tick(), deadline);
+		assert!(simulation.stats().timed_out);
+		assert_eq!(simulation.stats().remaining_ticks, Some(0));
+		assert_eq!(Simulation::new(3).stats().remaining_ticks, None);
+	}
+
+	#[test]
+	fn doors_open_when_used_and_slam_shut_near_monsters() {
+		let mut simulation = Simulation::new(3);
+		let (section, door) = simulation
+			.sections
+			.iter()
+			.find_map(|(p, section)| Some((*p, *section.layout.hinged_doors.first()?)))
+			.expect("some door is simulated");
+		let (position, direction, toward) = match door.slot {
+			WallSlot::Above(col) => (Position(0, col), Direction::Up, (-1.0, 0.0)),
+			WallSlot::Left(row) => (Position(row, 0), Direction::Left, (0.0, 1.0)),
+			WallSlot::Right(pos) => (pos, Direction::Right, (0.0, -1.0)),
+			WallSlot::Below(pos) => (pos, Direction::Down, (1.0, 0.0)),
+		};
+		let cell = WorldCell::new(section, position);
+		assert!(simulation.is_shut(cell, direction));
+		assert!(!simulation.is_open(cell, direction));
+		let center = cell.center();
+		let reach = 0.75 * MAZE_SIZE_HALF;
+		simulation.camera_mut().set_eye(Point3::new(
+			center.x + toward.0 * reach,
+			0.25,
+			center.z + toward.1 * reach,
+		));
+		assert_eq!(simulation.nearby_hinged_door(), Some(door.id));
+
+		let interact = TickInput {
+			actions: Actions::USE,
+			look: (0, 0),
+		};
+		simulation.step(interact);
+		assert_eq!(simulation.nearby_hinged_door(), None);
+		for _ in 0..TICKS_PER_SECOND as usize {
+			simulation.step(interact);
+		}
+		assert!(simulation.door(&door.id).is_open());
+		assert!(!simulation.is_shut(cell, direction));
+		assert_eq!(
+			simulation.is_open(cell, direction),
+			simulation.is_passable(cell, direction)
+		);
+
+		let layout = &simulation.sections[&section].layout;
+		let position = layout.offset.vector + door.slot.transformation().translation.vector;
+		let monsters = &mut simulation.sections.get_mut(&section).unwrap().monsters;
+		monsters.push(SimulatedMonster {
+			position: Point3::from(position),
+			monster: Monster::default(),
+			distance: f32::INFINITY,
+			visible: false,
+		});
+		simulation.step(TickInput::default());
+		assert!(simulation.door(&door.id).is_shut());
+		assert_eq!(simulation.nearby_hinged_door(), Some(door.id));
+	}
+
+	#[test]
+	fn partners_open_doors() {
+		let mut simulation = Simulation::new(3);
+		let (section, layout) = simulation
+			.sections
+			.iter()
+			.map(|(p, section)| (*p, &section.layout))
+			.find(|(_, layout)| !layout.hinged_doors.is_empty())
+			.expect("some door is simulated");
+		let door = layout.hinged_doors[0];
+		let doorless = DIRECTIONS
+			.iter()
+			.map(|direction| (section, *direction))
+			.find(|id| layout.hinged_doors.iter().all(|door| door.id != *id))
+			.unwrap();
+
+		assert!(simulation.open_shared(door.id));
+		assert!(!simulation.open_shared(door.id));
+		assert!(!simulation.door(&door.id).is_shut());
+		assert!(!simulation.open_shared(doorless));
+		assert!(simulation.door(&doorless).is_shut());
+	}
+
+	#[test]
+	fn partners_share_pickups_and_monsters() {
+		let mut simulation = Simulation::new(3);
+		let (cell, kind) = simulation.sections[&(0, 1)].layout.items[0];
+		assert_eq!(kind, ItemKind::Coin);
+		assert!(simulation.collect_shared((0, 1), cell));
+		assert!(!simulation.collect_shared((0, 1), cell));
+		let params = WorldParams::default();
+		let key = rand_for_key::<StdRng>(3, &params, 0);
+		let layout = SectionLayout::generate_with_params(3, &params, key, true);
+		let (key_cell, _) = layout.items[0];
+		assert!(simulation.collect_shared(key, key_cell));
+		assert_eq!((simulation.keys(), simulation.coins()), (1, 1));
+		assert!(simulation.sections[&(0, 1)].layout.items.is_empty());
+
+		// Cells without items, and the lock, can not be picked up.
+		let empty = (0..ROOM_SIZE)
+			.flat_map(|row| (0..ROOM_SIZE).map(move |col| Position(row, col)))
+			.find(|cell| layout.items.iter().all(|(pos, _)| pos != cell))
+			.unwrap();
+		assert!(!simulation.collect_shared(key, empty));
+		assert!(!simulation.collect_shared((0, 0), Position(ROOM_CENTER, ROOM_CENTER)));
+		assert_eq!((simulation.keys(), simulation.coins()), (1, 1));
+
+		let (section, position) = simulation
+			.sections
+			.iter()
+			.find_map(|(p, section)| Some((*p, section.monsters.first()?.position)))
+			.expect("some monster is simulated");
+		simulation.step(TickInput::default());
+		assert!(simulation.monsters(&section)[0].distance > 1.0);
+		simulation.set_partner(Some((position.x, position.z)));
+		simulation.step(TickInput::default());
+		assert!(simulation.monsters(&section)[0].distance < 1.0);
+	}
+}
@@ -11,22 +11,75 @@ use kiss3d::{
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use super::{InnerGameState, MouseAction, MouseButtons};
+use super::{
+	super::{
+		daily::Date,
+		dialogue::{dialogue, Facts, Mode, INTRO},
+		locale::tr,
+	},
+	race::Race,
+	InnerGameState,
+	MouseAction,
+	MouseButtons,
+};
 
 pub struct StoryState {
 	ui_ids: UiIds,
+	pages: Vec<String>,
 	page: usize,
 	page_time: Instant,
 	space_action: MouseAction,
+	run: Run,
+}
+
+/// The run to start once the story has been told.
+#[derive(Copy, Clone)]
+enum Run {
+	New,
+	Daily(Date),
+	Timed,
+	Race,
+}
+
+impl Run {
+	fn mode(&self) -> Mode {
+		match self {
+			Self::New => Mode::Normal,
+			Self::Daily(_) => Mode::Daily,
+			Self::Timed => Mode::Timed,
+			Self::Race => Mode::Race,
+		}
+	}
 }
 
 impl StoryState {
 	pub fn new(window: &mut Window) -> Self {
+		Self::before(window, Run::New)
+	}
+
+	/// The story before the Daily Maze of `date`.
+	pub fn daily(window: &mut Window, date: Date) -> Self {
+		Self::before(window, Run::Daily(date))
+	}
+
+	/// The story before a timed run.
+	pub fn timed(window: &mut Window) -> Self {
+		Self::before(window, Run::Timed)
+	}
+
+	/// The story before the first turn of a race.
+	pub fn race(window: &mut Window) -> Self {
+		Self::before(window, Run::Race)
+	}
+
+	fn before(window: &mut Window, run: Run) -> Self {
 		Self {
 			ui_ids: UiIds::new(window.conrod_ui_mut().widget_id_generator()),
-			page: 6,
+			pages: dialogue().tell(INTRO, &Facts::before(run.mode())),
+			page: 0,
 			page_time: Instant::now(),
 			space_action: MouseAction::Free,
+			run,
 		}
 	}
 }
@@ -41,28 +94,13 @@ impl InnerGameState for StoryState {
 			let mut ui = window.conrod_ui_mut().set_widgets();
 			let time = self.page_time.elapsed().as_secs_f32() * 0.5;
 
-			widget::Text::new(match self.page {
-				6 => "Wake up Agent!",
-				5 => "I'm terribly sorry to tell you this, but...",
-				4 => "You're in the maze",
-				3 => "You know the drill.\nFind the key and get back to base as soon as possible.",
-				2 => {
-					"As you know, prolonged exposure usually doesn't end well.\nLuckily, the key \
-					 should be in a nearby section."
-				}
-				1 => {
-					"If you find any coins, take 'em with you.\nBut remember, coins aren't worth \
-					 anything if don't\nmake it back."
-				}
-				0 => "Good luck!",
-				_ => unreachable!(),
-			})
-			.font_size(24)
-			.color(Color::Rgba(1.0, 1.0, 1.0, time.clamp(0.0, 1.0)))
-			.middle()
-			.set(self.ui_ids.story_text, &mut ui);
+			widget::Text::new(self.pages.get(self.page).map_or("", String::as_str))
+				.font_size(24)
+				.color(Color::Rgba(1.0, 1.0, 1.0, time.clamp(0.0, 1.0)))
+				.middle()
+				.set(self.ui_ids.story_text, &mut ui);
 
-			widget::Text::new("LMB or Space to continue...")
+			widget::Text::new(&tr("story-continue"))
 				.font_size(20)
 				.rgba(1.0, 1.0, 1.0, (time * 1.0 - 2.0).clamp(0.0, 1.0))
 				.bottom_right_with_margin(50.0)
@@ -73,13 +111,16 @@ impl InnerGameState for StoryState {
 		self.space_action = self.space_action.step().update(&window.get_key(Key::Space));
 
 		if mouse_buttons.lmb == MouseAction::Pressed || self.space_action == MouseAction::Pressed {
-			if self.page == 0 {
-				Some(Box::new(super::PlayingState::new(
-					window,
-					StdRng::from_entropy().gen(),
-				)))
+			if self.page + 1 >= self.pages.len() {
+				let seed = StdRng::from_entropy().gen();
+				Some(Box::new(match self.run {
+					Run::New => super::PlayingState::new(window, seed),
+					Run::Daily(date) => super::PlayingState::daily(window, date),
+					Run::Timed => super::PlayingState::timed(window, seed),
+					Run::Race => super::PlayingState::race(window, seed, Race::First),
+				}))
 			} else {
-				self.page -= 1;
+				self.page += 1;
 				self.page_time = Instant::now();
 				None
 			}
@@ -0,0 +1,510 @@
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	f32,
+};
+
+use kiss3d::nalgebra::{Point3, Vector2};
+
+use super::{
+	super::map::{Direction, Map, Position, DIRECTIONS, ROOM_CENTER, ROOM_SIZE},
+	section::MAZE_SIZE,
+	section_generator::manhattan,
+};
+
+/// A cell anywhere in the world, counted in cells from the first cell of
+/// section `(0, 0)`. Rows of a section run along `x` and its columns along
+/// `z`, towards negative world z.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WorldCell {
+	pub x: i64,
+	pub z: i64,
+}
+
+impl WorldCell {
+	pub fn new(section: (i64, i64), position: Position) -> Self {
+		Self {
+			x: section.1 * ROOM_SIZE as i64 + position.0 as i64,
+			z: section.0 * ROOM_SIZE as i64 + position.1 as i64,
+		}
+	}
+
+	/// The cell that `point` in the world is in.
+	pub fn containing(point: &Point3<f32>) -> Self {
+		let offset = ROOM_CENTER as f32;
+		Self {
+			x: (point.x / MAZE_SIZE + offset).round() as i64,
+			z: (-point.z / MAZE_SIZE + offset).round() as i64,
+		}
+	}
+
+	/// The middle of the cell, on the floor.
+	pub fn center(&self) -> Point3<f32> {
+		let offset = ROOM_CENTER as f32;
+		Point3::new(
+			(self.x as f32 - offset) * MAZE_SIZE,
+			0.0,
+			(offset - self.z as f32) * MAZE_SIZE,
+		)
+	}
+
+	pub fn section(&self) -> (i64, i64) {
+		(
+			self.z.div_euclid(ROOM_SIZE as i64),
+			self.x.div_euclid(ROOM_SIZE as i64),
+		)
+	}
+
+	pub fn position(&self) -> Position {
+		Position(
+			self.x.rem_euclid(ROOM_SIZE as i64) as usize,
+			self.z.rem_euclid(ROOM_SIZE as i64) as usize,
+		)
+	}
+
+	/// The neighbouring cell in `direction`.
+	pub fn step(&self, direction: Direction) -> Self {
+		let (x, z) = match direction {
+			Direction::Up => (self.x - 1, self.z),
+			Direction::Left => (self.x, self.z - 1),
+			Direction::Right => (self.x, self.z + 1),
+			Direction::Down => (self.x + 1, self.z),
+		};
+		Self {
+			x,
+			z,
+		}
+	}
+
+	/// End points of the side of this cell facing `direction`, in cells.
+	fn side(&self, direction: Direction) -> (Vector2<f32>, Vector2<f32>) {
+		let (x, z) = (self.x as f32, self.z as f32);
+		match direction {
+			Direction::Up => (
+				Vector2::new(x - 0.5, z - 0.5),
+				Vector2::new(x - 0.5, z + 0.5),
+			),
+			Direction::Left => (
+				Vector2::new(x - 0.5, z - 0.5),
+				Vector2::new(x + 0.5, z - 0.5),
+			),
+			Direction::Right => (
+				Vector2::new(x - 0.5, z + 0.5),
+				Vector2::new(x + 0.5, z + 0.5),
+			),
+			Direction::Down => (
+				Vector2::new(x + 0.5, z - 0.5),
+				Vector2::new(x + 0.5, z + 0.5),
+			),
+		}
+	}
+}
+
+/// The part of the world the camera can see from above, as a point and the
+/// angles to either side of the view direction.
+pub struct ViewCone {
+	/// Position of the camera, in cells.
+	origin: Vector2<f32>,
+	direction: Vector2<f32>,
+	half_angle: f32,
+}
+
+impl ViewCone {
+	/// Creates a view cone in cells, with `direction` already in cells.
+	pub fn new(origin: Vector2<f32>, direction: Vector2<f32>, half_angle: f32) -> Self {
+		Self {
+			origin,
+			direction: direction.normalize(),
+			half_angle,
+		}
+	}
+
+	/// The view cone of a camera at `eye` looking at `at`, with a vertical
+	/// field of view of `fovy` and an aspect ratio of `aspect`. Looking up or
+	/// down widens the cone, since the corners of the view reach further to
+	/// the sides.
+	pub fn for_camera(eye: &Point3<f32>, at: &Point3<f32>, fovy: f32, aspect: f32) -> Self {
+		let offset = ROOM_CENTER as f32;
+		let origin = Vector2::new(eye.x / MAZE_SIZE + offset, -eye.z / MAZE_SIZE + offset);
+		let look = (at - eye).normalize();
+		let direction = Vector2::new(look.x, -look.z);
+		let pitch = look.y.abs().min(1.0).asin();
+		let tan_v = (fovy / 2.0).tan();
+		let tan_h = tan_v * aspect;
+		let forward = pitch.cos() - pitch.sin() * tan_v;
+		if forward <= 0.0 || direction.norm() < f32::EPSILON {
+			Self::new(origin, Vector2::x(), f32::consts::PI)
+		} else {
+			Self::new(origin, direction, tan_h.atan2(forward))
+		}
+	}
+
+	fn cell(&self) -> WorldCell {
+		WorldCell {
+			x: self.origin.x.round() as i64,
+			z: self.origin.y.round() as i64,
+		}
+	}
+
+	fn angle_to(&self, point: &Vector2<f32>) -> f32 {
+		let v = point - self.origin;
+		self.direction.perp(&v).atan2(self.direction.dot(&v))
+	}
+
+	/// The angles through which the side of `cell` facing `direction` can be
+	/// seen, or `None` if the camera is standing in it.
+	fn portal(&self, cell: &WorldCell, direction: Direction) -> Option<(f32, f32)> {
+		let (a, b) = cell.side(direction);
+		let (v, w) = (a - self.origin, b - self.origin);
+		if v.perp(&w).abs() < 1e-4 {
+			return None;
+		}
+		let (a, b) = (self.angle_to(&a), self.angle_to(&b));
+		let (low, high) = if a < b { (a, b) } else { (b, a) };
+		if high - low > f32::consts::PI {
+			// The side passes behind the camera.
+			Some((high, low + 2.0 * f32::consts::PI))
+		} else {
+			Some((low, high))
+		}
+	}
+}
+
+/// Whether the way out of `position` in `direction` is open, given the map
+/// of its section and the openings in its border walls.
+pub fn is_open(
+	map: &Map,
+	openings: &[Option<usize>; 4],
+	position: Position,
+	direction: Direction,
+) -> bool {
+	let Position(row, col) = position;
+	match direction {
+		Direction::Up if row == 0 => openings[0] == Some(col),
+		Direction::Left if col == 0 => openings[1] == Some(row),
+		Direction::Right if col + 1 == ROOM_SIZE => openings[2] == Some(row),
+		Direction::Down if row + 1 == ROOM_SIZE => openings[3] == Some(col),
+		_ => !map.is(&position, &direction).unwrap_or(true),
+	}
+}
+
+/// The cells that can be seen in `view`, found by flooding out from the
+/// camera's cell through open passages. Every passage narrows the angles
+/// that can be seen through it, so cells around corners are left out.
+/// Sections further than `range` from the camera's are never entered.
+pub fn visible_cells<F>(view: &ViewCone, range: i64, is_open: F) -> HashSet<WorldCell>
+where
+	F: Fn(WorldCell, Direction) -> bool,
+{
+	let start = view.cell();
+	let center = start.section();
+	let full = view.half_angle >= f32::consts::PI;
+
+	let mut seen = HashMap::new();
+	seen.insert(start, (-view.half_angle, view.half_angle));
+	let mut queue = VecDeque::new();
+	queue.push_back((start, -view.half_angle, view.half_angle));
+	while let Some((cell, low, high)) = queue.pop_front() {
+		for direction in DIRECTIONS.iter() {
+			if !is_open(cell, *direction) {
+				continue;
+			}
+			let next = cell.step(*direction);
+			if manhattan(next.section(), center) > range {
+				continue;
+			}
+			let (low, high) = if full {
+				(low, high)
+			} else if let Some(portal) = view.portal(&cell, *direction) {
+				match narrow((low, high), portal) {
+					Some(angles) => angles,
+					None => continue,
+				}
+			} else {
+				(low, high)
+			};
+			let angles = match seen.get(&next) {
+				Some(&(l, h)) if l <= low && high <= h => continue,
+				Some(&(l, h)) => (l.min(low), h.max(high)),
+				None => (low, high),
+			};
+			seen.insert(next, angles);
+			queue.push_back((next, angles.0, angles.1));
+		}
+	}
+	seen.into_keys().collect()
+}
+
+/// Narrows the angles `(low, high)` to the ones that also go through
+/// `portal`, which may reach past a half turn.
+fn narrow((low, high): (f32, f32), portal: (f32, f32)) -> Option<(f32, f32)> {
+	let turn = 2.0 * f32::consts::PI;
+	[portal, (portal.0 - turn, portal.1 - turn)]
+		.iter()
+		.filter_map(|(l, h)| {
+			let (l, h) = (low.max(*l), high.min(*h));
+			if l < h {
+				Some((l, h))
+			} else {
+				None
+			}
+		})
+		.fold(None, |hull, (l, h)| match hull {
+			Some((hl, hh)) => Some((l.min(hl), h.max(hh))),
+			None => Some((l, h)),
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::section::{SectionLayout, WallSlot},
+		*,
+	};
+
+	const CAMERA_HALF_ANGLE: f32 = 0.8;
+
+	struct Section {
+		map: Map,
+		openings: [Option<usize>; 4],
+	}
+
+	impl Section {
+		fn closed() -> Self {
+			Self {
+				map: Map::new(),
+				openings: [None; 4],
+			}
+		}
+
+		fn open(&mut self, position: Position, direction: Direction) -> &mut Self {
+			self.map.set(&position, &direction, false);
+			self
+		}
+	}
+
+	fn cells_visible_from(
+		sections: &HashMap<(i64, i64), Section>,
+		origin: (f32, f32),
+		direction: (f32, f32),
+		half_angle: f32,
+	) -> HashSet<WorldCell> {
+		let view = ViewCone::new(
+			Vector2::new(origin.0, origin.1),
+			Vector2::new(direction.0, direction.1),
+			half_angle,
+		);
+		visible_cells(&view, 1, |cell, direction| {
+			sections.contains_key(&cell.step(direction).section())
+				&& is_open(
+					&sections[&cell.section()].map,
+					&sections[&cell.section()].openings,
+					cell.position(),
+					direction,
+				)
+		})
+	}
+
+	fn cell(x: i64, z: i64) -> WorldCell {
+		WorldCell {
+			x,
+			z,
+		}
+	}
+
+	/// A section with a corridor along the middle row, open at both ends.
+	fn corridor() -> Section {
+		let mut section = Section::closed();
+		for col in 0..ROOM_SIZE - 1 {
+			section.open(Position(ROOM_CENTER, col), Direction::Right);
+		}
+		section.openings[1] = Some(ROOM_CENTER);
+		section.openings[2] = Some(ROOM_CENTER);
+		section
+	}
+
+	#[test]
+	fn world_cells_round_trip() {
+		for section in [(0, 0), (-1, 2), (3, -4)].iter() {
+			for row in 0..ROOM_SIZE {
+				for col in 0..ROOM_SIZE {
+					let cell = WorldCell::new(*section, Position(row, col));
+					assert_eq!(cell.section(), *section);
+					assert_eq!(cell.position(), Position(row, col));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn cells_contain_their_centers() {
+		for section in [(0, 0), (-1, 2), (3, -4)].iter() {
+			let cell = WorldCell::new(*section, Position(1, 3));
+			assert_eq!(WorldCell::containing(&cell.center()), cell);
+		}
+		assert_eq!(
+			WorldCell::containing(&Point3::new(0.0, 0.25, 0.0)),
+			WorldCell::new((0, 0), Position(ROOM_CENTER, ROOM_CENTER))
+		);
+	}
+
+	#[test]
+	fn camera_is_in_its_own_cell() {
+		let center = ROOM_CENTER as f32;
+		let view = ViewCone::for_camera(
+			&Point3::new(0.0, 0.0, 0.0),
+			&Point3::new(0.0, 0.0, -1.0),
+			f32::consts::PI / 4.0,
+			1.0,
+		);
+		assert_eq!(
+			view.cell(),
+			WorldCell::new((0, 0), Position(ROOM_CENTER, ROOM_CENTER))
+		);
+		assert!((view.origin - Vector2::new(center, center)).norm() < 1e-6);
+		assert!(view.direction.y > 0.99);
+
+		let down = ViewCone::for_camera(
+			&Point3::new(0.0, 0.0, 0.0),
+			&Point3::new(0.0, -1.0, -0.01),
+			f32::consts::PI / 4.0,
+			1.0,
+		);
+		assert!(down.half_angle >= f32::consts::PI);
+	}
+
+	#[test]
+	fn sees_down_a_corridor() {
+		let mut sections = HashMap::new();
+		sections.insert((0, 0), corridor());
+		let row = ROOM_CENTER as i64;
+
+		let visible =
+			cells_visible_from(&sections, (row as f32, 0.0), (0.0, 1.0), CAMERA_HALF_ANGLE);
+		let expected: HashSet<_> = (0..ROOM_SIZE as i64).map(|z| cell(row, z)).collect();
+		assert_eq!(visible, expected);
+
+		let visible =
+			cells_visible_from(&sections, (row as f32, 0.0), (0.0, -1.0), CAMERA_HALF_ANGLE);
+		assert_eq!(visible, [cell(row, 0)].iter().copied().collect());
+	}
+
+	#[test]
+	fn walls_block_the_view() {
+		let mut sections = HashMap::new();
+		let mut section = corridor();
+		section
+			.map
+			.set(&Position(ROOM_CENTER, 2), &Direction::Right, true);
+		sections.insert((0, 0), section);
+		let row = ROOM_CENTER as i64;
+
+		let visible =
+			cells_visible_from(&sections, (row as f32, 0.0), (0.0, 1.0), CAMERA_HALF_ANGLE);
+		let expected: HashSet<_> = (0..3).map(|z| cell(row, z)).collect();
+		assert_eq!(visible, expected);
+	}
+
+	#[test]
+	fn corners_hide_what_is_behind_them() {
+		let mut sections = HashMap::new();
+		let mut section = Section::closed();
+		section
+			.open(Position(0, 0), Direction::Right)
+			.open(Position(0, 1), Direction::Right)
+			.open(Position(0, 2), Direction::Down)
+			.open(Position(1, 2), Direction::Down)
+			.open(Position(2, 2), Direction::Down);
+		sections.insert((0, 0), section);
+
+		let visible = cells_visible_from(&sections, (0.0, 0.0), (0.0, 1.0), CAMERA_HALF_ANGLE);
+		let expected: HashSet<_> = [cell(0, 0), cell(0, 1), cell(0, 2), cell(1, 2)]
+			.iter()
+			.copied()
+			.collect();
+		assert_eq!(visible, expected);
+
+		let everything = cells_visible_from(&sections, (0.0, 0.0), (0.0, 1.0), f32::consts::PI);
+		assert_eq!(everything.len(), 6);
+	}
+
+	#[test]
+	fn border_openings_lead_into_neighbours() {
+		let row = ROOM_CENTER as i64;
+		let size = ROOM_SIZE as i64;
+		let mut sections = HashMap::new();
+		sections.insert((0, 0), corridor());
+		sections.insert((1, 0), corridor());
+		sections.insert((2, 0), corridor());
+
+		// Sections further than the range away are never entered.
+		let visible =
+			cells_visible_from(&sections, (row as f32, 0.0), (0.0, 1.0), CAMERA_HALF_ANGLE);
+		let expected: HashSet<_> = (0..2 * size).map(|z| cell(row, z)).collect();
+		assert_eq!(visible, expected);
+
+		sections.get_mut(&(0, 0)).unwrap().openings[2] = None;
+		let visible =
+			cells_visible_from(&sections, (row as f32, 0.0), (0.0, 1.0), CAMERA_HALF_ANGLE);
+		assert!(visible.iter().all(|c| c.section() == (0, 0)));
+	}
+
+	#[test]
+	fn standing_in_a_passage_sees_both_sides() {
+		let mut sections = HashMap::new();
+		sections.insert((0, 0), corridor());
+		let row = ROOM_CENTER as i64;
+
+		let visible =
+			cells_visible_from(&sections, (row as f32, 1.5), (1.0, 0.0), CAMERA_HALF_ANGLE);
+		assert!(visible.contains(&cell(row, 1)));
+		assert!(visible.contains(&cell(row, 2)));
+	}
+
+	#[test]
+	fn is_open_follows_map_and_openings() {
+		let section = corridor();
+		let middle = Position(ROOM_CENTER, 0);
+		assert!(is_open(
+			&section.map,
+			&section.openings,
+			middle,
+			Direction::Left
+		));
+		assert!(is_open(
+			&section.map,
+			&section.openings,
+			middle,
+			Direction::Right
+		));
+		assert!(!is_open(
+			&section.map,
+			&section.openings,
+			middle,
+			Direction::Up
+		));
+		assert!(!is_open(
+			&section.map,
+			&section.openings,
+			Position(0, 0),
+			Direction::Left
+		));
+	}
+
+	#[test]
+	fn is_open_matches_generated_walls() {
+		let layout = SectionLayout::generate(3, (1, -1), false);
+		for row in 0..ROOM_SIZE {
+			for col in 0..ROOM_SIZE {
+				let pos = Position(row, col);
+				assert_eq!(
+					is_open(&layout.map, &layout.openings, pos, Direction::Right),
+					!layout.is_closed(WallSlot::Right(pos))
+				);
+				assert_eq!(
+					is_open(&layout.map, &layout.openings, pos, Direction::Down),
+					!layout.is_closed(WallSlot::Below(pos))
+				);
+			}
+		}
+	}
+}
@@ -9,6 +9,24 @@ extern "C" {
 	pub fn get_cursor_movement() -> JsValue;
 	#[wasm_bindgen(js_namespace = game)]
 	pub fn get_focus() -> bool;
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn load_stats() -> Option<String>;
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn save_stats(stats: &str);
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn coop_open(url: &str);
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn coop_send(message: &str);
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn coop_receive() -> Option<String>;
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn coop_is_closed() -> bool;
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn coop_close();
+	#[wasm_bindgen(js_namespace = game)]
+	pub fn get_language() -> String;
+	#[wasm_bindgen(js_namespace = Date, js_name = now)]
+	pub fn date_now() -> f64;
 }
 
 #[derive(Deserialize)]
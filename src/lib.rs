@@ -1,11 +1,20 @@
 #![feature(hash_drain_filter)]
 
+pub mod atlas;
 mod camera;
-mod game;
+pub mod coop;
+mod daily;
+pub mod dialogue;
+mod effects;
+pub mod game;
 mod js;
+mod locale;
 mod map;
 mod meshes;
+pub mod metrics;
 mod rng;
+mod settings;
+mod stats;
 mod text;
 mod textures;
 
@@ -14,20 +23,31 @@ use wasm_bindgen::prelude::wasm_bindgen;
 
 use self::{
 	game::{GameState, InnerGameState, MenuState},
+	locale::detect_language,
 	meshes::init_meshes,
 	textures::init_textures,
 };
+pub use self::{
+	map::{Generator, GENERATOR_NAMES},
+	rng::WorldParams,
+};
 
 pub const GAME_NAME: &str = "Little Maze";
 
 #[wasm_bindgen(start)]
 pub fn main() {
 	let mut window = Window::new(GAME_NAME);
-	init_textures();
-	init_meshes();
+	init_resources();
+	detect_language();
 
 	let mut menu_state = Box::new(MenuState::new(&mut window));
 	menu_state.init(&mut window);
 	let state = GameState::new(menu_state);
 	window.render_loop(state);
 }
+
+/// Registers the textures, materials, and meshes used by the game.
+pub fn init_resources() {
+	init_textures();
+	init_meshes();
+}
@@ -0,0 +1,149 @@
+# Buttons
+start-new = Start New
+timed-escape = Timed Escape
+daily-maze = Daily Maze
+hot-seat-race = Hot-Seat Race
+co-op = Co-op
+settings = Settings
+exit = Exit
+continue = Continue
+main-menu = Main Menu
+play-again = Play Again
+back = Back
+created-by = Created by
+for-the = for the
+
+# Story
+story-continue = LMB or Space to continue...
+intro-wake-up = Wake up Agent!
+intro-sorry = I'm terribly sorry to tell you this, but...
+intro-in-the-maze = You're in the maze
+intro-the-drill =
+    You know the drill.
+    Find the key and get back to base as soon as possible.
+intro-the-clock =
+    And this time the clock is running.
+    Coins and keys buy you more time.
+intro-the-daily-maze =
+    Every Agent is in the same maze today.
+    Only your first try is recorded, so make it count.
+intro-the-race =
+    Two Agents, one maze.
+    Whoever gets back to base first wins.
+intro-exposure =
+    As you know, prolonged exposure usually doesn't end well.
+    The keys are behind rings of doors around base,
+    each opening the doors of its colour on the way back.
+intro-coins =
+    If you find any coins, take 'em with you.
+    But remember, coins aren't worth anything if don't
+    make it back.
+intro-good-luck = Good luck!
+debrief-out-of-time =
+    You didn't make it back in time Agent.
+    The maze keeps its coins, and you.
+debrief-no-coins = At least you made it back alive Agent
+debrief-a-coin =
+    Glad to have you back Agent!
+    And a coin sure doesn't hurt either.
+debrief-some-coins =
+    Glad to have you back Agent!
+    And these coins sure doesn't hurt either.
+debrief-many-coins =
+    I'm happy to have you back but,
+    you shouldn't risk your life for these coins Agent!
+
+# Radio
+radio-partner =
+    Your partner is in there with you, Agent.
+    Whatever one of you picks up, you both have.
+radio-monster =
+    Something just noticed you, Agent.
+    Whatever you do, don't stop to find out what.
+radio-door-key =
+    That's a door key.
+    Doors of its colour will open for you now.
+radio-key =
+    That's the key to base!
+    Now get back here as fast as you can.
+radio-coin = A coin! Just don't let it slow you down.
+radio-far =
+    You're getting far from base, Agent.
+    The key shouldn't be this far out.
+radio-long =
+    It's been five minutes, Agent.
+    The longer you stay in there, the worse it gets.
+
+# During runs
+action-unlock = Press LMB to unlock and escape
+action-find-key = Come back here once you've found the key to escape
+action-collect-key = Press LMB to collect key
+action-collect-coin = Press LMB to collect coin
+action-door = This door opens with the key of its colour
+action-open-door = Press LMB to open the door
+hud-coins = Coins collected: { $coins }
+hud-keys = Keys found: { $keys } of { $total }
+hud-player = Player { $player }
+partner-escaped = The other Agent has escaped
+partner-left = The other Agent has left
+
+# Landmarks
+landmark-atrium = The Atrium of { $name }
+landmark-flooded = Flooded { $name }
+landmark-shrine = The Shrine of { $name }
+landmark-fallen-agent = { $name }, where an Agent fell
+note-fallen-agent =
+    The note reads: “I kept walking toward the lock, and the maze kept
+    turning me around. If you find this, tell base I tried.”
+
+# After runs
+end-score =
+    Score: { $score }
+    { $coins ->
+        [one] { $coins } coin
+       *[other] { $coins } coins
+    } and { $time } left
+end-first-try = Only the first try of the day counts
+
+# Hot-seat race
+race-handover =
+    Player 1 made it back in { $time }.
+    Player 2, you're up! Move with the arrow keys
+    and beat the ghost of Player 1 back to the lock.
+race-start = Start
+race-again = Race Again
+race-winner = Player { $player } wins!
+race-draw = It's a draw!
+race-time = Player { $player }: { $time }
+race-still-in-maze = still in the maze
+
+# Co-op
+coop-joining = Joining the room...
+coop-unreachable =
+    Could not reach the relay at
+    { $url }
+    ({ $error })
+coop-waiting = Waiting for the other Agent...
+coop-full = The room already has two Agents
+coop-lost = Lost the connection to the relay
+
+# Settings
+setting-toggle = { $name }: { $state ->
+        [on] On
+       *[off] Off
+    }
+effect-screen-shake = Screen shake
+effect-chromatic-aberration = Chromatic aberration
+effect-pixelation = Pixelation
+effect-film-grain = Film grain
+effect-vignette = Vignette
+setting-dithering = Dithering
+setting-view-distance = View distance: { $distance }
+setting-adaptive-view-distance = Adaptive view distance
+setting-language = Language: { $language }
+setting-section-names = Section names: { $names }
+names-of-language = Of the language
+names-english = English
+names-latin = Latin
+names-nordic = Nordic
+names-eldritch = Eldritch
@@ -0,0 +1,417 @@
+//! The part of Fluent (https://projectfluent.org) the game's messages are
+//! written in: messages of text and `{ $variable }` placeables, and select
+//! expressions that pick a variant by number, plural category, or text.
+
+use std::{collections::HashMap, fmt, iter::Peekable, str::Chars};
+
+/// The plural category of a number, as named by the Unicode CLDR.
+pub type PluralRule = fn(i64) -> &'static str;
+
+/// What a variable of a message can be set to.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+	Number(i64),
+	Text(String),
+}
+
+/// The messages of one language.
+pub struct Bundle {
+	messages: HashMap<String, Pattern>,
+	plural: PluralRule,
+}
+
+type Pattern = Vec<Element>;
+
+#[derive(Debug)]
+enum Element {
+	Text(String),
+	Variable(String),
+	Select {
+		selector: String,
+		variants: Vec<(String, Pattern)>,
+		/// Index of the variant used when no other matches.
+		default: usize,
+	},
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ParseError {
+	/// Line of the message that could not be read, counting from one.
+	pub line: usize,
+	pub reason: &'static str,
+}
+
+impl Bundle {
+	/// Reads messages from `source`, picking plural variants by `plural`.
+	/// Each message starts with its id at the start of a line, and goes on
+	/// over the indented lines after it.
+	pub fn parse(source: &str, plural: PluralRule) -> Result<Self, ParseError> {
+		let mut messages = HashMap::new();
+		let mut lines = source.lines().enumerate().peekable();
+		while let Some((i, line)) = lines.next() {
+			let line_number = i + 1;
+			let error = |reason| ParseError {
+				line: line_number,
+				reason,
+			};
+			if line.trim().is_empty() || line.starts_with('#') {
+				continue;
+			}
+			let equals = line.find('=').ok_or_else(|| error("expected a message"))?;
+			let id = line[..equals].trim_end();
+			if !is_identifier(id) {
+				return Err(error("expected a message id"));
+			}
+
+			let mut value = line[equals + 1..].trim().to_string();
+			while let Some((_, next)) = lines.peek() {
+				if !next.starts_with(char::is_whitespace) || next.trim().is_empty() {
+					break;
+				}
+				if !value.is_empty() {
+					value.push('\n');
+				}
+				value.push_str(next.trim());
+				lines.next();
+			}
+
+			let pattern = parse_pattern(&mut value.chars().peekable(), false).map_err(error)?;
+			if messages.insert(id.to_string(), pattern).is_some() {
+				return Err(error("the message is already defined"));
+			}
+		}
+		Ok(Self {
+			messages,
+			plural,
+		})
+	}
+
+	/// The message `id` with its variables set to `args`, if there is such a
+	/// message. Variables without a value are left as they were written.
+	pub fn format(&self, id: &str, args: &[(&str, Value)]) -> Option<String> {
+		let pattern = self.messages.get(id)?;
+		let mut text = String::new();
+		self.write(pattern, args, &mut text);
+		Some(text)
+	}
+
+	fn write(&self, pattern: &[Element], args: &[(&str, Value)], text: &mut String) {
+		let arg = |name: &str| args.iter().find(|(n, _)| *n == name).map(|(_, v)| v);
+		for element in pattern {
+			match element {
+				Element::Text(t) => text.push_str(t),
+				Element::Variable(name) => match arg(name) {
+					Some(Value::Number(n)) => text.push_str(&n.to_string()),
+					Some(Value::Text(t)) => text.push_str(t),
+					None => {
+						text.push_str("{$");
+						text.push_str(name);
+						text.push('}');
+					}
+				},
+				Element::Select {
+					selector,
+					variants,
+					default,
+				} => {
+					let value = arg(selector);
+					// An exact match goes before the plural category.
+					let exact = variants.iter().position(|(key, _)| match value {
+						Some(Value::Number(n)) => *key == n.to_string(),
+						Some(Value::Text(t)) => key == t,
+						None => false,
+					});
+					let category = || match value {
+						Some(Value::Number(n)) => {
+							let category = (self.plural)(*n);
+							variants.iter().position(|(key, _)| key == category)
+						}
+						_ => None,
+					};
+					let variant = exact.or_else(category).unwrap_or(*default);
+					self.write(&variants[variant].1, args, text);
+				}
+			}
+		}
+	}
+
+	#[cfg(test)]
+	fn ids(&self) -> impl Iterator<Item = &str> {
+		self.messages.keys().map(String::as_str)
+	}
+}
+
+fn is_identifier(id: &str) -> bool {
+	id.starts_with(|c: char| c.is_ascii_alphabetic())
+		&& id
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Reads text and placeables up to the end of `chars`, or of the line if
+/// `in_variant`.
+fn parse_pattern(chars: &mut Peekable<Chars>, in_variant: bool) -> Result<Pattern, &'static str> {
+	let mut pattern = Vec::new();
+	let mut text = String::new();
+	while let Some(&c) = chars.peek() {
+		match c {
+			'\n' | '}' if in_variant => break,
+			'{' => {
+				chars.next();
+				if !text.is_empty() {
+					pattern.push(Element::Text(text.split_off(0)));
+				}
+				pattern.push(parse_placeable(chars)?);
+			}
+			'}' => return Err("unexpected }"),
+			_ => {
+				text.push(c);
+				chars.next();
+			}
+		}
+	}
+	if !text.is_empty() {
+		pattern.push(Element::Text(text));
+	}
+	Ok(pattern)
+}
+
+/// Reads what is between `{` and `}`, the first having already been read.
+fn parse_placeable(chars: &mut Peekable<Chars>) -> Result<Element, &'static str> {
+	skip_blank(chars);
+	let element = match chars.next() {
+		Some('$') => {
+			let mut name = String::new();
+			while let Some(c) =
+				chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+			{
+				name.push(c);
+			}
+			if !is_identifier(&name) {
+				return Err("expected a variable name");
+			}
+			skip_blank(chars);
+			if chars.next_if_eq(&'-').is_some() {
+				if chars.next() != Some('>') {
+					return Err("expected ->");
+				}
+				parse_select(name, chars)?
+			} else {
+				Element::Variable(name)
+			}
+		}
+		// String literals let text be written that would otherwise be read
+		// as something else, like `{"{"}`.
+		Some('"') => {
+			let mut text = String::new();
+			loop {
+				match chars.next() {
+					Some('"') => break,
+					Some('\n') | None => return Err("expected \""),
+					Some(c) => text.push(c),
+				}
+			}
+			Element::Text(text)
+		}
+		_ => return Err("expected a variable or a string"),
+	};
+	skip_blank(chars);
+	if chars.next() != Some('}') {
+		return Err("expected }");
+	}
+	Ok(element)
+}
+
+/// Reads the variants of a select expression on `selector`, each on a line
+/// of its own.
+fn parse_select(selector: String, chars: &mut Peekable<Chars>) -> Result<Element, &'static str> {
+	let mut variants = Vec::new();
+	let mut default = None;
+	loop {
+		skip_blank(chars);
+		let is_default = chars.next_if_eq(&'*').is_some();
+		if chars.next_if_eq(&'[').is_none() {
+			if is_default {
+				return Err("expected a variant");
+			}
+			break;
+		}
+		let mut key = String::new();
+		loop {
+			match chars.next() {
+				Some(']') => break,
+				Some('\n') | None => return Err("expected ]"),
+				Some(c) => key.push(c),
+			}
+		}
+		while chars.next_if_eq(&' ').is_some() {}
+		let value = parse_pattern(chars, true)?;
+		if is_default {
+			if default.is_some() {
+				return Err("there is more than one default variant");
+			}
+			default = Some(variants.len());
+		}
+		variants.push((key.trim().to_string(), value));
+	}
+	Ok(Element::Select {
+		selector,
+		variants,
+		default: default.ok_or("expected a default variant")?,
+	})
+}
+
+fn skip_blank(chars: &mut Peekable<Chars>) {
+	while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+impl From<i64> for Value {
+	fn from(n: i64) -> Self {
+		Self::Number(n)
+	}
+}
+
+impl From<u64> for Value {
+	fn from(n: u64) -> Self {
+		Self::Number(n as i64)
+	}
+}
+
+impl From<usize> for Value {
+	fn from(n: usize) -> Self {
+		Self::Number(n as i64)
+	}
+}
+
+impl From<&str> for Value {
+	fn from(text: &str) -> Self {
+		Self::Text(text.to_string())
+	}
+}
+
+impl From<String> for Value {
+	fn from(text: String) -> Self {
+		Self::Text(text)
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: {}", self.line, self.reason)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{super::LANGUAGES, *};
+
+	fn english(n: i64) -> &'static str {
+		if n == 1 {
+			"one"
+		} else {
+			"other"
+		}
+	}
+
+	#[test]
+	fn messages_take_variables() {
+		let bundle = Bundle::parse(
+			"# A comment\nhello = Hello, { $name }!\n\ntwo-lines =\n    First\n    second\n",
+			english,
+		)
+		.unwrap();
+		assert_eq!(
+			bundle.format("hello", &[("name", "Agent".into())]),
+			Some("Hello, Agent!".to_string())
+		);
+		assert_eq!(
+			bundle.format("hello", &[]),
+			Some("Hello, {$name}!".to_string())
+		);
+		assert_eq!(
+			bundle.format("two-lines", &[]),
+			Some("First\nsecond".to_string())
+		);
+		assert_eq!(bundle.format("goodbye", &[]), None);
+	}
+
+	#[test]
+	fn selects_pick_variants() {
+		let bundle = Bundle::parse(
+			"coins = { $coins ->\n    [0] No coins\n    [one] { $coins } coin\n   *[other] { \
+			 $coins } coins\n    } left\nstate = { $state ->\n    [on] On\n   *[off] Off\n    \
+			 }\nbraces = {\"{\"}{ $x }{\"}\"}",
+			english,
+		)
+		.unwrap();
+		let coins = |n: i64| bundle.format("coins", &[("coins", n.into())]).unwrap();
+		assert_eq!(coins(0), "No coins left");
+		assert_eq!(coins(1), "1 coin left");
+		assert_eq!(coins(7), "7 coins left");
+		assert_eq!(
+			bundle.format("state", &[("state", "on".into())]),
+			Some("On".to_string())
+		);
+		assert_eq!(bundle.format("state", &[]), Some("Off".to_string()));
+		assert_eq!(
+			bundle.format("braces", &[("x", 2i64.into())]),
+			Some("{2}".to_string())
+		);
+	}
+
+	#[test]
+	fn errors_point_at_the_message() {
+		let error = |source| Bundle::parse(source, english).err();
+		assert_eq!(
+			error("ok = Fine\nnot a message\n"),
+			Some(ParseError {
+				line: 2,
+				reason: "expected a message",
+			})
+		);
+		assert_eq!(
+			error("ok = Fine\n\nbroken = { $x\n"),
+			Some(ParseError {
+				line: 3,
+				reason: "expected }",
+			})
+		);
+		assert_eq!(
+			error("no-default = { $x ->\n    [one] One\n}\n"),
+			Some(ParseError {
+				line: 1,
+				reason: "expected a default variant",
+			})
+		);
+		assert_eq!(
+			error("twice = One\ntwice = Two\n"),
+			Some(ParseError {
+				line: 2,
+				reason: "the message is already defined",
+			})
+		);
+	}
+
+	#[test]
+	fn translations_have_every_message() {
+		let english = LANGUAGES[0].bundle();
+		for language in LANGUAGES.iter() {
+			let bundle = language.bundle();
+			for id in english.ids() {
+				assert!(
+					bundle.messages.contains_key(id),
+					"{:?} has no {}",
+					language,
+					id
+				);
+			}
+			for id in bundle.ids() {
+				assert!(
+					english.messages.contains_key(id),
+					"{} is not in English",
+					id
+				);
+			}
+		}
+	}
+}
@@ -0,0 +1,256 @@
+//! Text shown to the player, in the language of the settings. Messages are
+//! written in a Fluent file per language, and English fills in for whatever a
+//! translation lacks.
+
+mod fluent;
+
+use std::{cell::RefCell, collections::HashMap};
+
+use kiss3d::{
+	conrod::text::{font, Font},
+	window::Window,
+};
+use serde::Deserialize;
+
+use self::fluent::Bundle;
+pub use self::fluent::Value;
+use super::{
+	settings::{set_settings, settings, Settings},
+	text::{Syllables, RUSSIAN_SYLLABLES, SWEDISH_SYLLABLES},
+};
+
+/// Has the Cyrillic letters the default font lacks.
+const DEJAVU_SANS: &[u8] = include_bytes!("./fonts/DejaVuSans.ttf");
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Language {
+	#[serde(rename = "en")]
+	English,
+	#[serde(rename = "sv")]
+	Swedish,
+	#[serde(rename = "ru")]
+	Russian,
+}
+
+/// All languages there are messages in, English first.
+pub const LANGUAGES: [Language; 3] = [Language::English, Language::Swedish, Language::Russian];
+
+thread_local! {
+	static BUNDLES: HashMap<Language, Bundle> =
+		LANGUAGES.iter().map(|language| (*language, language.bundle())).collect();
+	static FONTS: RefCell<Fonts> = RefCell::default();
+}
+
+/// The language of the settings.
+pub fn language() -> Language {
+	settings().language
+}
+
+/// Sets the language to the one of the system, if there are messages in it.
+pub fn detect_language() {
+	set_settings(Settings {
+		language: Language::detect(),
+		..settings()
+	});
+}
+
+/// The message `id` in the current language.
+pub fn tr(id: &str) -> String {
+	tr_with(id, &[])
+}
+
+/// The message `id` in the current language, with its variables set to
+/// `args`. The id itself if there is no such message.
+pub fn tr_with(id: &str, args: &[(&str, Value)]) -> String {
+	BUNDLES.with(|bundles| {
+		bundles[&language()]
+			.format(id, args)
+			.or_else(|| bundles[&Language::English].format(id, args))
+			.unwrap_or_else(|| id.to_string())
+	})
+}
+
+impl Language {
+	pub fn code(self) -> &'static str {
+		match self {
+			Self::English => "en",
+			Self::Swedish => "sv",
+			Self::Russian => "ru",
+		}
+	}
+
+	/// The name of the language, in itself.
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::English => "English",
+			Self::Swedish => "Svenska",
+			Self::Russian => "Русский",
+		}
+	}
+
+	/// The language of a locale like `sv`, `sv-SE`, or `sv_SE.UTF-8`.
+	pub fn by_locale(locale: &str) -> Option<Self> {
+		let code = locale.split(|c| c == '-' || c == '_' || c == '.').next()?;
+		LANGUAGES
+			.iter()
+			.copied()
+			.find(|language| language.code().eq_ignore_ascii_case(code))
+	}
+
+	/// The language the system is set to, or English if there are no
+	/// messages in it.
+	#[cfg(not(target_arch = "wasm32"))]
+	fn detect() -> Self {
+		["LC_ALL", "LC_MESSAGES", "LANG"]
+			.iter()
+			.filter_map(|name| std::env::var(name).ok())
+			.find(|locale| !locale.is_empty())
+			.and_then(|locale| Self::by_locale(&locale))
+			.unwrap_or(Self::English)
+	}
+
+	/// The language the browser is set to, or English if there are no
+	/// messages in it.
+	#[cfg(target_arch = "wasm32")]
+	fn detect() -> Self {
+		Self::by_locale(&super::js::get_language()).unwrap_or(Self::English)
+	}
+
+	/// The language after this one, going back to the first after the last.
+	pub fn next(self) -> Self {
+		let i = LANGUAGES.iter().position(|l| *l == self).unwrap_or(0);
+		LANGUAGES[(i + 1) % LANGUAGES.len()]
+	}
+
+	/// What section names are made of in this language, if not of the
+	/// English letter pairs.
+	pub fn syllables(self) -> Option<&'static Syllables> {
+		match self {
+			Self::English => None,
+			Self::Swedish => Some(&SWEDISH_SYLLABLES),
+			Self::Russian => Some(&RUSSIAN_SYLLABLES),
+		}
+	}
+
+	/// A font with the letters of this language, if the default one does not
+	/// have them.
+	fn font(self) -> Option<&'static [u8]> {
+		match self {
+			Self::English | Self::Swedish => None,
+			Self::Russian => Some(DEJAVU_SANS),
+		}
+	}
+
+	fn bundle(self) -> Bundle {
+		let (source, plural): (_, fluent::PluralRule) = match self {
+			Self::English => (include_str!("./en.ftl"), one_other),
+			Self::Swedish => (include_str!("./sv.ftl"), one_other),
+			Self::Russian => (include_str!("./ru.ftl"), east_slavic),
+		};
+		Bundle::parse(source, plural)
+			.unwrap_or_else(|e| panic!("the {} messages are invalid, {}", self.code(), e))
+	}
+}
+
+fn one_other(n: i64) -> &'static str {
+	if n == 1 {
+		"one"
+	} else {
+		"other"
+	}
+}
+
+fn east_slavic(n: i64) -> &'static str {
+	let n = n.abs();
+	match (n % 10, n % 100) {
+		(1, r) if r != 11 => "one",
+		(2..=4, r) if !(12..=14).contains(&r) => "few",
+		_ => "many",
+	}
+}
+
+#[derive(Default)]
+struct Fonts {
+	/// The font conrod started out with.
+	default: Option<font::Id>,
+	/// Fonts added for languages the default one does not have the letters
+	/// of.
+	added: HashMap<Language, font::Id>,
+	/// The language whose font is in use.
+	current: Option<Language>,
+}
+
+/// Makes conrod write in a font with the letters of the current language.
+pub fn use_font(window: &mut Window) {
+	let language = language();
+	FONTS.with(|fonts| {
+		let mut fonts = fonts.borrow_mut();
+		if fonts.current == Some(language) {
+			return;
+		}
+		let ui = window.conrod_ui_mut();
+		if fonts.current.is_none() {
+			fonts.default = ui.theme.font_id.or_else(|| ui.fonts.ids().next());
+		}
+		let font_id = match language.font() {
+			Some(bytes) => Some(*fonts.added.entry(language).or_insert_with(|| {
+				ui.fonts
+					.insert(Font::try_from_bytes(bytes).expect("the built-in fonts are valid"))
+			})),
+			None => fonts.default,
+		};
+		ui.theme.font_id = font_id;
+		fonts.current = Some(language);
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn languages_come_from_locales() {
+		assert_eq!(Language::by_locale("sv_SE.UTF-8"), Some(Language::Swedish));
+		assert_eq!(Language::by_locale("ru-RU"), Some(Language::Russian));
+		assert_eq!(Language::by_locale("EN"), Some(Language::English));
+		assert_eq!(Language::by_locale("C"), None);
+		assert_eq!(Language::by_locale(""), None);
+	}
+
+	#[test]
+	fn russian_plurals() {
+		let categories: Vec<_> = [1, 2, 5, 11, 12, 21, 22, 25, 101, 111, 0]
+			.iter()
+			.map(|n| east_slavic(*n))
+			.collect();
+		assert_eq!(categories, [
+			"one", "few", "many", "many", "many", "one", "few", "many", "one", "many", "many"
+		]);
+	}
+
+	#[test]
+	fn coin_counts_agree() {
+		let coins = |language: Language, n: i64| {
+			language
+				.bundle()
+				.format("hud-coins", &[("coins", n.into())])
+				.unwrap()
+		};
+		assert_eq!(coins(Language::English, 1), "Coins collected: 1");
+		assert_eq!(coins(Language::Russian, 1), "Собрана 1 монета");
+		assert_eq!(coins(Language::Russian, 3), "Собрано 3 монеты");
+		assert_eq!(coins(Language::Russian, 11), "Собрано 11 монет");
+		assert_eq!(coins(Language::Russian, 21), "Собрана 21 монета");
+	}
+
+	#[test]
+	fn bundled_font_has_every_letter() {
+		let font = Font::try_from_bytes(DEJAVU_SANS).unwrap();
+		let messages = include_str!("./ru.ftl");
+		let names = RUSSIAN_SYLLABLES.all();
+		let letters = messages.chars().chain(names.flat_map(str::chars));
+		for c in letters.filter(|c| !c.is_whitespace()) {
+			assert_ne!(font.glyph(c).id().0, 0, "{:?} is missing", c);
+		}
+	}
+}
@@ -0,0 +1,154 @@
+# Кнопки
+start-new = Новая игра
+timed-escape = Побег на время
+daily-maze = Лабиринт дня
+hot-seat-race = Гонка по очереди
+co-op = Кооператив
+settings = Настройки
+exit = Выход
+continue = Продолжить
+main-menu = Главное меню
+play-again = Ещё раз
+back = Назад
+created-by = Автор
+for-the = для
+
+# Рассказ
+story-continue = ЛКМ или пробел, чтобы продолжить...
+intro-wake-up = Просыпайтесь, Агент!
+intro-sorry = Мне ужасно жаль говорить вам это, но...
+intro-in-the-maze = Вы в лабиринте
+intro-the-drill =
+    Вы знаете, что делать.
+    Найдите ключ и как можно скорее возвращайтесь на базу.
+intro-the-clock =
+    И на этот раз время идёт.
+    Монеты и ключи дадут вам больше времени.
+intro-the-daily-maze =
+    Сегодня все Агенты в одном лабиринте.
+    Засчитывается только первая попытка, так что не упустите её.
+intro-the-race =
+    Два Агента, один лабиринт.
+    Побеждает тот, кто первым вернётся на базу.
+intro-exposure =
+    Как вы знаете, долгое пребывание здесь обычно плохо кончается.
+    Ключи лежат за кольцами дверей вокруг базы,
+    и каждый открывает двери своего цвета на обратном пути.
+intro-coins =
+    Если найдёте монеты, берите их с собой.
+    Но помните: монеты ничего не стоят, если вы
+    не вернётесь.
+intro-good-luck = Удачи!
+debrief-out-of-time =
+    Вы не успели вернуться, Агент.
+    Лабиринт оставит себе свои монеты, и вас.
+debrief-no-coins = По крайней мере, вы вернулись живым, Агент
+debrief-a-coin =
+    Рад, что вы вернулись, Агент!
+    Да и монета лишней не будет.
+debrief-some-coins =
+    Рад, что вы вернулись, Агент!
+    Да и эти монеты лишними не будут.
+debrief-many-coins =
+    Я рад, что вы вернулись, но
+    не стоит рисковать жизнью ради этих монет, Агент!
+
+# Радио
+radio-partner =
+    Ваш напарник там вместе с вами, Агент.
+    Всё, что подберёт один из вас, достанется обоим.
+radio-monster =
+    Что-то только что заметило вас, Агент.
+    Что бы ни случилось, не останавливайтесь, чтобы узнать что.
+radio-door-key =
+    Это ключ от двери.
+    Двери того же цвета теперь откроются для вас.
+radio-key =
+    Это ключ от базы!
+    Теперь возвращайтесь как можно быстрее.
+radio-coin = Монета! Только не дайте ей вас задержать.
+radio-far =
+    Вы уходите далеко от базы, Агент.
+    Ключ не должен быть так далеко.
+radio-long =
+    Прошло пять минут, Агент.
+    Чем дольше вы там, тем хуже.
+
+# Во время забега
+action-unlock = Нажмите ЛКМ, чтобы открыть замок и сбежать
+action-find-key = Возвращайтесь сюда, когда найдёте ключ
+action-collect-key = Нажмите ЛКМ, чтобы взять ключ
+action-collect-coin = Нажмите ЛКМ, чтобы взять монету
+action-door = Эту дверь открывает ключ её цвета
+action-open-door = Нажмите ЛКМ, чтобы открыть дверь
+hud-coins = { $coins ->
+        [one] Собрана { $coins } монета
+        [few] Собрано { $coins } монеты
+       *[many] Собрано { $coins } монет
+    }
+hud-keys = Найдено ключей: { $keys } из { $total }
+hud-player = Игрок { $player }
+partner-escaped = Другой Агент сбежал
+partner-left = Другой Агент ушёл
+
+# Ориентиры
+landmark-atrium = Атриум — { $name }
+landmark-flooded = Затопленный зал — { $name }
+landmark-shrine = Святилище — { $name }
+landmark-fallen-agent = { $name }, где пал Агент
+note-fallen-agent =
+    Записка: «Я шёл к замку, но лабиринт снова и снова разворачивал меня.
+    Если найдёшь это, передай базе, что я старался.»
+
+# После забега
+end-score =
+    Счёт: { $score }
+    { $coins ->
+        [one] { $coins } монета
+        [few] { $coins } монеты
+       *[many] { $coins } монет
+    } и { $time } в запасе
+end-first-try = Засчитывается только первая попытка дня
+
+# Гонка по очереди
+race-handover =
+    Игрок 1 вернулся за { $time }.
+    Игрок 2, ваша очередь! Двигайтесь стрелками
+    и обгоните призрака Игрока 1 на пути к замку.
+race-start = Старт
+race-again = Ещё гонка
+race-winner = Игрок { $player } победил!
+race-draw = Ничья!
+race-time = Игрок { $player }: { $time }
+race-still-in-maze = всё ещё в лабиринте
+
+# Кооператив
+coop-joining = Вход в комнату...
+coop-unreachable =
+    Не удалось связаться с ретранслятором
+    { $url }
+    ({ $error })
+coop-waiting = Ждём другого Агента...
+coop-full = В комнате уже два Агента
+coop-lost = Связь с ретранслятором потеряна
+
+# Настройки
+setting-toggle = { $name }: { $state ->
+        [on] Вкл
+       *[off] Выкл
+    }
+effect-screen-shake = Тряска экрана
+effect-chromatic-aberration = Хроматическая аберрация
+effect-pixelation = Пикселизация
+effect-film-grain = Зернистость
+effect-vignette = Виньетка
+setting-dithering = Дизеринг
+setting-view-distance = Дальность обзора: { $distance }
+setting-adaptive-view-distance = Гибкая дальность обзора
+setting-language = Язык: { $language }
+setting-section-names = Названия секций: { $names }
+names-of-language = На языке
+names-english = Английские
+names-latin = Латинские
+names-nordic = Северные
+names-eldritch = Жуткие
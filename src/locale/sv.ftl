@@ -0,0 +1,146 @@
+# Knappar
+start-new = Ny runda
+timed-escape = Mot klockan
+daily-maze = Dagens labyrint
+hot-seat-race = Turvis kapplöpning
+co-op = Samarbete
+settings = Inställningar
+exit = Avsluta
+continue = Fortsätt
+main-menu = Huvudmeny
+play-again = Spela igen
+back = Tillbaka
+created-by = Skapat av
+for-the = för
+
+# Berättelsen
+story-continue = Vänsterklicka eller tryck på mellanslag för att fortsätta...
+intro-wake-up = Vakna Agent!
+intro-sorry = Jag är hemskt ledsen att behöva säga det, men...
+intro-in-the-maze = Du är i labyrinten
+intro-the-drill =
+    Du vet hur det går till.
+    Hitta nyckeln och ta dig tillbaka till basen så fort som möjligt.
+intro-the-clock =
+    Och den här gången tickar klockan.
+    Mynt och nycklar ger dig mer tid.
+intro-the-daily-maze =
+    Alla agenter är i samma labyrint i dag.
+    Bara ditt första försök räknas, så se till att det blir bra.
+intro-the-race =
+    Två agenter, en labyrint.
+    Den som kommer tillbaka till basen först vinner.
+intro-exposure =
+    Som du vet brukar det inte sluta väl att stanna länge.
+    Nycklarna ligger bakom ringar av dörrar runt basen,
+    och var och en öppnar dörrarna i sin färg på vägen tillbaka.
+intro-coins =
+    Om du hittar några mynt, ta med dem.
+    Men kom ihåg, mynt är inget värda om du inte
+    tar dig tillbaka.
+intro-good-luck = Lycka till!
+debrief-out-of-time =
+    Du kom inte tillbaka i tid Agent.
+    Labyrinten behåller sina mynt, och dig.
+debrief-no-coins = Du kom i alla fall tillbaka levande Agent
+debrief-a-coin =
+    Skönt att ha dig tillbaka Agent!
+    Och ett mynt skadar ju inte heller.
+debrief-some-coins =
+    Skönt att ha dig tillbaka Agent!
+    Och de här mynten skadar ju inte heller.
+debrief-many-coins =
+    Jag är glad att ha dig tillbaka men,
+    du borde inte riskera livet för de här mynten Agent!
+
+# Radio
+radio-partner =
+    Din partner är där inne med dig, Agent.
+    Det den ena av er plockar upp har ni båda.
+radio-monster =
+    Något har just lagt märke till dig, Agent.
+    Vad du än gör, stanna inte för att ta reda på vad.
+radio-door-key =
+    Det där är en dörrnyckel.
+    Dörrar i dess färg öppnas för dig nu.
+radio-key =
+    Det är nyckeln till basen!
+    Ta dig nu hit så fort du kan.
+radio-coin = Ett mynt! Låt det bara inte sakta ner dig.
+radio-far =
+    Du kommer långt från basen, Agent.
+    Nyckeln borde inte ligga så här långt bort.
+radio-long =
+    Det har gått fem minuter, Agent.
+    Ju längre du stannar där inne, desto värre blir det.
+
+# Under rundor
+action-unlock = Vänsterklicka för att låsa upp och fly
+action-find-key = Kom tillbaka hit när du har hittat nyckeln för att fly
+action-collect-key = Vänsterklicka för att ta nyckeln
+action-collect-coin = Vänsterklicka för att ta myntet
+action-door = Den här dörren öppnas med nyckeln i dess färg
+action-open-door = Vänsterklicka för att öppna dörren
+hud-coins = Insamlade mynt: { $coins }
+hud-keys = Hittade nycklar: { $keys } av { $total }
+hud-player = Spelare { $player }
+partner-escaped = Den andra agenten har flytt
+partner-left = Den andra agenten har lämnat
+
+# Landmärken
+landmark-atrium = Atriet i { $name }
+landmark-flooded = Översvämmade { $name }
+landmark-shrine = Helgedomen i { $name }
+landmark-fallen-agent = { $name }, där en Agent föll
+note-fallen-agent =
+    Lappen lyder: ”Jag fortsatte mot låset, men labyrinten vände mig om
+    och om igen. Om du hittar det här, säg till basen att jag försökte.”
+
+# Efter rundor
+end-score =
+    Poäng: { $score }
+    { $coins } mynt och { $time } kvar
+end-first-try = Bara dagens första försök räknas
+
+# Turvis kapplöpning
+race-handover =
+    Spelare 1 kom tillbaka på { $time }.
+    Spelare 2, din tur! Gå med piltangenterna
+    och slå spöket av Spelare 1 tillbaka till låset.
+race-start = Starta
+race-again = Spring igen
+race-winner = Spelare { $player } vinner!
+race-draw = Oavgjort!
+race-time = Spelare { $player }: { $time }
+race-still-in-maze = fortfarande i labyrinten
+
+# Samarbete
+coop-joining = Går in i rummet...
+coop-unreachable =
+    Kunde inte nå reläet på
+    { $url }
+    ({ $error })
+coop-waiting = Väntar på den andra agenten...
+coop-full = Rummet har redan två agenter
+coop-lost = Tappade anslutningen till reläet
+
+# Inställningar
+setting-toggle = { $name }: { $state ->
+        [on] På
+       *[off] Av
+    }
+effect-screen-shake = Skakningar
+effect-chromatic-aberration = Kromatisk aberration
+effect-pixelation = Pixelering
+effect-film-grain = Filmkorn
+effect-vignette = Vinjettering
+setting-dithering = Rastrering
+setting-view-distance = Siktavstånd: { $distance }
+setting-adaptive-view-distance = Anpassat siktavstånd
+setting-language = Språk: { $language }
+setting-section-names = Sektionsnamn: { $names }
+names-of-language = Språkets
+names-english = Engelska
+names-latin = Latinska
+names-nordic = Nordiska
+names-eldritch = Kosmiska
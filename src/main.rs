@@ -1,30 +1,167 @@
 #![feature(hash_drain_filter)]
 
 mod camera;
+mod coop;
+mod daily;
+mod dialogue;
+mod effects;
 mod game;
+mod locale;
 mod map;
 mod meshes;
 mod rng;
+mod settings;
+mod stats;
 mod text;
 mod textures;
 
+use std::{env, fs, path::PathBuf, process, str::FromStr};
+
 use kiss3d::window::Window;
 
 use self::{
-	game::{GameState, InnerGameState, MenuState},
+	dialogue::{set_dialogue, Dialogue},
+	game::{
+		bot_by_name,
+		run_bot,
+		set_record_path,
+		set_relay_url,
+		GameState,
+		InnerGameState,
+		MenuState,
+		PlayingState,
+		Replay,
+		BOT_NAMES,
+	},
+	locale::detect_language,
+	map::{Generator, GENERATOR_NAMES},
 	meshes::init_meshes,
+	rng::WorldParams,
 	textures::init_textures,
 };
 
 pub const GAME_NAME: &str = "Little Maze";
 
+const USAGE: &str = "Usage: little_game [--record <file>] [--replay <file>] [--relay <url>]
+                   [--dialogue <file>]
+       little_game simulate --seed <seed> [--runs <runs>] [--bot <bot>]
+                            [--max-ticks <ticks>] [--key-distance <sections>]
+                            [--door-odds <odds>] [--generator <generator>]
+                            [--locked-rings <rings>]";
+/// Half an hour at sixty ticks a second.
+const DEFAULT_MAX_TICKS: u64 = 108_000;
+
 fn main() {
+	let mut replay = None;
+	let mut args = env::args().skip(1).peekable();
+	if args.peek().map(String::as_str) == Some("simulate") {
+		args.next();
+		simulate(args);
+		return;
+	}
+	while let Some(arg) = args.next() {
+		match (arg.as_str(), args.next()) {
+			("--record", Some(path)) => set_record_path(PathBuf::from(path)),
+			("--replay", Some(path)) => replay = Some(read_replay(&path)),
+			("--relay", Some(url)) => set_relay_url(url),
+			("--dialogue", Some(path)) => set_dialogue(read_dialogue(&path)),
+			_ => usage_error(),
+		}
+	}
+
 	let mut window = Window::new_with_size(GAME_NAME, 1280, 800);
 	init_textures();
 	init_meshes();
+	detect_language();
 
-	let mut menu_state = Box::new(MenuState::new(&mut window));
-	menu_state.init(&mut window);
-	let state = GameState::new(menu_state);
+	let mut first_state: Box<dyn InnerGameState> = match replay {
+		Some(replay) => Box::new(PlayingState::replay(&mut window, replay)),
+		None => Box::new(MenuState::new(&mut window)),
+	};
+	first_state.init(&mut window);
+	let state = GameState::new(first_state);
 	window.render_loop(state);
 }
+
+fn read_replay(path: &str) -> Replay {
+	let bytes = fs::read(path).unwrap_or_else(|e| {
+		eprintln!("Could not read {}: {}", path, e);
+		process::exit(1);
+	});
+	Replay::decode(&bytes).unwrap_or_else(|e| {
+		eprintln!("Could not play back {}: {}", path, e);
+		process::exit(1);
+	})
+}
+
+/// Reads dialogue to tell instead of the built-in one, so that it can be tried
+/// out without building the game again.
+fn read_dialogue(path: &str) -> Dialogue {
+	let json = fs::read_to_string(path).unwrap_or_else(|e| {
+		eprintln!("Could not read {}: {}", path, e);
+		process::exit(1);
+	});
+	Dialogue::parse(&json).unwrap_or_else(|e| {
+		eprintln!("Could not use the dialogue in {}: {}", path, e);
+		process::exit(1);
+	})
+}
+
+/// Lets a bot play runs without a window, on `runs` seeds from `seed` on, and
+/// prints the statistics of each run as a line of JSON.
+fn simulate(mut args: impl Iterator<Item = String>) {
+	let mut seed = None;
+	let mut runs = 1;
+	let mut bot_name = "explorer".to_string();
+	let mut max_ticks = DEFAULT_MAX_TICKS;
+	let mut params = WorldParams::default();
+	while let Some(arg) = args.next() {
+		match (arg.as_str(), args.next()) {
+			("--seed", Some(value)) => seed = Some(parse_arg(&value)),
+			("--runs", Some(value)) => runs = parse_arg(&value),
+			("--bot", Some(name)) => bot_name = name,
+			("--max-ticks", Some(value)) => max_ticks = parse_arg(&value),
+			("--key-distance", Some(value)) => params.key_distance = parse_arg(&value),
+			("--door-odds", Some(value)) => params.door_odds = parse_arg(&value),
+			("--locked-rings", Some(value)) => params.locked_rings = parse_arg(&value),
+			("--generator", Some(name)) => {
+				params.generator = Generator::by_name(&name).unwrap_or_else(|| {
+					eprintln!(
+						"Unknown generator {}, expected one of {}",
+						name,
+						GENERATOR_NAMES.join(", ")
+					);
+					process::exit(2);
+				})
+			}
+			_ => usage_error(),
+		}
+	}
+	let seed: u64 = seed.unwrap_or_else(|| usage_error());
+	if params.key_distance < 1 {
+		usage_error();
+	}
+	if bot_by_name(&bot_name).is_none() {
+		eprintln!(
+			"Unknown bot {}, expected one of {}",
+			bot_name,
+			BOT_NAMES.join(", ")
+		);
+		process::exit(2);
+	}
+
+	for seed in seed..seed.saturating_add(runs) {
+		let mut bot = bot_by_name(&bot_name).unwrap();
+		let run = run_bot(seed, params, bot.as_mut(), max_ticks);
+		println!("{}", serde_json::to_string(&run).unwrap());
+	}
+}
+
+fn parse_arg<T: FromStr>(value: &str) -> T {
+	value.parse().unwrap_or_else(|_| usage_error())
+}
+
+fn usage_error() -> ! {
+	eprintln!("{}", USAGE);
+	process::exit(2);
+}
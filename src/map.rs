@@ -1,12 +1,14 @@
 use std::{collections::HashSet, iter::FromIterator};
 
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 pub const ROOM_SIZE: usize = 5;
 pub const ROOM_CENTER: usize = ROOM_SIZE / 2;
 pub const MAP_LENGTH: usize = ROOM_SIZE * 2 * ROOM_SIZE - (ROOM_SIZE + ROOM_SIZE);
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
 	Up,
 	Left,
@@ -14,14 +16,14 @@ pub enum Direction {
 	Down,
 }
 
-const DIRECTIONS: [Direction; 4] = [
+pub const DIRECTIONS: [Direction; 4] = [
 	Direction::Up,
 	Direction::Left,
 	Direction::Right,
 	Direction::Down,
 ];
 
-#[derive(Hash, PartialEq, Eq, Copy, Clone)]
+#[derive(Hash, PartialEq, Eq, Copy, Clone, Debug)]
 pub struct Position(
 	/// Row
 	pub usize,
@@ -29,10 +31,18 @@ pub struct Position(
 	pub usize,
 );
 
+#[derive(Clone)]
 pub struct Map([bool; MAP_LENGTH]);
 
+impl Default for Map {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 impl Map {
-	fn new() -> Map {
+	/// A map with every wall closed.
+	pub fn new() -> Map {
 		Map([true; MAP_LENGTH])
 	}
 
@@ -62,6 +72,38 @@ impl Map {
 		map
 	}
 
+	/// Carves the map by walking in random directions, backing up whenever
+	/// there is nowhere new to go.
+	pub fn generate_backtracker<R>(rng: &mut R) -> Map
+	where
+		R: Rng,
+	{
+		let mut map = Map::new();
+
+		let mut visited = HashSet::new();
+		visited.insert(Position(ROOM_CENTER, ROOM_CENTER));
+		let mut stack = vec![Position(ROOM_CENTER, ROOM_CENTER)];
+
+		while let Some(from) = stack.last().copied() {
+			let unvisited: Vec<_> = DIRECTIONS
+				.iter()
+				.filter_map(|dir| map.move_in_direction(&from, dir).map(|to| (*dir, to)))
+				.filter(|(_, to)| !visited.contains(to))
+				.collect();
+			if unvisited.is_empty() {
+				stack.pop();
+			} else {
+				let (dir, to) = unvisited[rng.gen_range(0..unvisited.len())];
+				map.set(&from, &dir, false);
+
+				visited.insert(to);
+				stack.push(to);
+			}
+		}
+
+		map
+	}
+
 	#[inline]
 	pub fn set_above(&mut self, pos: &Position, closed: bool) {
 		self.set_below(&Position(pos.0 - 1, pos.1), closed);
@@ -137,7 +179,7 @@ impl Map {
 		}
 	}
 
-	fn move_in_direction(&self, current: &Position, dir: &Direction) -> Option<Position> {
+	pub fn move_in_direction(&self, current: &Position, dir: &Direction) -> Option<Position> {
 		match dir {
 			Direction::Up if current.0 > 0 => Some(Position(current.0 - 1, current.1)),
 			Direction::Left if current.1 > 0 => Some(Position(current.0, current.1 - 1)),
@@ -166,3 +208,49 @@ impl Map {
 			.collect()
 	}
 }
+
+/// Ways of carving a map out of one with every wall closed.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Generator {
+	/// [`Map::generate_prim`], which makes many short dead ends.
+	Prim,
+	/// [`Map::generate_backtracker`], which makes long winding corridors.
+	Backtracker,
+}
+
+/// Names of the generators that can be picked with [`Generator::by_name`].
+pub const GENERATOR_NAMES: [&str; 2] = ["prim", "backtracker"];
+
+impl Generator {
+	/// The generator called `name` on the command line.
+	pub fn by_name(name: &str) -> Option<Self> {
+		match name {
+			"prim" => Some(Self::Prim),
+			"backtracker" => Some(Self::Backtracker),
+			_ => None,
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Prim => "prim",
+			Self::Backtracker => "backtracker",
+		}
+	}
+
+	pub fn generate<R>(self, rng: &mut R) -> Map
+	where
+		R: Rng,
+	{
+		match self {
+			Self::Prim => Map::generate_prim(rng),
+			Self::Backtracker => Map::generate_backtracker(rng),
+		}
+	}
+}
+
+impl Default for Generator {
+	fn default() -> Self {
+		Self::Prim
+	}
+}
@@ -0,0 +1,99 @@
+//! Draws a region of the world from above, without starting the game.
+
+use std::{env, fs::File, io::BufWriter, process, str::FromStr};
+
+use little_game::{atlas::Atlas, metrics::RegionMetrics, Generator, WorldParams, GENERATOR_NAMES};
+
+const USAGE: &str = "Usage: maze --seed <seed> --from <row>,<col> [--to <row>,<col>]
+            [--generator <generator>] [--locked-rings <rings>]
+            [--png <file>] [--cell-size <pixels>] [--metrics]";
+const DEFAULT_CELL_SIZE: usize = 8;
+
+fn main() {
+	let mut seed = None;
+	let mut from = None;
+	let mut to = None;
+	let mut png_path = None;
+	let mut cell_size = DEFAULT_CELL_SIZE;
+	let mut params = WorldParams::default();
+	let mut metrics = false;
+	let mut args = env::args().skip(1);
+	while let Some(arg) = args.next() {
+		if arg == "--metrics" {
+			metrics = true;
+			continue;
+		}
+		match (arg.as_str(), args.next()) {
+			("--seed", Some(value)) => seed = Some(parse_arg(&value)),
+			("--from", Some(value)) => from = Some(parse_section(&value)),
+			("--to", Some(value)) => to = Some(parse_section(&value)),
+			("--png", Some(path)) => png_path = Some(path),
+			("--cell-size", Some(value)) => cell_size = parse_arg(&value),
+			("--locked-rings", Some(value)) => params.locked_rings = parse_arg(&value),
+			("--generator", Some(name)) => {
+				params.generator = Generator::by_name(&name).unwrap_or_else(|| {
+					eprintln!(
+						"Unknown generator {}, expected one of {}",
+						name,
+						GENERATOR_NAMES.join(", ")
+					);
+					process::exit(2);
+				})
+			}
+			_ => usage_error(),
+		}
+	}
+	let seed: u64 = seed.unwrap_or_else(|| usage_error());
+	let from = from.unwrap_or_else(|| usage_error());
+	let to = to.unwrap_or(from);
+	if cell_size == 0 {
+		usage_error();
+	}
+
+	if metrics {
+		let metrics = RegionMetrics::measure(seed, &params, from, to);
+		println!("{}", serde_json::to_string(&metrics).unwrap());
+		return;
+	}
+
+	let atlas = Atlas::generate(seed, &params, from, to);
+	match png_path {
+		Some(path) => {
+			if let Err(e) = write_png(&atlas, cell_size, &path) {
+				eprintln!("Could not write {}: {}", path, e);
+				process::exit(1);
+			}
+		}
+		None => print!("{}", atlas.to_ascii()),
+	}
+	for ((row, col), name) in atlas.names() {
+		println!("({}, {}) {}", row, col, name);
+	}
+}
+
+fn write_png(atlas: &Atlas, cell_size: usize, path: &str) -> Result<(), png::EncodingError> {
+	let (width, height, pixels) = atlas.to_rgb(cell_size);
+	let file = File::create(path)?;
+	let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+	encoder.set_color(png::ColorType::RGB);
+	encoder.set_depth(png::BitDepth::Eight);
+	encoder.write_header()?.write_image_data(&pixels)
+}
+
+/// A section written as `row,col`.
+fn parse_section(value: &str) -> (i64, i64) {
+	let mut parts = value.split(',');
+	match (parts.next(), parts.next(), parts.next()) {
+		(Some(row), Some(col), None) => (parse_arg(row.trim()), parse_arg(col.trim())),
+		_ => usage_error(),
+	}
+}
+
+fn parse_arg<T: FromStr>(value: &str) -> T {
+	value.parse().unwrap_or_else(|_| usage_error())
+}
+
+fn usage_error() -> ! {
+	eprintln!("{}", USAGE);
+	process::exit(2);
+}
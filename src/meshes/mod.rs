@@ -8,17 +8,19 @@ use kiss3d::{
 };
 use rand::{rngs::StdRng, Rng};
 
-use super::{rng::rng_for_maze, textures::hsl_to_rgb};
+use super::{
+	rng::{rng_for_key, WorldParams},
+	textures::hsl_to_rgb,
+};
 
 const KEY: &str = "key";
 const LOCK: &str = "lock";
 const COIN: &str = "coin";
 
-pub type MeshGenerator = dyn FnOnce(&mut SceneNode) -> SceneNode;
-
-#[derive(PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ItemKind {
-	Key,
+	/// Opens the doors of its tier, or the lock once past them all.
+	Key(usize),
 	Lock,
 	Coin,
 }
@@ -54,9 +56,23 @@ fn add_meshes(manager: &mut MeshManager) {
 	);
 }
 
-pub fn generate_key(parent: &mut SceneNode, seed: u64, position: (i64, i64)) -> SceneNode {
-	let mut rng: StdRng = rng_for_maze(seed, position);
-	let (r, g, b) = hsl_to_rgb(rng.gen(), 0.5, 0.5);
+/// Colour of key `tier` and of the doors it opens. The hues of the keys are
+/// spread evenly around from the first one's, so that no two look alike.
+pub fn key_color(seed: u64, params: &WorldParams, tier: usize) -> (f32, f32, f32) {
+	let first: f32 = rng_for_key::<StdRng>(seed, 0).gen();
+	let hue = (first + tier as f32 / (params.locked_rings + 1) as f32).fract();
+	hsl_to_rgb(hue, 0.5, 0.5)
+}
+
+/// A key with teeth unique to the seed and its tier.
+pub fn generate_key(
+	parent: &mut SceneNode,
+	seed: u64,
+	params: &WorldParams,
+	tier: usize,
+) -> SceneNode {
+	let mut rng: StdRng = rng_for_key(seed, tier);
+	let (r, g, b) = key_color(seed, params, tier);
 
 	let mut group = parent.add_group();
 	let mut key = group
@@ -107,3 +123,24 @@ pub fn generate_coin(parent: &mut SceneNode) -> SceneNode {
 	coin.set_material_with_name("pixel");
 	coin
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key_colours_are_far_apart() {
+		let params = WorldParams::default();
+		for seed in 0..20 {
+			let colours: Vec<_> = (0..=params.locked_rings)
+				.map(|tier| key_color(seed, &params, tier))
+				.collect();
+			for (i, a) in colours.iter().enumerate() {
+				for b in colours[i + 1..].iter() {
+					let difference = (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs();
+					assert!(difference > 0.3, "{:?} and {:?} look alike", a, b);
+				}
+			}
+		}
+	}
+}
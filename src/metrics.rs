@@ -0,0 +1,266 @@
+//! Numbers describing the shape of generated mazes, for tuning generators.
+
+use std::collections::{HashSet, VecDeque};
+
+use serde::Serialize;
+
+use super::{
+	atlas::span,
+	game::SectionLayout,
+	map::{Map, Position, DIRECTIONS, ROOM_SIZE},
+	rng::WorldParams,
+};
+
+const CELLS: usize = ROOM_SIZE * ROOM_SIZE;
+
+impl Map {
+	/// Number of cells with only one way out.
+	pub fn dead_ends(&self) -> usize {
+		positions()
+			.filter(|pos| self.exits(pos).count() == 1)
+			.count()
+	}
+
+	/// Number of cells with more than two ways out.
+	pub fn junctions(&self) -> usize {
+		positions()
+			.filter(|pos| self.exits(pos).count() > 2)
+			.count()
+	}
+
+	/// Number of steps on the shortest way from `from` to `to`, if there is
+	/// one.
+	pub fn path_length(&self, from: &Position, to: &Position) -> Option<usize> {
+		self.distances(from)[to.0 * ROOM_SIZE + to.1]
+	}
+
+	/// Number of steps between the two cells furthest apart.
+	pub fn longest_path(&self) -> usize {
+		positions()
+			.flat_map(|from| self.distances(&from).to_vec())
+			.flatten()
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Number of cells in each corridor, a run of cells with exactly two ways
+	/// out.
+	pub fn corridor_runs(&self) -> Vec<usize> {
+		let in_corridor = |pos: &Position| self.exits(pos).count() == 2;
+		let mut visited = HashSet::new();
+		let mut runs = Vec::new();
+		for start in positions().filter(in_corridor) {
+			if !visited.insert(start) {
+				continue;
+			}
+			let mut run = 0;
+			let mut stack = vec![start];
+			while let Some(pos) = stack.pop() {
+				run += 1;
+				for next in self.exits(&pos) {
+					if in_corridor(&next) && visited.insert(next) {
+						stack.push(next);
+					}
+				}
+			}
+			runs.push(run);
+		}
+		runs
+	}
+
+	/// Share of cells in corridors, where the maze flows on without
+	/// branching or stopping.
+	pub fn river_factor(&self) -> f32 {
+		self.corridor_runs().iter().sum::<usize>() as f32 / CELLS as f32
+	}
+
+	/// Number of steps from `from` to every cell, by row and then column.
+	fn distances(&self, from: &Position) -> [Option<usize>; CELLS] {
+		let mut distances = [None; CELLS];
+		distances[from.0 * ROOM_SIZE + from.1] = Some(0);
+		let mut queue = VecDeque::new();
+		queue.push_back((*from, 0));
+		while let Some((pos, distance)) = queue.pop_front() {
+			for next in self.exits(&pos) {
+				let known = &mut distances[next.0 * ROOM_SIZE + next.1];
+				if known.is_none() {
+					*known = Some(distance + 1);
+					queue.push_back((next, distance + 1));
+				}
+			}
+		}
+		distances
+	}
+
+	/// The cells next to `pos` without a wall in between.
+	fn exits<'a>(&'a self, pos: &'a Position) -> impl Iterator<Item = Position> + 'a {
+		DIRECTIONS
+			.iter()
+			.filter_map(move |dir| match self.is(pos, dir) {
+				Some(false) => self.move_in_direction(pos, dir),
+				_ => None,
+			})
+	}
+}
+
+/// Averages of the metrics of every section in a region.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct RegionMetrics {
+	pub seed: u64,
+	pub generator: &'static str,
+	pub sections: usize,
+	pub dead_ends: f32,
+	pub junctions: f32,
+	pub longest_path: f32,
+	/// Steps between two doors of the same section.
+	pub door_path: f32,
+	pub corridor_run: f32,
+	pub longest_corridor_run: usize,
+	pub river_factor: f32,
+}
+
+impl RegionMetrics {
+	/// The metrics of the sections in the rectangle with `corner` and `other`
+	/// at opposite corners.
+	pub fn measure(seed: u64, params: &WorldParams, corner: (i64, i64), other: (i64, i64)) -> Self {
+		let mut maps = Vec::new();
+		let mut door_paths = Vec::new();
+		for row in span(corner.0, other.0) {
+			for col in span(corner.1, other.1) {
+				let layout = SectionLayout::generate_with_params(seed, params, (row, col), false);
+				let doors = door_cells(&layout.openings);
+				for (i, from) in doors.iter().enumerate() {
+					door_paths.extend(
+						doors[i + 1..]
+							.iter()
+							.filter_map(|to| layout.map.path_length(from, to)),
+					);
+				}
+				maps.push(layout.map);
+			}
+		}
+		let runs: Vec<_> = maps.iter().flat_map(Map::corridor_runs).collect();
+
+		Self {
+			seed,
+			generator: params.generator.name(),
+			sections: maps.len(),
+			dead_ends: mean(maps.iter().map(|m| m.dead_ends() as f32)),
+			junctions: mean(maps.iter().map(|m| m.junctions() as f32)),
+			longest_path: mean(maps.iter().map(|m| m.longest_path() as f32)),
+			door_path: mean(door_paths.iter().map(|steps| *steps as f32)),
+			corridor_run: mean(runs.iter().map(|run| *run as f32)),
+			longest_corridor_run: runs.iter().copied().max().unwrap_or(0),
+			river_factor: mean(maps.iter().map(Map::river_factor)),
+		}
+	}
+}
+
+/// The cells just inside the doors of a section, given the openings in its
+/// border walls.
+fn door_cells(openings: &[Option<usize>; 4]) -> Vec<Position> {
+	let [up, left, right, down] = *openings;
+	let last = ROOM_SIZE - 1;
+	up.map(|col| Position(0, col))
+		.into_iter()
+		.chain(left.map(|row| Position(row, 0)))
+		.chain(right.map(|row| Position(row, last)))
+		.chain(down.map(|col| Position(last, col)))
+		.collect()
+}
+
+/// Every cell, by row and then column.
+fn positions() -> impl Iterator<Item = Position> {
+	(0..CELLS).map(|i| Position(i / ROOM_SIZE, i % ROOM_SIZE))
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+	let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+	if count == 0 {
+		0.0
+	} else {
+		sum / count as f32
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rand::{rngs::StdRng, SeedableRng};
+
+	use super::{
+		super::map::{Generator, ROOM_CENTER},
+		*,
+	};
+
+	const GENERATORS: [Generator; 2] = [Generator::Prim, Generator::Backtracker];
+
+	/// A single corridor winding back and forth across every row, from the
+	/// first cell to the last.
+	fn serpentine() -> Map {
+		let mut map = Map::new();
+		for row in 0..ROOM_SIZE {
+			for col in 0..ROOM_SIZE - 1 {
+				map.set_right(&Position(row, col), false);
+			}
+			if row + 1 < ROOM_SIZE {
+				let col = if row % 2 == 0 { ROOM_SIZE - 1 } else { 0 };
+				map.set_below(&Position(row, col), false);
+			}
+		}
+		map
+	}
+
+	fn maps(generator: Generator) -> impl Iterator<Item = Map> {
+		(0..200).map(move |seed| generator.generate(&mut StdRng::seed_from_u64(seed)))
+	}
+
+	fn total(generator: Generator, metric: fn(&Map) -> f32) -> f32 {
+		maps(generator).map(|map| metric(&map)).sum()
+	}
+
+	#[test]
+	fn serpentine_metrics() {
+		let map = serpentine();
+		let last = Position(ROOM_SIZE - 1, ROOM_SIZE - 1);
+		assert_eq!(map.dead_ends(), 2);
+		assert_eq!(map.junctions(), 0);
+		assert_eq!(map.longest_path(), CELLS - 1);
+		assert_eq!(map.path_length(&Position(0, 0), &last), Some(CELLS - 1));
+		assert_eq!(map.corridor_runs(), vec![CELLS - 2]);
+		assert_eq!(Map::new().path_length(&Position(0, 0), &last), None);
+	}
+
+	#[test]
+	fn generators_reach_every_cell_one_way() {
+		let center = Position(ROOM_CENTER, ROOM_CENTER);
+		for generator in GENERATORS.iter() {
+			for map in maps(*generator) {
+				assert!(positions().all(|pos| map.path_length(&center, &pos).is_some()));
+				let passages = positions()
+					.map(|pos| map.exits(&pos).count())
+					.sum::<usize>() / 2;
+				assert_eq!(passages, CELLS - 1, "{:?}", generator);
+			}
+		}
+	}
+
+	#[test]
+	fn backtracker_winds_more_than_prim() {
+		let (prim, backtracker) = (Generator::Prim, Generator::Backtracker);
+		let dead_ends = |map: &Map| map.dead_ends() as f32;
+		let longest_path = |map: &Map| map.longest_path() as f32;
+		assert!(total(backtracker, dead_ends) < total(prim, dead_ends));
+		assert!(total(backtracker, longest_path) > total(prim, longest_path));
+		assert!(total(backtracker, Map::river_factor) > total(prim, Map::river_factor));
+	}
+
+	#[test]
+	fn region_metrics_cover_every_section() {
+		let params = WorldParams::default();
+		let metrics = RegionMetrics::measure(5, &params, (1, 1), (-1, -1));
+		assert_eq!(metrics.sections, 9);
+		assert_eq!(metrics.generator, "prim");
+		assert!(metrics.door_path > 0.0);
+		assert!((0.0..=1.0).contains(&metrics.river_factor));
+	}
+}
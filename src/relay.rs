@@ -0,0 +1,226 @@
+//! Passes messages between the two Agents of each co-op room. The room is
+//! the path an Agent connects with, and nothing about the world is kept here.
+
+use std::{
+	collections::HashMap,
+	env,
+	io::ErrorKind,
+	net::{TcpListener, TcpStream},
+	process,
+	sync::{
+		mpsc::{self, Receiver, Sender},
+		Arc,
+		Mutex,
+	},
+	thread,
+	time::Duration,
+};
+
+use little_game::coop::{Message, RELAY_PORT};
+use tungstenite::{
+	accept_hdr,
+	handshake::server::{Request, Response},
+	Message as WsMessage,
+	WebSocket,
+};
+
+const USAGE: &str = "Usage: relay [--bind <address>]";
+/// How long to wait for a message before passing on the other Agent's.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The Agents in each room, by player number.
+type Rooms = Arc<Mutex<HashMap<String, Vec<(usize, Sender<Message>)>>>>;
+
+fn main() {
+	let mut address = format!("127.0.0.1:{}", RELAY_PORT);
+	let mut args = env::args().skip(1);
+	while let Some(arg) = args.next() {
+		match (arg.as_str(), args.next()) {
+			("--bind", Some(value)) => address = value,
+			_ => {
+				eprintln!("{}", USAGE);
+				process::exit(2);
+			}
+		}
+	}
+
+	let listener = TcpListener::bind(&address).unwrap_or_else(|e| {
+		eprintln!("Could not listen on {}: {}", address, e);
+		process::exit(1);
+	});
+	println!("Relaying on ws://{}/<room>", address);
+	serve(listener);
+}
+
+fn serve(listener: TcpListener) {
+	let rooms = Rooms::default();
+	for stream in listener.incoming().flatten() {
+		let rooms = rooms.clone();
+		thread::spawn(move || relay(stream, rooms));
+	}
+}
+
+/// Lets one Agent into its room and passes messages to and from it until it
+/// leaves.
+fn relay(stream: TcpStream, rooms: Rooms) {
+	let mut room = String::new();
+	let mut socket = match accept_hdr(stream, |request: &Request, response: Response| {
+		room = request.uri().path().to_string();
+		Ok(response)
+	}) {
+		Ok(socket) => socket,
+		Err(_) => return,
+	};
+
+	let (sender, inbox) = mpsc::channel();
+	let player = {
+		let mut rooms = rooms.lock().unwrap();
+		let agents = rooms.entry(room.clone()).or_default();
+		if agents.len() >= 2 {
+			let _ = send(&mut socket, &Message::Full);
+			let _ = socket.close(None);
+			let _ = socket.write_pending();
+			return;
+		}
+		let player = if agents.iter().any(|(p, _)| *p == 0) {
+			1
+		} else {
+			0
+		};
+		for (_, other) in agents.iter() {
+			let _ = other.send(Message::Joined);
+		}
+		agents.push((player, sender));
+		player
+	};
+
+	let welcome = Message::Welcome {
+		player,
+	};
+	let timeout = socket.get_mut().set_read_timeout(Some(POLL_INTERVAL));
+	if timeout.is_ok() && send(&mut socket, &welcome).is_ok() {
+		pass_messages(&mut socket, &inbox, &rooms, &room, player);
+	}
+
+	let mut rooms = rooms.lock().unwrap();
+	if let Some(agents) = rooms.get_mut(&room) {
+		agents.retain(|(p, _)| *p != player);
+		for (_, other) in agents.iter() {
+			let _ = other.send(Message::Left);
+		}
+		if agents.is_empty() {
+			rooms.remove(&room);
+		}
+	}
+}
+
+fn pass_messages(
+	socket: &mut WebSocket<TcpStream>,
+	inbox: &Receiver<Message>,
+	rooms: &Rooms,
+	room: &str,
+	player: usize,
+) {
+	loop {
+		match socket.read_message() {
+			Ok(WsMessage::Text(text)) => {
+				// Only what the Agents say to each other is passed on, the
+				// relay speaks for itself.
+				match Message::from_text(&text) {
+					Some(Message::Welcome {
+						..
+					})
+					| Some(Message::Joined)
+					| Some(Message::Left)
+					| Some(Message::Full)
+					| None => {}
+					Some(message) => {
+						for (_, other) in rooms.lock().unwrap()[room]
+							.iter()
+							.filter(|(p, _)| *p != player)
+						{
+							let _ = other.send(message.clone());
+						}
+					}
+				}
+			}
+			Ok(WsMessage::Close(_)) => return,
+			Ok(_) => {}
+			Err(tungstenite::Error::Io(e))
+				if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {}
+			Err(_) => return,
+		}
+		for message in inbox.try_iter() {
+			if send(socket, &message).is_err() {
+				return;
+			}
+		}
+	}
+}
+
+fn send(socket: &mut WebSocket<TcpStream>, message: &Message) -> tungstenite::Result<()> {
+	socket.write_message(WsMessage::Text(message.to_text()))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Instant;
+
+	use little_game::coop::Connection;
+
+	use super::*;
+
+	fn start_relay() -> String {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		thread::spawn(move || serve(listener));
+		format!("ws://{}/test", address)
+	}
+
+	fn next(connection: &mut Connection) -> Message {
+		let start = Instant::now();
+		loop {
+			if let Some(message) = connection.receive() {
+				return message;
+			}
+			assert!(start.elapsed() < Duration::from_secs(5), "no message came");
+			thread::sleep(Duration::from_millis(1));
+		}
+	}
+
+	#[test]
+	fn agents_in_a_room_hear_each_other() {
+		let url = start_relay();
+		let mut first = Connection::open(&url).unwrap();
+		assert_eq!(next(&mut first), Message::Welcome {
+			player: 0
+		});
+		let mut second = Connection::open(&url).unwrap();
+		assert_eq!(next(&mut second), Message::Welcome {
+			player: 1
+		});
+		assert_eq!(next(&mut first), Message::Joined);
+
+		let world = Message::World {
+			seed: 42,
+		};
+		first.send(&world);
+		assert_eq!(next(&mut second), world);
+		let collected = Message::Collected {
+			section: (1, -2),
+			cell: (3, 0),
+		};
+		second.send(&collected);
+		assert_eq!(next(&mut first), collected);
+
+		let mut third = Connection::open(&url).unwrap();
+		assert_eq!(next(&mut third), Message::Full);
+
+		drop(second);
+		assert_eq!(next(&mut first), Message::Left);
+		let mut fourth = Connection::open(&url).unwrap();
+		assert_eq!(next(&mut fourth), Message::Welcome {
+			player: 1
+		});
+	}
+}
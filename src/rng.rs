@@ -1,6 +1,6 @@
 use rand::{Rng, SeedableRng};
 
-use super::map::Direction;
+use super::map::{Direction, Generator};
 
 pub fn rng_for_maze<R: SeedableRng>(seed: u64, position: (i64, i64)) -> R {
 	rng_from_bytes(&[
@@ -10,22 +10,148 @@ pub fn rng_for_maze<R: SeedableRng>(seed: u64, position: (i64, i64)) -> R {
 	])
 }
 
-const KEY_DISTANCE: i64 = 1;
-pub fn rand_for_key<R: SeedableRng + Rng>(seed: u64) -> (i64, i64) {
-	let mut rng: R = rng_from_bytes(&[&seed.to_be_bytes()]);
-	(
-		rng.gen_range(1..=KEY_DISTANCE) * if rng.gen() { 1 } else { -1 },
-		rng.gen_range(1..=KEY_DISTANCE) * if rng.gen() { 1 } else { -1 },
-	)
+/// Randomness of key `tier` that has nothing to do with where it is, kept
+/// apart from that of the sections.
+pub fn rng_for_key<R: SeedableRng>(seed: u64, tier: usize) -> R {
+	rng_from_bytes(&[&seed.to_be_bytes(), b"key", &(tier as u64).to_be_bytes()])
 }
 
+const KEY_DISTANCE: i64 = 1;
 const DOOR_ODDS: f32 = 0.8;
+const LOCKED_RINGS: usize = 2;
+
+/// How the world is laid out, apart from the seed.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WorldParams {
+	/// How many rings of sections each key can be in, counted outwards from
+	/// the doors the previous key opens, or from the lock for the first key.
+	pub key_distance: i64,
+	/// Odds of there being a door in a border wall.
+	pub door_odds: f32,
+	/// How the maze inside each section is carved.
+	pub generator: Generator,
+	/// Number of rings of locked doors around the lock. The doors of each
+	/// ring open with a key found inside it, and the key to the lock is
+	/// outside of them all.
+	pub locked_rings: usize,
+}
+
+impl Default for WorldParams {
+	fn default() -> Self {
+		Self {
+			key_distance: KEY_DISTANCE,
+			door_odds: DOOR_ODDS,
+			generator: Generator::default(),
+			locked_rings: LOCKED_RINGS,
+		}
+	}
+}
+
+impl WorldParams {
+	/// Furthest ring that has keys in it. Every border within it has a door,
+	/// so that nothing inside the locked rings is walled off.
+	pub fn key_rings(&self) -> i64 {
+		(self.locked_rings as i64 + 1) * self.key_distance
+	}
+
+	/// Tier of the locked door between the neighbouring sections `a` and `b`,
+	/// if there is one. The doors of tier `n` open with key `n`.
+	pub fn door_tier(&self, a: (i64, i64), b: (i64, i64)) -> Option<usize> {
+		let inner = ring(a).min(ring(b));
+		if ring(a) == ring(b) || inner % self.key_distance != 0 {
+			return None;
+		}
+		let tier = inner / self.key_distance - 1;
+		if 0 <= tier && (tier as usize) < self.locked_rings {
+			Some(tier as usize)
+		} else {
+			None
+		}
+	}
+}
+
+/// Number of sections between `position` and the lock's, along the axis
+/// where it is furthest.
+pub fn ring(position: (i64, i64)) -> i64 {
+	position.0.abs().max(position.1.abs())
+}
+
+/// Section of key `tier`, in the rings between the doors of the tier before
+/// and its own doors. Without locked rings there is only the one key, which
+/// is off to a diagonal from the lock.
+pub fn rand_for_key<R: SeedableRng + Rng>(
+	seed: u64,
+	params: &WorldParams,
+	tier: usize,
+) -> (i64, i64) {
+	let key_distance = params.key_distance;
+	if params.locked_rings == 0 {
+		let mut rng: R = rng_from_bytes(&[&seed.to_be_bytes()]);
+		return (
+			rng.gen_range(1..=key_distance) * if rng.gen() { 1 } else { -1 },
+			rng.gen_range(1..=key_distance) * if rng.gen() { 1 } else { -1 },
+		);
+	}
+
+	let mut rng: R = rng_from_bytes(&[&seed.to_be_bytes(), &(tier as u64).to_be_bytes()]);
+	let tier = tier as i64;
+	let ring = rng.gen_range(tier * key_distance + 1..=(tier + 1) * key_distance);
+	let along = rng.gen_range(-ring..ring);
+	match rng.gen_range(0..4) {
+		0 => (ring, along),
+		1 => (-along, ring),
+		2 => (-ring, -along),
+		_ => (along, -ring),
+	}
+}
+
 pub fn rand_for_border_walls<R: SeedableRng + Rng>(
 	seed: u64,
-	mut position: (i64, i64),
-	mut direction: Direction,
+	position: (i64, i64),
+	direction: Direction,
 	max: usize,
+	door_odds: f32,
 ) -> Option<usize> {
+	let (position, direction) = shared_border(position, direction);
+	let mut rng: R = rng_from_bytes(&[
+		&seed.to_be_bytes(),
+		&position.0.to_be_bytes(),
+		&position.1.to_be_bytes(),
+		&[direction as u8],
+	]);
+	if rng.gen::<f32>() < door_odds {
+		Some(rng.gen_range(0..max))
+	} else {
+		None
+	}
+}
+
+/// Whether the opening in the border wall of `position` in `direction` has
+/// a door that swings open, with the odds of `odds`.
+pub fn rand_for_hinged_door<R: SeedableRng + Rng>(
+	seed: u64,
+	position: (i64, i64),
+	direction: Direction,
+	odds: f32,
+) -> bool {
+	let (position, direction) = shared_border(position, direction);
+	let mut rng: R = rng_from_bytes(&[
+		&seed.to_be_bytes(),
+		&position.0.to_be_bytes(),
+		&position.1.to_be_bytes(),
+		&[direction as u8],
+		b"door",
+	]);
+	rng.gen::<f32>() < odds
+}
+
+/// The border wall of `position` in `direction`, as seen from whichever of
+/// the two sections it is between that is closer to the middle, so that both
+/// see it the same.
+pub fn shared_border(
+	mut position: (i64, i64),
+	mut direction: Direction,
+) -> ((i64, i64), Direction) {
 	match direction {
 		Direction::Up if position.1 > 0 => {
 			direction = Direction::Down;
@@ -45,18 +171,7 @@ pub fn rand_for_border_walls<R: SeedableRng + Rng>(
 		}
 		_ => {}
 	};
-
-	let mut rng: R = rng_from_bytes(&[
-		&seed.to_be_bytes(),
-		&position.0.to_be_bytes(),
-		&position.1.to_be_bytes(),
-		&[direction as u8],
-	]);
-	if rng.gen::<f32>() < DOOR_ODDS {
-		Some(rng.gen_range(0..max))
-	} else {
-		None
-	}
+	(position, direction)
 }
 
 fn rng_from_bytes<R: SeedableRng>(seeds: &[&[u8]]) -> R {
@@ -79,31 +194,76 @@ mod tests {
 
 	use super::{super::map::Direction, *};
 
+	#[test]
+	fn keys_are_between_their_doors() {
+		let params = WorldParams {
+			key_distance: 2,
+			..WorldParams::default()
+		};
+		for seed in 0..50 {
+			for tier in 0..=params.locked_rings {
+				let key = rand_for_key::<StdRng>(seed, &params, tier);
+				let tier = tier as i64;
+				assert!(tier * 2 < ring(key) && ring(key) <= (tier + 1) * 2);
+			}
+		}
+	}
+
+	#[test]
+	fn doors_are_on_ring_borders() {
+		let params = WorldParams::default();
+		assert_eq!(params.door_tier((0, 0), (0, 1)), None);
+		assert_eq!(params.door_tier((1, 0), (2, 0)), Some(0));
+		assert_eq!(params.door_tier((-3, 2), (-2, 2)), Some(1));
+		assert_eq!(params.door_tier((2, 2), (2, 3)), Some(1));
+		assert_eq!(params.door_tier((3, 0), (4, 0)), None);
+		assert_eq!(params.door_tier((1, 1), (1, 2)), Some(0));
+		assert_eq!(params.door_tier((2, 1), (2, 2)), None);
+	}
+
 	#[test]
 	fn upward_stability() {
-		let up_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Up, 5);
-		let down_above_origin = rand_for_border_walls::<StdRng>(0, (0, -1), Direction::Down, 5);
+		let up_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Up, 5, DOOR_ODDS);
+		let down_above_origin =
+			rand_for_border_walls::<StdRng>(0, (0, -1), Direction::Down, 5, DOOR_ODDS);
 		assert_eq!(up_origin, down_above_origin);
 	}
 
 	#[test]
 	fn left_stability() {
-		let left_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Left, 5);
-		let right_left_origin = rand_for_border_walls::<StdRng>(0, (-1, 0), Direction::Right, 5);
+		let left_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Left, 5, DOOR_ODDS);
+		let right_left_origin =
+			rand_for_border_walls::<StdRng>(0, (-1, 0), Direction::Right, 5, DOOR_ODDS);
 		assert_eq!(left_origin, right_left_origin);
 	}
 
 	#[test]
 	fn right_stability() {
-		let right_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Right, 5);
-		let left_right_origin = rand_for_border_walls::<StdRng>(0, (1, 0), Direction::Left, 5);
+		let right_origin =
+			rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Right, 5, DOOR_ODDS);
+		let left_right_origin =
+			rand_for_border_walls::<StdRng>(0, (1, 0), Direction::Left, 5, DOOR_ODDS);
 		assert_eq!(right_origin, left_right_origin);
 	}
 
 	#[test]
 	fn downward_stability() {
-		let down_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Down, 5);
-		let up_below_origin = rand_for_border_walls::<StdRng>(0, (0, 1), Direction::Up, 5);
+		let down_origin = rand_for_border_walls::<StdRng>(0, (0, 0), Direction::Down, 5, DOOR_ODDS);
+		let up_below_origin =
+			rand_for_border_walls::<StdRng>(0, (0, 1), Direction::Up, 5, DOOR_ODDS);
 		assert_eq!(down_origin, up_below_origin);
 	}
+
+	#[test]
+	fn hinged_doors_are_seen_from_both_sides() {
+		for seed in 0..20 {
+			let right = rand_for_hinged_door::<StdRng>(seed, (2, -1), Direction::Right, 0.5);
+			let left = rand_for_hinged_door::<StdRng>(seed, (3, -1), Direction::Left, 0.5);
+			assert_eq!(right, left);
+		}
+		assert_eq!(
+			shared_border((0, 3), Direction::Up),
+			shared_border((0, 2), Direction::Down)
+		);
+	}
 }
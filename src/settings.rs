@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+
+use super::{
+	effects::ScreenEffect,
+	locale::Language,
+	text::{NameTheme, NAME_THEMES},
+};
+
+thread_local! {
+	static SETTINGS: RefCell<Settings> = RefCell::new(Settings::default());
+}
+
+pub fn settings() -> Settings {
+	SETTINGS.with(|s| s.borrow().clone())
+}
+
+pub fn set_settings(settings: Settings) {
+	SETTINGS.with(|s| *s.borrow_mut() = settings);
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Settings {
+	pub screen_shake: bool,
+	pub chromatic_aberration: bool,
+	pub pixelation: bool,
+	pub film_grain: bool,
+	pub vignette: bool,
+	pub dithering: bool,
+	/// Number of sections in each direction that are kept around the Agent.
+	pub view_distance: i64,
+	/// Shrinks the view distance, down to the minimum, when frames take too
+	/// long, and grows it back up to `view_distance` when they do not.
+	pub adaptive_view_distance: bool,
+	/// Language of the menus, the dialogue and the section names. Messages
+	/// missing from it are shown in English.
+	pub language: Language,
+	/// What section names are made like, or `None` for names of the
+	/// language, made of its syllables if it has any.
+	pub name_theme: Option<NameTheme>,
+}
+
+pub const MIN_VIEW_DISTANCE: i64 = 1;
+pub const MAX_VIEW_DISTANCE: i64 = 4;
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self {
+			screen_shake: true,
+			chromatic_aberration: true,
+			pixelation: true,
+			film_grain: true,
+			vignette: true,
+			dithering: false,
+			view_distance: 2,
+			adaptive_view_distance: false,
+			language: Language::English,
+			name_theme: None,
+		}
+	}
+}
+
+impl Settings {
+	pub fn is_effect_enabled(&self, effect: ScreenEffect) -> bool {
+		match effect {
+			ScreenEffect::ScreenShake => self.screen_shake,
+			ScreenEffect::ChromaticAberration => self.chromatic_aberration,
+			ScreenEffect::Pixelation => self.pixelation,
+			ScreenEffect::FilmGrain => self.film_grain,
+			ScreenEffect::Vignette => self.vignette,
+		}
+	}
+
+	pub fn toggle_effect(&mut self, effect: ScreenEffect) {
+		let enabled = match effect {
+			ScreenEffect::ScreenShake => &mut self.screen_shake,
+			ScreenEffect::ChromaticAberration => &mut self.chromatic_aberration,
+			ScreenEffect::Pixelation => &mut self.pixelation,
+			ScreenEffect::FilmGrain => &mut self.film_grain,
+			ScreenEffect::Vignette => &mut self.vignette,
+		};
+		*enabled = !*enabled;
+	}
+
+	/// Steps the view distance up, going back to the minimum after the
+	/// maximum.
+	pub fn cycle_view_distance(&mut self) {
+		self.view_distance = if self.view_distance >= MAX_VIEW_DISTANCE {
+			MIN_VIEW_DISTANCE
+		} else {
+			self.view_distance + 1
+		};
+	}
+
+	/// Steps through the name themes, starting and ending with names of the
+	/// language.
+	pub fn cycle_name_theme(&mut self) {
+		let next = match self.name_theme {
+			None => 0,
+			Some(theme) => NAME_THEMES.iter().position(|t| *t == theme).unwrap_or(0) + 1,
+		};
+		self.name_theme = NAME_THEMES.get(next).copied();
+	}
+}
@@ -0,0 +1,201 @@
+//! Results kept between runs of the game, on disk or in the browser's local
+//! storage.
+
+use std::collections::{btree_map, BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::{daily::Date, game::TICKS_PER_SECOND};
+
+#[cfg(not(target_arch = "wasm32"))]
+const STATS_PATH: &str = "little_game_stats.json";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
+pub struct Stats {
+	/// The first result of each day the Daily Maze was escaped, by date.
+	#[serde(default)]
+	pub daily: BTreeMap<String, DailyResult>,
+	/// The fastest escape from the world of each seed, by seed.
+	#[serde(default)]
+	pub best_runs: BTreeMap<String, BestRun>,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DailyResult {
+	pub coins: usize,
+	pub ticks: u64,
+	pub sections: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BestRun {
+	/// Version of the world the run was in, as it is for replays. Runs from
+	/// other versions of the world are not shown.
+	#[serde(default)]
+	pub version: u8,
+	pub ticks: u64,
+	/// Where the Agent went, encoded as hexadecimal.
+	pub trajectory: String,
+}
+
+impl DailyResult {
+	pub fn seconds(&self) -> u64 {
+		(self.ticks as f32 / TICKS_PER_SECOND) as u64
+	}
+}
+
+impl Stats {
+	/// The stats stored so far, or empty ones if there are none or they could
+	/// not be read.
+	pub fn load() -> Self {
+		read()
+			.and_then(|json| serde_json::from_str(&json).ok())
+			.unwrap_or_default()
+	}
+
+	pub fn save(&self) {
+		write(&serde_json::to_string(self).unwrap());
+	}
+
+	/// Keeps `result` for `date` unless there already is one, since only the
+	/// first try of a Daily Maze counts. Returns whether it was kept.
+	pub fn record_daily(&mut self, date: &Date, result: DailyResult) -> bool {
+		if let btree_map::Entry::Vacant(entry) = self.daily.entry(date.to_string()) {
+			entry.insert(result);
+			true
+		} else {
+			false
+		}
+	}
+
+	/// The best run of `seed`, if it was in the world of `version`.
+	pub fn best_run(&self, seed: u64, version: u8) -> Option<&BestRun> {
+		self.best_runs
+			.get(&seed.to_string())
+			.filter(|run| run.version == version)
+	}
+
+	/// Keeps `run` as the best one of `seed` if it is faster than the one
+	/// kept so far, or that one was in another version of the world. Returns
+	/// whether it was kept.
+	pub fn record_best_run(&mut self, seed: u64, run: BestRun) -> bool {
+		match self.best_runs.entry(seed.to_string()) {
+			btree_map::Entry::Occupied(best)
+				if best.get().version == run.version && best.get().ticks <= run.ticks =>
+			{
+				false
+			}
+			btree_map::Entry::Occupied(mut best) => {
+				best.insert(run);
+				true
+			}
+			btree_map::Entry::Vacant(entry) => {
+				entry.insert(run);
+				true
+			}
+		}
+	}
+}
+
+/// Records the result of a Daily Maze run in the stored stats. Returns
+/// whether it was the first result of `date`.
+pub fn record_daily(date: &Date, result: DailyResult) -> bool {
+	let mut stats = Stats::load();
+	let recorded = stats.record_daily(date, result);
+	if recorded {
+		stats.save();
+	}
+	recorded
+}
+
+/// Records `run` as the best one of `seed` in the stored stats, if it is.
+pub fn record_best_run(seed: u64, run: BestRun) {
+	let mut stats = Stats::load();
+	if stats.record_best_run(seed, run) {
+		stats.save();
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read() -> Option<String> {
+	std::fs::read_to_string(STATS_PATH).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(json: &str) {
+	if let Err(e) = std::fs::write(STATS_PATH, json) {
+		eprintln!("Could not write stats to {}: {}", STATS_PATH, e);
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read() -> Option<String> {
+	super::js::load_stats()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(json: &str) {
+	super::js::save_stats(json);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn only_the_first_daily_result_counts() {
+		let date = Date::from_days(18_779);
+		let first = DailyResult {
+			coins: 2,
+			ticks: 3600,
+			sections: 9,
+		};
+		let second = DailyResult {
+			coins: 7,
+			..first
+		};
+		let mut stats = Stats::default();
+		assert!(stats.record_daily(&date, first));
+		assert!(!stats.record_daily(&date, second));
+		assert_eq!(stats.daily.get("2021-06-01"), Some(&first));
+
+		let json = serde_json::to_string(&stats).unwrap();
+		assert_eq!(serde_json::from_str::<Stats>(&json).unwrap(), stats);
+		assert_eq!(
+			serde_json::from_str::<Stats>("{}").unwrap(),
+			Stats::default()
+		);
+	}
+
+	#[test]
+	fn only_faster_runs_are_best() {
+		let run = |ticks| BestRun {
+			version: 2,
+			ticks,
+			trajectory: String::new(),
+		};
+		let mut stats = Stats::default();
+		assert!(stats.record_best_run(7, run(900)));
+		assert!(!stats.record_best_run(7, run(900)));
+		assert!(!stats.record_best_run(7, run(1000)));
+		assert!(stats.record_best_run(7, run(800)));
+		assert_eq!(stats.best_run(7, 2), Some(&run(800)));
+		assert_eq!(stats.best_run(8, 2), None);
+	}
+
+	#[test]
+	fn runs_of_other_worlds_are_replaced() {
+		let mut stats: Stats =
+			serde_json::from_str(r#"{ "best_runs": { "7": { "ticks": 600, "trajectory": "" } } }"#)
+				.unwrap();
+		assert_eq!(stats.best_run(7, 0).map(|run| run.ticks), Some(600));
+		assert_eq!(stats.best_run(7, 2), None);
+		let newer = BestRun {
+			version: 2,
+			ticks: 900,
+			trajectory: String::new(),
+		};
+		assert!(stats.record_best_run(7, newer.clone()));
+		assert_eq!(stats.best_run(7, 2), Some(&newer));
+	}
+}
@@ -0,0 +1,31 @@
+//! Words that must not turn up in section names, made up as they are.
+
+thread_local! {
+	static BLOCKLIST: Vec<String> = include_str!("./names/blocklist.txt")
+		.lines()
+		.filter(|line| !line.starts_with('#'))
+		.flat_map(str::split_whitespace)
+		.map(str::to_uppercase)
+		.collect();
+}
+
+/// Whether `name` has a blocked word anywhere in it.
+pub fn is_blocked(name: &str) -> bool {
+	let name = name.to_uppercase();
+	BLOCKLIST.with(|blocklist| blocklist.iter().any(|word| name.contains(word.as_str())))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn blocked_words_are_found_anywhere() {
+		assert!(is_blocked("FUCK"));
+		assert!(is_blocked("ASHITOR"));
+		assert!(is_blocked("Ashitor"));
+		assert!(is_blocked("ГРАДСУКА"));
+		assert!(!is_blocked("MAIREVESS"));
+		assert!(!is_blocked("BJÖRBY"));
+	}
+}
@@ -0,0 +1,298 @@
+//! Names made by a Markov chain over letters. A name starts with letters some
+//! word starts with, and each letter after is one that follows the letters
+//! before it somewhere in the words.
+
+use std::{collections::HashMap, fmt};
+
+use rand::{rngs::StdRng, Rng};
+
+use super::NameModel;
+
+/// Dead ends a name may run into before it is given up on. Backing up only a
+/// few letters can lead into the same dead ends again and again, when sparse
+/// words have no way on from the letters left.
+const MAX_DEAD_ENDS: usize = 64;
+
+pub struct MarkovModel {
+	/// How many letters before decide the next.
+	order: usize,
+	/// The first `order` letters of words.
+	starts: Vec<Vec<char>>,
+	/// What follows each run of `order` letters.
+	follow: HashMap<Vec<char>, Follow>,
+}
+
+#[derive(Default)]
+struct Follow {
+	/// Letters that follow in the middle of words.
+	middle: Vec<char>,
+	/// Letters that follow at the end of words.
+	end: Vec<char>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TableError {
+	/// Line of the table that could not be read, counting from one.
+	pub line: usize,
+	pub reason: &'static str,
+}
+
+impl MarkovModel {
+	/// Reads a model from a table. The table gives its `order` first, then
+	/// lists the `start`s of words, and then the letters following each run
+	/// of letters, in the middle and at the end of words, like `TH EAIRO ES`.
+	/// A `-` stands for no letters. Letters earlier in a list are not more
+	/// likely.
+	pub fn parse(table: &str) -> Result<Self, TableError> {
+		let mut order = None;
+		let mut starts = Vec::new();
+		let mut follow = HashMap::new();
+		let mut line_count = 0;
+		for (i, line) in table.lines().enumerate() {
+			line_count = i + 1;
+			let error = |reason| TableError {
+				line: i + 1,
+				reason,
+			};
+			let mut words = line.split_whitespace();
+			let first = match words.next() {
+				Some(word) if !word.starts_with('#') => word,
+				_ => continue,
+			};
+			if first == "order" {
+				order = Some(
+					words
+						.next()
+						.and_then(|n| n.parse().ok())
+						.filter(|n| *n > 0)
+						.ok_or_else(|| error("expected an order above zero"))?,
+				);
+				continue;
+			}
+			let order = order.ok_or_else(|| error("expected the order first"))?;
+			let letters = |word: &str| {
+				let letters: Vec<char> = word.chars().collect();
+				if letters.len() == order {
+					Ok(letters)
+				} else {
+					Err(error("expected as many letters as the order"))
+				}
+			};
+			if first == "start" {
+				for word in words {
+					starts.push(letters(word)?);
+				}
+				continue;
+			}
+			let mut followers = || match words.next() {
+				Some("-") => Ok(Vec::new()),
+				Some(word) => Ok(word.chars().collect()),
+				None => Err(error("expected the letters that follow")),
+			};
+			let followers = Follow {
+				middle: followers()?,
+				end: followers()?,
+			};
+			if follow.insert(letters(first)?, followers).is_some() {
+				return Err(error("the letters are already listed"));
+			}
+		}
+		let order = order.ok_or(TableError {
+			line: line_count,
+			reason: "expected an order",
+		})?;
+		if starts.is_empty() {
+			return Err(TableError {
+				line: line_count,
+				reason: "expected starts",
+			});
+		}
+		Ok(Self {
+			order,
+			starts,
+			follow,
+		})
+	}
+
+	/// Builds a model of `order` from the words of `list`. Words are separated
+	/// by whitespace, lines starting with `#` are comments, and words no longer
+	/// than `order` are skipped. Letters that follow more often are listed
+	/// more often, so they are more likely.
+	pub fn train(list: &str, order: usize) -> Self {
+		let mut starts = Vec::new();
+		let mut follow: HashMap<Vec<char>, Follow> = HashMap::new();
+		let words = list
+			.lines()
+			.filter(|line| !line.starts_with('#'))
+			.flat_map(str::split_whitespace);
+		for word in words {
+			let letters: Vec<char> = word
+				.chars()
+				.filter(|c| c.is_alphabetic())
+				.flat_map(char::to_uppercase)
+				.collect();
+			if letters.len() <= order {
+				continue;
+			}
+			starts.push(letters[..order].to_vec());
+			for i in order..letters.len() {
+				let follow = follow.entry(letters[i - order..i].to_vec()).or_default();
+				if i + 1 == letters.len() {
+					follow.end.push(letters[i]);
+				} else {
+					follow.middle.push(letters[i]);
+				}
+			}
+		}
+		assert!(!starts.is_empty(), "no word is longer than the order");
+		Self {
+			order,
+			starts,
+			follow,
+		}
+	}
+}
+
+impl NameModel for MarkovModel {
+	fn generate(&self, rng: &mut StdRng) -> Option<String> {
+		let desired_length = rng.gen_range(4..10);
+
+		let mut letters: Vec<char> = Vec::with_capacity(desired_length + self.order);
+		let mut dead_ends = 0;
+		while letters.len() < desired_length {
+			if letters.len() < self.order {
+				letters.extend(&self.starts[rng.gen_range(0..self.starts.len())]);
+			} else {
+				let length = letters.len();
+				let choices = self
+					.follow
+					.get(&letters[length - self.order..])
+					.map(|follow| {
+						if length + 1 == desired_length {
+							&follow.end
+						} else {
+							&follow.middle
+						}
+					})
+					.filter(|choices| !choices.is_empty());
+				if let Some(choices) = choices {
+					letters.push(choices[rng.gen_range(0..choices.len())]);
+				} else {
+					dead_ends += 1;
+					if dead_ends > MAX_DEAD_ENDS {
+						return None;
+					}
+					// Back up past the dead end and try another way.
+					letters.truncate(length - (self.order + 1).min(length));
+				}
+			}
+		}
+		Some(letters.into_iter().collect())
+	}
+}
+
+impl fmt::Display for TableError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: {}", self.line, self.reason)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		super::{super::rng::rng_for_maze, section_name, NameTheme, NAME_THEMES},
+		*,
+	};
+
+	/// The first try at the name of the section at `position`.
+	fn name(model: &dyn NameModel, seed: u64, position: (i64, i64)) -> String {
+		model.generate(&mut rng_for_maze(seed, position)).unwrap()
+	}
+
+	#[test]
+	fn english_names_are_unchanged() {
+		let english = NameTheme::English.model();
+		assert_eq!(name(&*english, 0, (0, 0)), "MAIREVESS");
+		assert_eq!(name(&*english, 1, (3, -2)), "EYEDEDIR");
+		assert_eq!(name(&*english, 42, (-7, 11)), "FURROTIS");
+		assert_eq!(name(&*english, u64::MAX, (100, 100)), "ADERNMEA");
+		assert_eq!(name(&*english, 123456789, (-1, -1)), "PLIEL");
+
+		// FNV-1a of many names, as they were made before the model was read
+		// from a table.
+		let mut names = String::new();
+		for seed in 0..20 {
+			for x in -10..10 {
+				for z in -10..10 {
+					names.push_str(&name(&*english, seed, (x, z)));
+					names.push(' ');
+				}
+			}
+		}
+		let hash = names.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+			(hash ^ b as u64).wrapping_mul(0x100000001b3)
+		});
+		assert_eq!(names.len(), 59966);
+		assert_eq!(hash, 0x43d81baa62be7f9e);
+	}
+
+	#[test]
+	fn trained_models_follow_their_words() {
+		let model = MarkovModel::train("# Not a word\nBANANA\nbandana", 2);
+		assert_eq!(model.starts, [vec!['B', 'A'], vec!['B', 'A']]);
+		assert_eq!(model.follow[&vec!['A', 'N']].middle, ['A', 'D']);
+		assert_eq!(model.follow[&vec!['A', 'N']].end, ['A', 'A']);
+		assert!(model.follow[&vec!['N', 'A']].end.is_empty());
+
+		let model = MarkovModel::train("BANANA", 3);
+		assert_eq!(model.follow[&vec!['A', 'N', 'A']].middle, ['N']);
+		assert!(model.follow[&vec!['A', 'N', 'A']].end.is_empty());
+		assert_eq!(model.follow[&vec!['N', 'A', 'N']].end, ['A']);
+	}
+
+	#[test]
+	fn themes_make_names() {
+		for theme in NAME_THEMES.iter() {
+			let model = theme.model();
+			for x in -20..20 {
+				let name = section_name(&*model, 7, (x, -x));
+				assert!((4..10 + model.order).contains(&name.chars().count()));
+				assert!(name.chars().all(char::is_alphabetic), "{}", name);
+			}
+		}
+	}
+
+	#[test]
+	fn table_errors_point_at_the_line() {
+		let error = |table| MarkovModel::parse(table).err();
+		assert_eq!(
+			error("start AB\n"),
+			Some(TableError {
+				line: 1,
+				reason: "expected the order first",
+			})
+		);
+		assert_eq!(
+			error("order 2\nstart AB ABC\n"),
+			Some(TableError {
+				line: 2,
+				reason: "expected as many letters as the order",
+			})
+		);
+		assert_eq!(
+			error("order 2\nstart AB\nAB CD\n"),
+			Some(TableError {
+				line: 3,
+				reason: "expected the letters that follow",
+			})
+		);
+		assert_eq!(
+			error("# Nothing\norder 2\n"),
+			Some(TableError {
+				line: 2,
+				reason: "expected starts",
+			})
+		);
+		assert!(MarkovModel::parse("order 2\nstart AB\nAB C -\n").is_ok());
+	}
+}
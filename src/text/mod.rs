@@ -0,0 +1,275 @@
+//! Names of sections, made up from the seed of the world and where they are.
+
+mod blocklist;
+mod markov;
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use rand::{rngs::StdRng, Rng};
+
+use self::blocklist::is_blocked;
+pub use self::markov::MarkovModel;
+use super::rng::rng_for_maze;
+
+/// Tries at a name for a section before it is named by its position instead.
+const MAX_TRIES: usize = 16;
+
+/// Makes up names for sections.
+pub trait NameModel {
+	/// Makes up a name with `rng`, or gives up if it cannot within a bounded
+	/// number of steps.
+	fn generate(&self, rng: &mut StdRng) -> Option<String>;
+}
+
+/// Kinds of names for sections, made by models of words like them.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NameTheme {
+	English,
+	Latin,
+	Nordic,
+	Eldritch,
+}
+
+pub const NAME_THEMES: [NameTheme; 4] = [
+	NameTheme::English,
+	NameTheme::Latin,
+	NameTheme::Nordic,
+	NameTheme::Eldritch,
+];
+
+thread_local! {
+	static MODELS: RefCell<HashMap<NameTheme, Rc<MarkovModel>>> = RefCell::default();
+}
+
+impl NameTheme {
+	/// Id of the message naming the theme.
+	pub fn message(self) -> &'static str {
+		match self {
+			Self::English => "names-english",
+			Self::Latin => "names-latin",
+			Self::Nordic => "names-nordic",
+			Self::Eldritch => "names-eldritch",
+		}
+	}
+
+	/// The model names of this theme are made by, built the first time it is
+	/// asked for.
+	pub fn model(self) -> Rc<MarkovModel> {
+		MODELS.with(|models| {
+			models
+				.borrow_mut()
+				.entry(self)
+				.or_insert_with(|| Rc::new(self.build()))
+				.clone()
+		})
+	}
+
+	fn build(self) -> MarkovModel {
+		match self {
+			Self::English => MarkovModel::parse(include_str!("./names/english.txt"))
+				.unwrap_or_else(|e| panic!("the English names are invalid, {}", e)),
+			Self::Latin => MarkovModel::train(include_str!("./names/latin.txt"), 2),
+			Self::Nordic => MarkovModel::train(include_str!("./names/nordic.txt"), 3),
+			Self::Eldritch => MarkovModel::train(include_str!("./names/eldritch.txt"), 2),
+		}
+	}
+}
+
+/// Appends the English name of the section at `position` to `name`.
+pub fn generate_name(seed: u64, position: (i64, i64), name: &mut String) {
+	name.push_str(&section_name(&*NameTheme::English.model(), seed, position));
+}
+
+/// The name `model` gives the section at `position` in the world of `seed`.
+/// Names with blocked words in them are made again, and so are names on odd
+/// squares that the section next to them already has, so that no two sections
+/// next to each other share a name. Each try goes on with the random numbers
+/// of the one before, so the same section always gets the same name.
+pub fn section_name(model: &dyn NameModel, seed: u64, position: (i64, i64)) -> String {
+	let (x, z) = position;
+	if (x ^ z) & 1 == 0 {
+		return name_where(model, seed, position, |_| true);
+	}
+	let neighbours: Vec<_> = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+		.iter()
+		.map(|(dx, dz)| {
+			let neighbour = (x.wrapping_add(*dx), z.wrapping_add(*dz));
+			name_where(model, seed, neighbour, |_| true)
+		})
+		.collect();
+	name_where(model, seed, position, |name| {
+		!neighbours.iter().any(|n| n == name)
+	})
+}
+
+/// The first name `model` makes for the section at `position` that is not
+/// blocked and is `acceptable`. The position itself if there is none in
+/// [`MAX_TRIES`], since no two sections have that.
+fn name_where(
+	model: &dyn NameModel,
+	seed: u64,
+	position: (i64, i64),
+	acceptable: impl Fn(&str) -> bool,
+) -> String {
+	let mut rng: StdRng = rng_for_maze(seed, position);
+	(0..MAX_TRIES)
+		.filter_map(|_| model.generate(&mut rng))
+		.find(|name| !is_blocked(name) && acceptable(name))
+		.unwrap_or_else(|| format!("{}, {}", position.0, position.1))
+}
+
+/// The parts names are put together from in a language, a first and a last
+/// syllable with up to two in between.
+pub struct Syllables {
+	first: &'static [&'static str],
+	middle: &'static [&'static str],
+	last: &'static [&'static str],
+}
+
+pub const SWEDISH_SYLLABLES: Syllables = Syllables {
+	first: &[
+		"BJÖR", "STEN", "ÅL", "HÄL", "MOS", "LIN", "GRAN", "EK", "SVAR", "TOR", "RÅ", "VÄS", "KUL",
+		"MÅR", "ÖS", "SJÖ", "BERG", "FRÖ", "GÄV", "LUN", "SKÄR", "TRÄ", "ALV", "DAL",
+	],
+	middle: &["A", "E", "RE", "LA", "STA", "BY", "Ö", "NE", "VI", "KA"],
+	last: &[
+		"BY", "HOLM", "STAD", "VIK", "SJÖ", "NÄS", "ÅS", "BERG", "DAL", "TORP", "LUND", "FORS",
+		"MO", "RYD", "HULT", "Ö", "KÖPING", "ED",
+	],
+};
+
+pub const RUSSIAN_SYLLABLES: Syllables = Syllables {
+	first: &[
+		"ЛЕС", "КРАС", "БЕЛ", "ЧЕР", "ТОР", "ВЕЛ", "НОВ", "ЗВЕН", "ГОР", "ЯР", "ЛУГ", "ВОЛ",
+		"СОСН", "КАМ", "ТИХ", "ДУБ", "ОЗЁР", "ЖЕЛ",
+	],
+	middle: &["О", "Е", "ИН", "ОВ", "А", "ЕЛ", "ЯН"],
+	last: &[
+		"ГРАД",
+		"ОВО",
+		"ЕВО",
+		"ИНО",
+		"СК",
+		"ГОРОД",
+		"ПОЛЬ",
+		"ЁВКА",
+		"ОВКА",
+		"ЕЦ",
+		"ИЧИ",
+		"ЯНКА",
+		"МОСТ",
+	],
+};
+
+impl Syllables {
+	/// Every syllable, wherever in a name it goes.
+	#[cfg(test)]
+	pub fn all(&self) -> impl Iterator<Item = &'static str> {
+		self.first
+			.iter()
+			.chain(self.middle.iter())
+			.chain(self.last.iter())
+			.copied()
+	}
+}
+
+impl NameModel for Syllables {
+	fn generate(&self, rng: &mut StdRng) -> Option<String> {
+		let middles = rng.gen_range(0..3);
+		let mut name = self.first[rng.gen_range(0..self.first.len())].to_string();
+		for _ in 0..middles {
+			name.push_str(self.middle[rng.gen_range(0..self.middle.len())]);
+		}
+		name.push_str(self.last[rng.gen_range(0..self.last.len())]);
+		Some(name)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::ops::Range;
+
+	use super::*;
+
+	struct Stuck;
+
+	impl NameModel for Stuck {
+		fn generate(&self, _: &mut StdRng) -> Option<String> {
+			None
+		}
+	}
+
+	struct Always(&'static str);
+
+	impl NameModel for Always {
+		fn generate(&self, _: &mut StdRng) -> Option<String> {
+			Some(self.0.to_string())
+		}
+	}
+
+	/// Checks that no name of `model` in `seeds` and `reach` sections from
+	/// the middle is blocked or shared with a section next to it.
+	fn check_names(model: &dyn NameModel, seeds: Range<u64>, reach: i64) {
+		let side = 2 * reach as usize + 1;
+		for seed in seeds {
+			let names: Vec<_> = (0..side * side)
+				.map(|i| {
+					let position = ((i / side) as i64 - reach, (i % side) as i64 - reach);
+					section_name(model, seed, position)
+				})
+				.collect();
+			for (i, name) in names.iter().enumerate() {
+				assert!(!is_blocked(name), "{} is blocked", name);
+				let (x, z) = (i / side, i % side);
+				if x + 1 < side {
+					assert_ne!(name, &names[i + side], "in {} at {:?}", seed, (x, z));
+				}
+				if z + 1 < side {
+					assert_ne!(name, &names[i + 1], "in {} at {:?}", seed, (x, z));
+				}
+			}
+		}
+	}
+
+	fn models() -> Vec<Rc<dyn NameModel>> {
+		let mut models: Vec<Rc<dyn NameModel>> = NAME_THEMES
+			.iter()
+			.map(|theme| theme.model() as Rc<dyn NameModel>)
+			.collect();
+		models.push(Rc::new(SWEDISH_SYLLABLES));
+		models.push(Rc::new(RUSSIAN_SYLLABLES));
+		models
+	}
+
+	#[test]
+	fn names_are_fit_for_sections() {
+		for model in models() {
+			check_names(&*model, 0..4, 12);
+		}
+	}
+
+	/// Takes a while, run with `cargo test --release -- --ignored`.
+	#[test]
+	#[ignore]
+	fn millions_of_names_are_fit_for_sections() {
+		check_names(&*NameTheme::English.model(), 0..64, 128);
+		for model in models() {
+			check_names(&*model, 1000..1016, 128);
+		}
+	}
+
+	#[test]
+	fn names_are_made_again_until_they_fit() {
+		assert_eq!(section_name(&Stuck, 1, (3, -2)), "3, -2");
+		assert_eq!(section_name(&Always("SHIT"), 1, (0, 0)), "0, 0");
+		assert_eq!(section_name(&Always("SAME"), 1, (0, 0)), "SAME");
+		assert_eq!(section_name(&Always("SAME"), 1, (0, 1)), "0, 1");
+		check_names(&Always("SAME"), 0..1, 3);
+
+		let english = NameTheme::English.model();
+		for x in -10..10 {
+			let name = section_name(&*english, 5, (x, 2 * x));
+			assert_eq!(name, section_name(&*english, 5, (x, 2 * x)));
+		}
+	}
+}
@@ -0,0 +1,118 @@
+use std::cell::Cell;
+
+use kiss3d::nalgebra::Point3;
+
+use super::hsl_to_rgb;
+
+thread_local! {
+	static FOG: Cell<FogSettings> = Cell::new(FogSettings::default());
+}
+
+/// Sets the fog used by every object rendered with the `pixel` material.
+pub fn set_fog(fog: FogSettings) {
+	FOG.with(|f| f.set(fog));
+}
+
+/// The fog last set with [`set_fog`].
+pub fn fog() -> FogSettings {
+	FOG.with(|f| f.get())
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FogMode {
+	/// Fog grows linearly with the distance past `start`.
+	Linear,
+	/// Fog grows exponentially with the distance past `start`.
+	Exponential,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FogSettings {
+	pub mode: FogMode,
+	pub color: Point3<f32>,
+	/// Distance from the camera where the fog starts.
+	pub start: f32,
+	/// How quickly the fog thickens past `start`.
+	pub density: f32,
+	/// Distance from the camera where everything is hidden by fog, whatever
+	/// the mode, so that sections coming into view are not seen popping in.
+	pub end: f32,
+	/// Number of colour levels per channel, with ordered dithering between
+	/// them. `0` disables dithering.
+	pub dither_levels: u8,
+}
+
+impl Default for FogSettings {
+	fn default() -> Self {
+		Self {
+			mode: FogMode::Linear,
+			color: Point3::new(0.0, 0.0, 0.0),
+			start: 0.0,
+			density: 0.1,
+			end: 1024.0,
+			dither_levels: 0,
+		}
+	}
+}
+
+const EXPONENTIAL_EXPOSURE: f32 = 1.0;
+
+impl FogSettings {
+	/// Fog for a section with the given hue, getting thicker and more
+	/// exponential the higher the exposure.
+	pub fn for_section(hue: f32, exposure: f32) -> Self {
+		let exposure = exposure.max(0.0);
+		let (r, g, b) = hsl_to_rgb(hue, 0.3, 0.02 + 0.04 * exposure.min(1.0));
+		Self {
+			mode: if exposure < EXPONENTIAL_EXPOSURE {
+				FogMode::Linear
+			} else {
+				FogMode::Exponential
+			},
+			color: Point3::new(r, g, b),
+			start: (1.0 - exposure).max(0.0),
+			density: 0.1 + 0.1 * exposure.min(2.0),
+			..Self::default()
+		}
+	}
+
+	/// Moves these settings `t` of the way towards `target`. The mode and
+	/// dithering are switched directly.
+	pub fn lerp(&self, target: &Self, t: f32) -> Self {
+		let t = t.clamp(0.0, 1.0);
+		Self {
+			mode: target.mode,
+			color: self.color + (target.color - self.color) * t,
+			start: self.start + (target.start - self.start) * t,
+			density: self.density + (target.density - self.density) * t,
+			end: self.end + (target.end - self.end) * t,
+			dither_levels: target.dither_levels,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lerp_reaches_its_endpoints() {
+		let from = FogSettings::for_section(0.2, 0.0);
+		let to = FogSettings::for_section(0.7, 2.0);
+		let start = from.lerp(&to, 0.0);
+		assert_eq!(start.color, from.color);
+		assert_eq!(start.start, from.start);
+		assert_eq!(start.density, from.density);
+		assert_eq!(from.lerp(&to, 1.0), to);
+	}
+
+	#[test]
+	fn higher_exposure_gives_thicker_fog() {
+		let low = FogSettings::for_section(0.5, 0.2);
+		let high = FogSettings::for_section(0.5, 1.5);
+		assert!(high.start < low.start);
+		assert!(high.density > low.density);
+		assert_eq!(low.mode, FogMode::Linear);
+		assert_eq!(high.mode, FogMode::Exponential);
+	}
+}
@@ -9,6 +9,8 @@ use kiss3d::{
 	scene::ObjectData,
 };
 
+use super::fog::{fog, FogMode};
+
 pub fn init_materials() {
 	MaterialManager::get_global_manager(add_materials);
 }
@@ -30,6 +32,12 @@ struct PixelMaterial {
 	n_transform: ShaderUniform<Matrix3<f32>>,
 	view: ShaderUniform<Matrix4<f32>>,
 	proj: ShaderUniform<Matrix4<f32>>,
+	fog_color: ShaderUniform<Point3<f32>>,
+	fog_start: ShaderUniform<f32>,
+	fog_density: ShaderUniform<f32>,
+	fog_exponential: ShaderUniform<f32>,
+	fog_end: ShaderUniform<f32>,
+	dither_levels: ShaderUniform<f32>,
 	effect: Effect,
 }
 
@@ -46,6 +54,12 @@ impl PixelMaterial {
 			n_transform: effect.get_uniform("n_transform").unwrap(),
 			view: effect.get_uniform("view").unwrap(),
 			proj: effect.get_uniform("proj").unwrap(),
+			fog_color: effect.get_uniform("fog_color").unwrap(),
+			fog_start: effect.get_uniform("fog_start").unwrap(),
+			fog_density: effect.get_uniform("fog_density").unwrap(),
+			fog_exponential: effect.get_uniform("fog_exponential").unwrap(),
+			fog_end: effect.get_uniform("fog_end").unwrap(),
+			dither_levels: effect.get_uniform("dither_levels").unwrap(),
 			effect,
 		}
 	}
@@ -88,6 +102,17 @@ impl Material for PixelMaterial {
 		self.n_transform.upload(&formatted_n_transform);
 		self.scale.upload(&formatted_scale);
 
+		let fog = fog();
+		self.fog_color.upload(&fog.color);
+		self.fog_start.upload(&fog.start);
+		self.fog_density.upload(&fog.density);
+		self.fog_exponential.upload(&match fog.mode {
+			FogMode::Linear => 0.0,
+			FogMode::Exponential => 1.0,
+		});
+		self.fog_end.upload(&fog.end);
+		self.dither_levels.upload(&(fog.dither_levels as f32));
+
 		mesh.bind(&mut self.pos, &mut self.normal, &mut self.tex_coord);
 
 		ctx.active_texture(Context::TEXTURE0);
@@ -1,7 +1,9 @@
+mod fog;
 mod materials;
 
 use kiss3d::resource::TextureManager;
 
+pub use self::fog::{set_fog, FogSettings};
 use self::materials::init_materials;
 
 pub fn init_textures() {
@@ -14,6 +16,7 @@ fn add_textures(manager: &mut TextureManager) {
 	manager.add_image_from_memory(include_bytes!("./ceiling.png"), "ceiling");
 	manager.add_image_from_memory(include_bytes!("./floor.png"), "floor");
 	manager.add_image_from_memory(include_bytes!("./monster.png"), "monster");
+	manager.add_image_from_memory(include_bytes!("./ghost.png"), "ghost");
 }
 
 pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
@@ -11,6 +11,21 @@ varying vec3 vertPos;
 
 uniform vec3 color;
 uniform sampler2D tex;
+uniform vec3 fog_color;
+uniform float fog_start;
+uniform float fog_density;
+uniform float fog_exponential;
+uniform float fog_end;
+uniform float dither_levels;
+
+float bayer2(vec2 a) {
+	a = floor(a);
+	return fract(dot(a, vec2(0.5, a.y * 0.75)));
+}
+
+float bayer4(vec2 a) {
+	return bayer2(0.5 * a) * 0.25 + bayer2(a);
+}
 
 void main() {
 	vec3 normal = normalize(normalInterp);
@@ -19,5 +34,18 @@ void main() {
 	float lambertian = max(dot(lightDir, normal), 0.0);
 
 	vec4 tex_color = texture2D(tex, tex_coord_v);
-	gl_FragColor = tex_color * vec4(color + lambertian - length(vertPos) / 10.0 - lambertian, 1.0);
+	vec3 lit = tex_color.rgb * (color + lambertian - lambertian);
+
+	float fog_distance = max(length(vertPos) - fog_start, 0.0);
+	float fog_amount = fog_exponential > 0.5
+		? 1.0 - exp(-fog_distance * fog_density)
+		: fog_distance * fog_density;
+	fog_amount = max(fog_amount, smoothstep(fog_end * 0.75, fog_end, length(vertPos)));
+	vec3 result = mix(lit, fog_color, clamp(fog_amount, 0.0, 1.0));
+
+	if (dither_levels > 0.0) {
+		result = floor(result * dither_levels + bayer4(gl_FragCoord.xy)) / dither_levels;
+	}
+
+	gl_FragColor = vec4(result, tex_color.a);
 }
@@ -1,7 +1,11 @@
+const STATS_KEY = "little_game_stats";
+
 export class Game {
 	private readonly canvas: HTMLCanvasElement;
 	private movement: JsVector2 = new JsVector2();
 	private hasFocus: boolean = false;
+	private socket: WebSocket | null = null;
+	private received: string[] = [];
 
 	public constructor(canvas: HTMLCanvasElement) {
 		this.canvas = canvas;
@@ -11,6 +15,11 @@ export class Game {
 		this.get_cursor_movement = this.get_cursor_movement.bind(this);
 		this.onPointerLock = this.onPointerLock.bind(this);
 		this.get_focus = this.get_focus.bind(this);
+		this.coop_open = this.coop_open.bind(this);
+		this.coop_send = this.coop_send.bind(this);
+		this.coop_receive = this.coop_receive.bind(this);
+		this.coop_is_closed = this.coop_is_closed.bind(this);
+		this.coop_close = this.coop_close.bind(this);
 
 		this.canvas.addEventListener("mousemove", this.onMouseMove, true);
 		document.addEventListener("pointerlockchange", this.onPointerLock, true);
@@ -48,6 +57,49 @@ export class Game {
 	public get_focus(): boolean {
 		return this.hasFocus;
 	}
+
+	public get_language(): string {
+		return navigator.language;
+	}
+
+	public load_stats(): string | null {
+		return window.localStorage.getItem(STATS_KEY);
+	}
+
+	public save_stats(stats: string): void {
+		window.localStorage.setItem(STATS_KEY, stats);
+	}
+
+	public coop_open(url: string): void {
+		this.coop_close();
+		const socket = new WebSocket(url);
+		socket.addEventListener("message", e => {
+			if (this.socket === socket && typeof e.data === "string") {
+				this.received.push(e.data);
+			}
+		});
+		this.socket = socket;
+	}
+
+	public coop_send(message: string): void {
+		if (this.socket?.readyState === WebSocket.OPEN) {
+			this.socket.send(message);
+		}
+	}
+
+	public coop_receive(): string | null {
+		return this.received.shift() ?? null;
+	}
+
+	public coop_is_closed(): boolean {
+		return this.socket === null || this.socket.readyState >= WebSocket.CLOSING;
+	}
+
+	public coop_close(): void {
+		this.socket?.close();
+		this.socket = null;
+		this.received = [];
+	}
 }
 
 class JsVector2 {